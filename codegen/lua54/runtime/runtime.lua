@@ -0,0 +1,1271 @@
+local module = {}
+
+local math_floor = math.floor
+local math_fmod = math.fmod
+local math_to_integer = math.tointeger
+local math_ult = math.ult
+
+local string_byte = string.byte
+local string_char = string.char
+local string_pack = string.pack
+local string_sub = string.sub
+local string_unpack = string.unpack
+
+local table_unpack = table.unpack
+
+local function wrap_i32(num)
+	return ((num + 0x80000000) & 0xFFFFFFFF) - 0x80000000
+end
+
+local function truncate_f64(num)
+	if num >= 0 then
+		return (math_floor(num))
+	else
+		return -math_floor(-num)
+	end
+end
+
+-- The `math` rounding functions hand back integers whenever they can,
+-- so the results are turned back into floats and keep the sign of zero
+local function keep_float(result, num)
+	if result == 0 and 1 / num < 0 then
+		return -0.0
+	else
+		return result + 0.0
+	end
+end
+
+-- Traps are raised as one of these tables, so hosts can `pcall` and
+-- branch on `kind`. Errors raised by Lua itself still come through
+-- as strings.
+local trap = {}
+
+do
+	local trap_meta = {
+		__tostring = function(trap)
+			return trap.message
+		end,
+	}
+
+	local function add(kind, message)
+		trap[kind] = setmetatable({ kind = kind, message = message }, trap_meta)
+	end
+
+	add("unreachable", "unreachable executed")
+	add("integer_divide_by_zero", "integer divide by zero")
+	add("integer_overflow", "integer overflow")
+	add("invalid_conversion_to_integer", "invalid conversion to integer")
+	add("out_of_bounds_memory_access", "out of bounds memory access")
+	add("out_of_bounds_table_access", "out of bounds table access")
+	add("undefined_element", "undefined element")
+	add("uninitialized_element", "uninitialized element")
+	add("indirect_call_type_mismatch", "indirect call type mismatch")
+
+	module.trap = trap
+end
+
+do
+	local add = {}
+	local sub = {}
+	local mul = {}
+	local div = {}
+	local rem = {}
+	local neg = {}
+	local min = {}
+	local max = {}
+	local copysign = {}
+	local floor = {}
+	local ceil = {}
+	local truncate = {}
+	local nearest = {}
+
+	local math_abs = math.abs
+
+	function add.i32(lhs, rhs)
+		return (wrap_i32(lhs + rhs))
+	end
+
+	function sub.i32(lhs, rhs)
+		return (wrap_i32(lhs - rhs))
+	end
+
+	function mul.i32(lhs, rhs)
+		return (wrap_i32(lhs * rhs))
+	end
+
+	local function div_checked(lhs, rhs, min)
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		elseif lhs == min and rhs == -1 then
+			error(trap.integer_overflow)
+		end
+
+		local result = lhs // rhs
+
+		if result < 0 and result * rhs ~= lhs then
+			result = result + 1
+		end
+
+		return result
+	end
+
+	function div.i32(lhs, rhs)
+		return (div_checked(lhs, rhs, -0x80000000))
+	end
+
+	function div.i64(lhs, rhs)
+		return (div_checked(lhs, rhs, math.mininteger))
+	end
+
+	function div.u32(lhs, rhs)
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		end
+
+		return (wrap_i32((lhs & 0xFFFFFFFF) // (rhs & 0xFFFFFFFF)))
+	end
+
+	function div.u64(lhs, rhs)
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		elseif rhs < 0 then
+			if math_ult(lhs, rhs) then
+				return 0
+			else
+				return 1
+			end
+		elseif lhs >= 0 then
+			return lhs // rhs
+		end
+
+		local result = ((lhs >> 1) // rhs) << 1
+
+		if not math_ult(lhs - result * rhs, rhs) then
+			result = result + 1
+		end
+
+		return result
+	end
+
+	function rem.i32(lhs, rhs)
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		end
+
+		return (math_fmod(lhs, rhs))
+	end
+
+	rem.i64 = rem.i32
+
+	function rem.u32(lhs, rhs)
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		end
+
+		return (wrap_i32((lhs & 0xFFFFFFFF) % (rhs & 0xFFFFFFFF)))
+	end
+
+	function rem.u64(lhs, rhs)
+		return lhs - div.u64(lhs, rhs) * rhs
+	end
+
+	function neg.f32(num)
+		return -num
+	end
+
+	-- Unlike `math.min` and `math.max`, a NaN on either side wins and -0
+	-- is ordered below 0. Adding the operands quiets a signaling NaN.
+	function min.f32(lhs, rhs)
+		if lhs ~= lhs or rhs ~= rhs then
+			return lhs + rhs
+		elseif lhs == rhs then
+			if 1 / lhs < 0 then
+				return lhs
+			else
+				return rhs
+			end
+		elseif lhs < rhs then
+			return lhs
+		else
+			return rhs
+		end
+	end
+
+	function max.f32(lhs, rhs)
+		if lhs ~= lhs or rhs ~= rhs then
+			return lhs + rhs
+		elseif lhs == rhs then
+			if 1 / lhs < 0 then
+				return rhs
+			else
+				return lhs
+			end
+		elseif lhs > rhs then
+			return lhs
+		else
+			return rhs
+		end
+	end
+
+	function copysign.f32(lhs, rhs)
+		if string_byte(string_pack("<d", rhs), 8) >= 0x80 then
+			return -math_abs(lhs)
+		else
+			return (math_abs(lhs))
+		end
+	end
+
+	function floor.f32(num)
+		return (keep_float(math_floor(num), num))
+	end
+
+	function ceil.f32(num)
+		return (keep_float(-math_floor(-num), num))
+	end
+
+	function truncate.f32(num)
+		return (keep_float(truncate_f64(num), num))
+	end
+
+	function nearest.f32(num)
+		local result = math_floor(num)
+		local diff = num - result
+
+		if diff > 0.5 or (diff == 0.5 and result % 2 == 1) then
+			result = result + 1
+		end
+
+		return (keep_float(result, num))
+	end
+
+	neg.f64 = neg.f32
+	min.f64 = min.f32
+	max.f64 = max.f32
+	copysign.f64 = copysign.f32
+	floor.f64 = floor.f32
+	ceil.f64 = ceil.f32
+	truncate.f64 = truncate.f32
+	nearest.f64 = nearest.f32
+
+	module.add = add
+	module.sub = sub
+	module.mul = mul
+	module.div = div
+	module.rem = rem
+	module.min = min
+	module.max = max
+	module.neg = neg
+	module.copysign = copysign
+	module.floor = floor
+	module.ceil = ceil
+	module.truncate = truncate
+	module.nearest = nearest
+end
+
+do
+	local clz = {}
+	local ctz = {}
+	local popcnt = {}
+
+	function clz.i64(num)
+		if num == 0 then
+			return 64
+		end
+
+		local count = 0
+
+		if num & 0xFFFFFFFF00000000 == 0 then
+			num = num << 32
+			count = count + 32
+		end
+
+		if num & 0xFFFF000000000000 == 0 then
+			num = num << 16
+			count = count + 16
+		end
+
+		if num & 0xFF00000000000000 == 0 then
+			num = num << 8
+			count = count + 8
+		end
+
+		if num & 0xF000000000000000 == 0 then
+			num = num << 4
+			count = count + 4
+		end
+
+		if num & 0xC000000000000000 == 0 then
+			num = num << 2
+			count = count + 2
+		end
+
+		if num & 0x8000000000000000 == 0 then
+			count = count + 1
+		end
+
+		return count
+	end
+
+	function ctz.i64(num)
+		if num == 0 then
+			return 64
+		end
+
+		local count = 0
+
+		if num & 0xFFFFFFFF == 0 then
+			num = num >> 32
+			count = count + 32
+		end
+
+		if num & 0xFFFF == 0 then
+			num = num >> 16
+			count = count + 16
+		end
+
+		if num & 0xFF == 0 then
+			num = num >> 8
+			count = count + 8
+		end
+
+		if num & 0xF == 0 then
+			num = num >> 4
+			count = count + 4
+		end
+
+		if num & 0x3 == 0 then
+			num = num >> 2
+			count = count + 2
+		end
+
+		if num & 0x1 == 0 then
+			count = count + 1
+		end
+
+		return count
+	end
+
+	function popcnt.i64(num)
+		local count = 0
+
+		while num ~= 0 do
+			num = num & (num - 1)
+			count = count + 1
+		end
+
+		return count
+	end
+
+	function clz.i32(num)
+		return clz.i64(num & 0xFFFFFFFF) - 32
+	end
+
+	function ctz.i32(num)
+		if num == 0 then
+			return 32
+		else
+			return (ctz.i64(num))
+		end
+	end
+
+	function popcnt.i32(num)
+		return (popcnt.i64(num & 0xFFFFFFFF))
+	end
+
+	module.clz = clz
+	module.ctz = ctz
+	module.popcnt = popcnt
+end
+
+do
+	local shl = {}
+	local shr = {}
+	local rotl = {}
+	local rotr = {}
+
+	function shl.i32(lhs, rhs)
+		return (wrap_i32(lhs << (rhs & 31)))
+	end
+
+	function shr.i32(lhs, rhs)
+		return lhs // (1 << (rhs & 31))
+	end
+
+	function shr.u32(lhs, rhs)
+		return (wrap_i32((lhs & 0xFFFFFFFF) >> (rhs & 31)))
+	end
+
+	function rotl.i32(lhs, rhs)
+		local num = lhs & 0xFFFFFFFF
+		local amount = rhs & 31
+
+		return (wrap_i32((num << amount) | (num >> (32 - amount))))
+	end
+
+	function rotr.i32(lhs, rhs)
+		local num = lhs & 0xFFFFFFFF
+		local amount = rhs & 31
+
+		return (wrap_i32((num >> amount) | (num << (32 - amount))))
+	end
+
+	function shl.i64(lhs, rhs)
+		return lhs << (rhs & 63)
+	end
+
+	function shr.i64(lhs, rhs)
+		if lhs >= 0 then
+			return lhs >> (rhs & 63)
+		else
+			return ~(~lhs >> (rhs & 63))
+		end
+	end
+
+	function shr.u64(lhs, rhs)
+		return lhs >> (rhs & 63)
+	end
+
+	function rotl.i64(lhs, rhs)
+		local amount = rhs & 63
+
+		return (lhs << amount) | (lhs >> (64 - amount))
+	end
+
+	function rotr.i64(lhs, rhs)
+		local amount = rhs & 63
+
+		return (lhs >> amount) | (lhs << (64 - amount))
+	end
+
+	module.shl = shl
+	module.shr = shr
+	module.rotl = rotl
+	module.rotr = rotr
+end
+
+do
+	local le = {}
+	local lt = {}
+	local ge = {}
+	local gt = {}
+
+	function le.u32(lhs, rhs)
+		return (lhs & 0xFFFFFFFF) <= (rhs & 0xFFFFFFFF)
+	end
+
+	function lt.u32(lhs, rhs)
+		return (lhs & 0xFFFFFFFF) < (rhs & 0xFFFFFFFF)
+	end
+
+	function ge.u32(lhs, rhs)
+		return (lhs & 0xFFFFFFFF) >= (rhs & 0xFFFFFFFF)
+	end
+
+	function gt.u32(lhs, rhs)
+		return (lhs & 0xFFFFFFFF) > (rhs & 0xFFFFFFFF)
+	end
+
+	function le.u64(lhs, rhs)
+		return not math_ult(rhs, lhs)
+	end
+
+	function lt.u64(lhs, rhs)
+		return math_ult(lhs, rhs)
+	end
+
+	function ge.u64(lhs, rhs)
+		return not math_ult(lhs, rhs)
+	end
+
+	function gt.u64(lhs, rhs)
+		return math_ult(rhs, lhs)
+	end
+
+	module.le = le
+	module.lt = lt
+	module.ge = ge
+	module.gt = gt
+end
+
+do
+	local wrap = {}
+	local truncate = module.truncate
+	local saturate = {}
+	local extend = {}
+	local convert = {}
+	local promote = {}
+	local demote = {}
+	local reinterpret = {}
+
+	local NUM_BIT_63 = 0x1p63
+
+	-- Rounds to the nearest `f32` by going through its encoding
+	local function round_f32(num)
+		return (string_unpack("<f", string_pack("<f", num)))
+	end
+
+	local function truncate_checked(num, min, max)
+		if num ~= num then
+			error(trap.invalid_conversion_to_integer)
+		end
+
+		num = truncate_f64(num)
+
+		if num < min or num >= max then
+			error(trap.integer_overflow)
+		end
+
+		return num
+	end
+
+	function wrap.i32_i64(num)
+		return (wrap_i32(num))
+	end
+
+	function truncate.i32_f32(num)
+		return (math_to_integer(truncate_checked(num, -0x80000000, 0x80000000)))
+	end
+
+	function truncate.u32_f32(num)
+		local result = math_to_integer(truncate_checked(num, 0, 0x100000000))
+
+		return (wrap_i32(result))
+	end
+
+	function truncate.i64_f32(num)
+		return (math_to_integer(truncate_checked(num, -NUM_BIT_63, NUM_BIT_63)))
+	end
+
+	function truncate.u64_f32(num)
+		local result = truncate_checked(num, 0, 0x1p64)
+
+		if result >= NUM_BIT_63 then
+			return math_to_integer(result - NUM_BIT_63) | math.mininteger
+		else
+			return (math_to_integer(result))
+		end
+	end
+
+	truncate.i32_f64 = truncate.i32_f32
+	truncate.u32_f64 = truncate.u32_f32
+	truncate.i64_f64 = truncate.i64_f32
+	truncate.u64_f64 = truncate.u64_f32
+
+	function saturate.i32_f32(num)
+		if num ~= num then
+			return 0
+		elseif num <= -0x80000000 then
+			return -0x80000000
+		elseif num >= 0x7FFFFFFF then
+			return 0x7FFFFFFF
+		else
+			return (math_to_integer(truncate_f64(num)))
+		end
+	end
+
+	function saturate.u32_f32(num)
+		if num ~= num or num <= 0 then
+			return 0
+		elseif num >= 0xFFFFFFFF then
+			return -1
+		else
+			return (wrap_i32(math_to_integer(truncate_f64(num))))
+		end
+	end
+
+	function saturate.i64_f32(num)
+		if num ~= num then
+			return 0
+		elseif num <= -NUM_BIT_63 then
+			return math.mininteger
+		elseif num >= NUM_BIT_63 then
+			return math.maxinteger
+		else
+			return (math_to_integer(truncate_f64(num)))
+		end
+	end
+
+	function saturate.u64_f32(num)
+		if num ~= num or num <= 0 then
+			return 0
+		elseif num >= 0x1p64 then
+			return -1
+		else
+			return (truncate.u64_f32(num))
+		end
+	end
+
+	saturate.i32_f64 = saturate.i32_f32
+	saturate.u32_f64 = saturate.u32_f32
+	saturate.i64_f64 = saturate.i64_f32
+	saturate.u64_f64 = saturate.u64_f32
+
+	function extend.i32_n8(num)
+		return ((num & 0xFF) ~ 0x80) - 0x80
+	end
+
+	function extend.i32_n16(num)
+		return ((num & 0xFFFF) ~ 0x8000) - 0x8000
+	end
+
+	extend.i64_n8 = extend.i32_n8
+	extend.i64_n16 = extend.i32_n16
+	extend.i64_n32 = wrap.i32_i64
+
+	function extend.i64_i32(num)
+		return num
+	end
+
+	function extend.i64_u32(num)
+		return num & 0xFFFFFFFF
+	end
+
+	function convert.f64_i32(num)
+		return num + 0.0
+	end
+
+	function convert.f64_u32(num)
+		return (num & 0xFFFFFFFF) + 0.0
+	end
+
+	convert.f64_i64 = convert.f64_i32
+
+	-- Halving keeps the value in range and the low bit keeps
+	-- the rounding of the result correct
+	function convert.f64_u64(num)
+		if num >= 0 then
+			return num + 0.0
+		else
+			return ((num >> 1) | (num & 1)) * 2.0
+		end
+	end
+
+	function convert.f32_i32(num)
+		return (round_f32(convert.f64_i32(num)))
+	end
+
+	function convert.f32_u32(num)
+		return (round_f32(convert.f64_u32(num)))
+	end
+
+	function convert.f32_i64(num)
+		return (round_f32(convert.f64_i64(num)))
+	end
+
+	function convert.f32_u64(num)
+		return (round_f32(convert.f64_u64(num)))
+	end
+
+	function promote.f64_f32(num)
+		return num
+	end
+
+	demote.f32_f64 = round_f32
+
+	function reinterpret.i32_f32(num)
+		return (string_unpack("<i4", string_pack("<f", num)))
+	end
+
+	function reinterpret.i64_f64(num)
+		return (string_unpack("<i8", string_pack("<d", num)))
+	end
+
+	function reinterpret.f32_i32(num)
+		return (string_unpack("<f", string_pack("<i4", num)))
+	end
+
+	function reinterpret.f64_i64(num)
+		return (string_unpack("<d", string_pack("<i8", num)))
+	end
+
+	module.wrap = wrap
+	module.saturate = saturate
+	module.extend = extend
+	module.convert = convert
+	module.demote = demote
+	module.promote = promote
+	module.reinterpret = reinterpret
+end
+
+do
+	local load = {}
+	local store = {}
+	local allocator = {}
+
+	local WASM_PAGE_SIZE = 65536
+
+	-- Bytes are kept in a sparse table indexed by address, where
+	-- a missing entry reads as zero
+	local function check_range(memory, addr, len)
+		if addr < 0 then
+			addr = addr + 0x100000000
+		end
+
+		len = len & 0xFFFFFFFF
+
+		if addr + len > memory.min * WASM_PAGE_SIZE then
+			error(trap.out_of_bounds_memory_access)
+		end
+
+		return addr, len
+	end
+
+	local function read_bytes(memory, addr, len)
+		addr = check_range(memory, addr, len)
+
+		local data = memory.data
+		local result = 0
+
+		for i = len - 1, 0, -1 do
+			result = (result << 8) | (data[addr + i] or 0)
+		end
+
+		return result
+	end
+
+	local function write_bytes(memory, addr, len, value)
+		addr = check_range(memory, addr, len)
+
+		local data = memory.data
+
+		for i = 0, len - 1 do
+			data[addr + i] = value & 0xFF
+			value = value >> 8
+		end
+	end
+
+	function load.i32_i8(memory, addr)
+		return ((read_bytes(memory, addr, 1) ~ 0x80) - 0x80)
+	end
+
+	function load.i32_u8(memory, addr)
+		return (read_bytes(memory, addr, 1))
+	end
+
+	function load.i32_i16(memory, addr)
+		return ((read_bytes(memory, addr, 2) ~ 0x8000) - 0x8000)
+	end
+
+	function load.i32_u16(memory, addr)
+		return (read_bytes(memory, addr, 2))
+	end
+
+	function load.i32(memory, addr)
+		return ((read_bytes(memory, addr, 4) ~ 0x80000000) - 0x80000000)
+	end
+
+	load.i64_i8 = load.i32_i8
+	load.i64_u8 = load.i32_u8
+	load.i64_i16 = load.i32_i16
+	load.i64_u16 = load.i32_u16
+	load.i64_i32 = load.i32
+
+	function load.i64_u32(memory, addr)
+		return (read_bytes(memory, addr, 4))
+	end
+
+	function load.i64(memory, addr)
+		return (read_bytes(memory, addr, 8))
+	end
+
+	function load.f32(memory, addr)
+		return (string_unpack("<f", string_pack("<I4", read_bytes(memory, addr, 4))))
+	end
+
+	function load.f64(memory, addr)
+		return (string_unpack("<d", string_pack("<i8", read_bytes(memory, addr, 8))))
+	end
+
+	function load.string(memory, addr, len)
+		addr, len = check_range(memory, addr, len)
+
+		local data = memory.data
+		local list = {}
+
+		for i = 1, len do
+			list[i] = string_char(data[addr + i - 1] or 0)
+		end
+
+		return table.concat(list)
+	end
+
+	function store.i32_n8(memory, addr, value)
+		write_bytes(memory, addr, 1, value)
+	end
+
+	function store.i32_n16(memory, addr, value)
+		write_bytes(memory, addr, 2, value)
+	end
+
+	function store.i32(memory, addr, value)
+		write_bytes(memory, addr, 4, value)
+	end
+
+	store.i64_n8 = store.i32_n8
+	store.i64_n16 = store.i32_n16
+	store.i64_n32 = store.i32
+
+	function store.i64(memory, addr, value)
+		write_bytes(memory, addr, 8, value)
+	end
+
+	function store.f32(memory, addr, value)
+		write_bytes(memory, addr, 4, string_unpack("<I4", string_pack("<f", value)))
+	end
+
+	function store.f64(memory, addr, value)
+		write_bytes(memory, addr, 8, string_unpack("<i8", string_pack("<d", value)))
+	end
+
+	function store.string(memory, addr, data, len)
+		len = len or #data
+		addr = check_range(memory, addr, len)
+
+		local target = memory.data
+
+		for i = 1, len do
+			target[addr + i - 1] = string_byte(data, i)
+		end
+	end
+
+	function store.copy(memory_1, addr_1, memory_2, addr_2, len)
+		addr_1, len = check_range(memory_1, addr_1, len)
+		addr_2 = check_range(memory_2, addr_2, len)
+
+		local data_1 = memory_1.data
+		local data_2 = memory_2.data
+
+		if addr_1 <= addr_2 then
+			for i = 0, len - 1 do
+				data_1[addr_1 + i] = data_2[addr_2 + i]
+			end
+		else
+			for i = len - 1, 0, -1 do
+				data_1[addr_1 + i] = data_2[addr_2 + i]
+			end
+		end
+	end
+
+	function store.fill(memory, addr, len, value)
+		addr, len = check_range(memory, addr, len)
+
+		local data = memory.data
+
+		value = value & 0xFF
+
+		for i = addr, addr + len - 1 do
+			data[i] = value
+		end
+	end
+
+	function store.init(memory, addr, data, offset, len)
+		local content = data or ""
+
+		addr, len = check_range(memory, addr, len)
+		offset = offset & 0xFFFFFFFF
+
+		if offset + len > #content then
+			error(trap.out_of_bounds_memory_access)
+		end
+
+		store.string(memory, addr, string_sub(content, offset + 1, offset + len), len)
+	end
+
+	function allocator.new(min, max)
+		return { min = min, max = max, data = {} }
+	end
+
+	function allocator.grow(memory, num)
+		num = num & 0xFFFFFFFF
+
+		local old = memory.min
+		local new = old + num
+
+		if new > memory.max then
+			return -1
+		else
+			memory.min = new
+
+			return old
+		end
+	end
+
+	module.load = load
+	module.store = store
+	module.allocator = allocator
+end
+
+do
+	local add = module.add
+	local sub = module.sub
+	local mul = module.mul
+	local load = module.load
+	local store = module.store
+
+	local v128 = {}
+	local splat = {}
+	local extract_lane = {}
+	local replace_lane = {}
+
+	-- Vectors are immutable 16 byte strings, so lanes are read
+	-- and written with the matching `string.pack` format
+	local FORMAT_I8X16 = "<" .. string.rep("B", 16)
+	local FORMAT_I16X8 = "<" .. string.rep("H", 8)
+	local FORMAT_I32X4 = "<" .. string.rep("i4", 4)
+	local FORMAT_I64X2 = "<i8i8"
+
+	local function lane_wise(format, count, func)
+		return function(lhs, rhs)
+			local list_1 = { string_unpack(format, lhs) }
+			local list_2 = { string_unpack(format, rhs) }
+
+			for i = 1, count do
+				list_1[i] = func(list_1[i], list_2[i])
+			end
+
+			return (string_pack(format, table_unpack(list_1, 1, count)))
+		end
+	end
+
+	local function replace_with(format, size)
+		return function(value, lane, num)
+			local start = lane * size
+			local data = string_pack(format, num)
+
+			return string_sub(value, 1, start) .. data .. string_sub(value, start + size + 1)
+		end
+	end
+
+	v128.ZERO = string.rep("\0", 16)
+
+	function v128.from_u32(data_1, data_2, data_3, data_4)
+		return (string_pack("<I4I4I4I4", data_1, data_2, data_3, data_4))
+	end
+
+	function load.v128(memory, addr)
+		return (load.string(memory, addr, 16))
+	end
+
+	function store.v128(memory, addr, value)
+		store.string(memory, addr, value, 16)
+	end
+
+	function splat.i8x16(num)
+		return (string.rep(string_char(num & 0xFF), 16))
+	end
+
+	function splat.i16x8(num)
+		return (string.rep(string_pack("<I2", num & 0xFFFF), 8))
+	end
+
+	function splat.i32x4(num)
+		return (string.rep(string_pack("<i4", num), 4))
+	end
+
+	function splat.i64x2(num)
+		return (string.rep(string_pack("<i8", num), 2))
+	end
+
+	function splat.f32x4(num)
+		return (string.rep(string_pack("<f", num), 4))
+	end
+
+	function splat.f64x2(num)
+		return (string.rep(string_pack("<d", num), 2))
+	end
+
+	function extract_lane.i32_i8x16(value, lane)
+		return (string_unpack("<i1", value, lane + 1))
+	end
+
+	function extract_lane.i32_u8x16(value, lane)
+		return (string_unpack("<I1", value, lane + 1))
+	end
+
+	function extract_lane.i32_i16x8(value, lane)
+		return (string_unpack("<i2", value, lane * 2 + 1))
+	end
+
+	function extract_lane.i32_u16x8(value, lane)
+		return (string_unpack("<I2", value, lane * 2 + 1))
+	end
+
+	function extract_lane.i32x4(value, lane)
+		return (string_unpack("<i4", value, lane * 4 + 1))
+	end
+
+	function extract_lane.i64x2(value, lane)
+		return (string_unpack("<i8", value, lane * 8 + 1))
+	end
+
+	function extract_lane.f32x4(value, lane)
+		return (string_unpack("<f", value, lane * 4 + 1))
+	end
+
+	function extract_lane.f64x2(value, lane)
+		return (string_unpack("<d", value, lane * 8 + 1))
+	end
+
+	local replace_i8 = replace_with("<I1", 1)
+	local replace_i16 = replace_with("<I2", 2)
+
+	function replace_lane.i8x16(value, lane, num)
+		return (replace_i8(value, lane, num & 0xFF))
+	end
+
+	function replace_lane.i16x8(value, lane, num)
+		return (replace_i16(value, lane, num & 0xFFFF))
+	end
+
+	replace_lane.i32x4 = replace_with("<i4", 4)
+	replace_lane.i64x2 = replace_with("<i8", 8)
+	replace_lane.f32x4 = replace_with("<f", 4)
+	replace_lane.f64x2 = replace_with("<d", 8)
+
+	v128.band = lane_wise(FORMAT_I64X2, 2, function(lhs, rhs)
+		return lhs & rhs
+	end)
+
+	v128.bandnot = lane_wise(FORMAT_I64X2, 2, function(lhs, rhs)
+		return lhs & ~rhs
+	end)
+
+	v128.bor = lane_wise(FORMAT_I64X2, 2, function(lhs, rhs)
+		return lhs | rhs
+	end)
+
+	v128.bxor = lane_wise(FORMAT_I64X2, 2, function(lhs, rhs)
+		return lhs ~ rhs
+	end)
+
+	function v128.bnot(num)
+		local data_1, data_2 = string_unpack(FORMAT_I64X2, num)
+
+		return (string_pack(FORMAT_I64X2, ~data_1, ~data_2))
+	end
+
+	add.i8x16 = lane_wise(FORMAT_I8X16, 16, function(lhs, rhs)
+		return (lhs + rhs) & 0xFF
+	end)
+
+	sub.i8x16 = lane_wise(FORMAT_I8X16, 16, function(lhs, rhs)
+		return (lhs - rhs) & 0xFF
+	end)
+
+	add.i16x8 = lane_wise(FORMAT_I16X8, 8, function(lhs, rhs)
+		return (lhs + rhs) & 0xFFFF
+	end)
+
+	sub.i16x8 = lane_wise(FORMAT_I16X8, 8, function(lhs, rhs)
+		return (lhs - rhs) & 0xFFFF
+	end)
+
+	mul.i16x8 = lane_wise(FORMAT_I16X8, 8, function(lhs, rhs)
+		return (lhs * rhs) & 0xFFFF
+	end)
+
+	add.i32x4 = lane_wise(FORMAT_I32X4, 4, add.i32)
+	sub.i32x4 = lane_wise(FORMAT_I32X4, 4, sub.i32)
+	mul.i32x4 = lane_wise(FORMAT_I32X4, 4, mul.i32)
+
+	add.i64x2 = lane_wise(FORMAT_I64X2, 2, function(lhs, rhs)
+		return lhs + rhs
+	end)
+
+	sub.i64x2 = lane_wise(FORMAT_I64X2, 2, function(lhs, rhs)
+		return lhs - rhs
+	end)
+
+	mul.i64x2 = lane_wise(FORMAT_I64X2, 2, function(lhs, rhs)
+		return lhs * rhs
+	end)
+
+	module.v128 = v128
+	module.splat = splat
+	module.extract_lane = extract_lane
+	module.replace_lane = replace_lane
+end
+
+-- References are the only values that can be `nil` or `false`, so
+-- choosing between them cannot be written with `and` and `or`
+function module.select(condition, on_true, on_false)
+	if condition then
+		return on_true
+	else
+		return on_false
+	end
+end
+
+-- Imports are looked up as `imports[module][name]`, the shape used by
+-- the WebAssembly JS API. Instances work as a namespace too, through
+-- their `*_list` tables. Plain values given for globals are read and
+-- written through the table they came from, so both sides see changes.
+function module.import(imports, module_name, name, list)
+	local namespace = imports[module_name]
+	local value = nil
+
+	if namespace ~= nil then
+		value = namespace[name]
+
+		if value == nil and namespace[list] ~= nil then
+			namespace = namespace[list]
+			value = namespace[name]
+		end
+	end
+
+	if value == nil then
+		error(string.format("missing import `%s.%s`", module_name, name), 0)
+	elseif list == "global_list" and type(value) ~= "table" then
+		value = setmetatable({}, {
+			__index = function()
+				return namespace[name]
+			end,
+			__newindex = function(_, _, new)
+				namespace[name] = new
+			end,
+		})
+	end
+
+	return value
+end
+
+do
+	local tbl = {}
+
+	-- Indices are signed here, so negative ones wrapped around
+	-- from the top of the `i32` range and are out of bounds too
+	local function check_index(data, index)
+		if index < 0 or index >= data.min then
+			error(trap.out_of_bounds_table_access)
+		end
+	end
+
+	function tbl.get(data, index)
+		check_index(data, index)
+
+		return data.data[index]
+	end
+
+	function tbl.set(data, index, value)
+		check_index(data, index)
+
+		data.data[index] = value
+	end
+
+	-- Dropped segments are `nil` and behave as if they were empty
+	function tbl.init(data, element, dest, offset, size)
+		local len = element and element.len or 0
+
+		dest = dest & 0xFFFFFFFF
+		offset = offset & 0xFFFFFFFF
+		size = size & 0xFFFFFFFF
+
+		if offset + size > len or dest + size > data.min then
+			error(trap.out_of_bounds_table_access)
+		end
+
+		if size ~= 0 then
+			table.move(element.data, offset + 1, offset + size, dest, data.data)
+		end
+	end
+
+	-- Growing by a `nil` value only moves the bound, since missing
+	-- elements already read as `nil`
+	function tbl.grow(data, value, size)
+		size = size & 0xFFFFFFFF
+
+		local old = data.min
+		local new = old + size
+
+		if new > data.max then
+			return -1
+		end
+
+		if value ~= nil then
+			for i = old, new - 1 do
+				data.data[i] = value
+			end
+		end
+
+		data.min = new
+
+		return old
+	end
+
+	function tbl.fill(data, dest, value, size)
+		dest = dest & 0xFFFFFFFF
+		size = size & 0xFFFFFFFF
+
+		if dest + size > data.min then
+			error(trap.out_of_bounds_table_access)
+		end
+
+		for i = dest, dest + size - 1 do
+			data.data[i] = value
+		end
+	end
+
+	-- `table.move` picks the copy direction itself, so overlapping
+	-- ranges within the same table come out right
+	function tbl.copy(target, source, dest, offset, size)
+		dest = dest & 0xFFFFFFFF
+		offset = offset & 0xFFFFFFFF
+		size = size & 0xFFFFFFFF
+
+		if dest + size > target.min or offset + size > source.min then
+			error(trap.out_of_bounds_table_access)
+		end
+
+		if size ~= 0 then
+			table.move(source.data, offset, offset + size - 1, dest, target.data)
+		end
+	end
+
+	-- Functions are tagged with their signature as modules are set up,
+	-- so `call_indirect` can check it before calling through a table.
+	-- A host function imported under another signature than it already
+	-- has gets a wrapper of its own, which is what should be stored.
+	local signature_list = setmetatable({}, { __mode = "k" })
+
+	function tbl.register(func, signature)
+		if func == nil then
+			return nil
+		end
+
+		local old = signature_list[func]
+
+		if old == nil then
+			signature_list[func] = signature
+		elseif old ~= signature then
+			local inner = func
+
+			func = function(...)
+				return inner(...)
+			end
+
+			signature_list[func] = signature
+		end
+
+		return func
+	end
+
+	function tbl.indirect(data, index, signature)
+		if index < 0 or index >= data.min then
+			error(trap.undefined_element)
+		end
+
+		local func = data.data[index]
+
+		if func == nil then
+			error(trap.uninitialized_element)
+		elseif signature_list[func] ~= signature then
+			error(trap.indirect_call_type_mismatch)
+		end
+
+		return func
+	end
+
+	module.table = tbl
+end
+
+return module
@@ -0,0 +1,3 @@
+pub mod br_table;
+pub mod into_string;
+pub mod localize;
@@ -0,0 +1,4 @@
+pub mod manager;
+
+mod expression;
+mod statement;
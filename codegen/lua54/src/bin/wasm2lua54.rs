@@ -0,0 +1,35 @@
+use std::io::{ErrorKind, Result, Write};
+
+use wasm_ast::{error::Error, module::Module};
+
+fn load_arg_source() -> Result<Vec<u8>> {
+	let mut arguments = std::env::args();
+	let path = arguments.next().unwrap_or_else(|| "wasm2lua54".to_string());
+
+	arguments.next().map_or_else(
+		|| {
+			eprintln!("usage: {path} <file>\n");
+
+			Err(ErrorKind::NotFound.into())
+		},
+		std::fs::read,
+	)
+}
+
+fn do_runtime(lock: &mut dyn Write) -> Result<()> {
+	let runtime = codegen_lua54::RUNTIME;
+
+	writeln!(lock, "local rt = (function()")?;
+	writeln!(lock, "{runtime}")?;
+	writeln!(lock, "end)()")
+}
+
+fn main() -> Result<()> {
+	let data = load_arg_source()?;
+	let wasm = Module::try_from_data(&data).map_err(Error::from)?;
+
+	let lock = &mut std::io::stdout().lock();
+
+	do_runtime(lock)?;
+	codegen_lua54::from_module_untyped(&wasm, lock)
+}
@@ -23,6 +23,35 @@ local function truncate_f64(num)
 	end
 end
 
+-- Traps are raised as one of these tables, so hosts can `pcall` and
+-- branch on `kind`. Errors raised by Lua itself still come through
+-- as strings.
+local trap = {}
+
+do
+	local trap_meta = {
+		__tostring = function(trap)
+			return trap.message
+		end,
+	}
+
+	local function add(kind, message)
+		trap[kind] = setmetatable({ kind = kind, message = message }, trap_meta)
+	end
+
+	add("unreachable", "unreachable executed")
+	add("integer_divide_by_zero", "integer divide by zero")
+	add("integer_overflow", "integer overflow")
+	add("invalid_conversion_to_integer", "invalid conversion to integer")
+	add("out_of_bounds_memory_access", "out of bounds memory access")
+	add("out_of_bounds_table_access", "out of bounds table access")
+	add("undefined_element", "undefined element")
+	add("uninitialized_element", "uninitialized element")
+	add("indirect_call_type_mismatch", "indirect call type mismatch")
+
+	module.trap = trap
+end
+
 do
 	local add = {}
 	local sub = {}
@@ -36,8 +65,6 @@ do
 	local nearest = {}
 
 	local math_abs = math.abs
-	local math_min = math.min
-	local math_max = math.max
 
 	local RE_INSTANCE = ffi.new([[union {
 		double f64;
@@ -65,13 +92,17 @@ do
 	end
 
 	function div.i32(lhs, rhs)
-		assert(rhs ~= 0, "division by zero")
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		end
 
 		return (truncate_f64(lhs / rhs))
 	end
 
 	function div.u32(lhs, rhs)
-		assert(rhs ~= 0, "division by zero")
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		end
 
 		lhs = to_number(u32(lhs))
 		rhs = to_number(u32(rhs))
@@ -80,7 +111,9 @@ do
 	end
 
 	function rem.u32(lhs, rhs)
-		assert(rhs ~= 0, "division by zero")
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		end
 
 		lhs = to_number(u32(lhs))
 		rhs = to_number(u32(rhs))
@@ -89,13 +122,17 @@ do
 	end
 
 	function div.u64(lhs, rhs)
-		assert(rhs ~= 0, "division by zero")
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		end
 
 		return (i64(u64(lhs) / u64(rhs)))
 	end
 
 	function rem.u64(lhs, rhs)
-		assert(rhs ~= 0, "division by zero")
+		if rhs == 0 then
+			error(trap.integer_divide_by_zero)
+		end
 
 		return (i64(u64(lhs) % u64(rhs)))
 	end
@@ -104,23 +141,37 @@ do
 		return -num
 	end
 
+	-- Unlike `math.min` and `math.max`, a NaN on either side wins and -0
+	-- is ordered below 0. Adding the operands quiets a signaling NaN.
 	function min.f32(lhs, rhs)
-		if lhs ~= lhs then
+		if lhs ~= lhs or rhs ~= rhs then
+			return lhs + rhs
+		elseif lhs == rhs then
+			if 1 / lhs < 0 then
+				return lhs
+			else
+				return rhs
+			end
+		elseif lhs < rhs then
 			return lhs
-		elseif rhs ~= rhs then
-			return rhs
 		else
-			return (math_min(lhs, rhs))
+			return rhs
 		end
 	end
 
 	function max.f32(lhs, rhs)
-		if lhs ~= lhs then
+		if lhs ~= lhs or rhs ~= rhs then
+			return lhs + rhs
+		elseif lhs == rhs then
+			if 1 / lhs < 0 then
+				return rhs
+			else
+				return lhs
+			end
+		elseif lhs > rhs then
 			return lhs
-		elseif rhs ~= rhs then
-			return rhs
 		else
-			return (math_max(lhs, rhs))
+			return rhs
 		end
 	end
 
@@ -638,6 +689,7 @@ do
 	void *calloc(size_t num, size_t size);
 	void *realloc(void *ptr, size_t size);
 	void free(void *ptr);
+	void *memmove(void *dst, const void *src, size_t len);
 	]])
 
 	local alias_t = ffi.typeof("uint8_t *")
@@ -754,31 +806,140 @@ do
 		ffi.copy(start, data, len or #data)
 	end
 
+	-- Memory is little-endian and addresses need not be aligned, which the
+	-- casts above only get right on some hosts. Everywhere else, values are
+	-- moved through an aligned scratch copy and byte swapped when needed.
+	local LOAD_WIDTH = {
+		i32_i16 = 2,
+		i32_u16 = 2,
+		i32 = 4,
+		i64_i16 = 2,
+		i64_u16 = 2,
+		i64_i32 = 4,
+		i64_u32 = 4,
+		i64 = 8,
+		f32 = 4,
+		f64 = 8,
+	}
+
+	local STORE_WIDTH = {
+		i32_n16 = 2,
+		i32 = 4,
+		i64_n16 = 2,
+		i64_n32 = 4,
+		i64 = 8,
+		f32 = 4,
+		f64 = 8,
+	}
+
+	local arch = jit.arch
+	local is_direct = ffi.abi("le") and (arch == "x86" or arch == "x64" or arch == "arm64")
+
+	if not is_direct then
+		local scratch = { data = ffi.new("union Any[1]") }
+		local scratch_bytes = cast(alias_t, scratch.data)
+		local is_swapped = ffi.abi("be")
+
+		local function swap_scratch(len)
+			if not is_swapped then
+				return
+			end
+
+			for i = 0, len / 2 - 1 do
+				local j = len - 1 - i
+
+				scratch_bytes[i], scratch_bytes[j] = scratch_bytes[j], scratch_bytes[i]
+			end
+		end
+
+		for name, len in pairs(LOAD_WIDTH) do
+			local inner = load[name]
+
+			load[name] = function(memory, addr)
+				ffi.copy(scratch_bytes, cast(alias_t, memory.data) + addr, len)
+				swap_scratch(len)
+
+				return inner(scratch, 0)
+			end
+		end
+
+		for name, len in pairs(STORE_WIDTH) do
+			local inner = store[name]
+
+			store[name] = function(memory, addr, value)
+				inner(scratch, 0, value)
+				swap_scratch(len)
+				ffi.copy(cast(alias_t, memory.data) + addr, scratch_bytes, len)
+			end
+		end
+	end
+
+	local WASM_PAGE_SIZE = 65536
+
+	-- Bulk operations must trap before writing anything, so the whole
+	-- range is checked up front
+	local function check_range(memory, addr, len)
+		addr = addr % 0x100000000
+		len = len % 0x100000000
+
+		if addr + len > memory.min * WASM_PAGE_SIZE then
+			error(trap.out_of_bounds_memory_access)
+		end
+
+		return addr, len
+	end
+
 	function store.copy(memory_1, addr_1, memory_2, addr_2, len)
+		addr_1, len = check_range(memory_1, addr_1, len)
+		addr_2 = check_range(memory_2, addr_2, len)
+
 		local start_1 = by_offset(memory_1.data, addr_1)
 		local start_2 = by_offset(memory_2.data, addr_2)
 
-		ffi.copy(start_1, start_2, len)
+		ffi.C.memmove(start_1, start_2, len)
 	end
 
 	function store.fill(memory, addr, len, value)
+		addr, len = check_range(memory, addr, len)
+
 		local start = by_offset(memory.data, addr)
 
 		ffi.fill(start, len, value)
 	end
 
-	local WASM_PAGE_SIZE = 65536
+	function store.init(memory, addr, data, offset, len)
+		local content = data or ""
+
+		addr, len = check_range(memory, addr, len)
+		offset = offset % 0x100000000
+
+		if offset + len > #content then
+			error(trap.out_of_bounds_memory_access)
+		end
+
+		local start = by_offset(memory.data, addr)
+
+		ffi.copy(start, string.sub(content, offset + 1, offset + len), len)
+	end
 
 	local function finalizer(memory)
 		ffi.C.free(memory.data)
 	end
 
+	-- Running out of host memory is a valid way for growing to fail,
+	-- and `realloc` leaves the old block untouched when it does
 	local function grow_unchecked(memory, old, new)
-		memory.data = ffi.C.realloc(memory.data, new)
+		local data = ffi.C.realloc(memory.data, new)
+
+		if data == nil then
+			return false
+		end
 
-		assert(memory.data ~= nil, "failed to reallocate")
+		memory.data = data
 
 		ffi.fill(by_offset(memory.data, old), new - old, 0)
+
+		return true
 	end
 
 	function allocator.new(min, max)
@@ -792,6 +953,8 @@ do
 	end
 
 	function allocator.grow(memory, num)
+		num = num % 0x100000000
+
 		if num == 0 then
 			return memory.min
 		end
@@ -801,8 +964,9 @@ do
 
 		if new > memory.max then
 			return -1
+		elseif not grow_unchecked(memory, old * WASM_PAGE_SIZE, new * WASM_PAGE_SIZE) then
+			return -1
 		else
-			grow_unchecked(memory, old * WASM_PAGE_SIZE, new * WASM_PAGE_SIZE)
 			memory.min = new
 
 			return old
@@ -814,4 +978,558 @@ do
 	module.allocator = allocator
 end
 
+do
+	local add = module.add
+	local sub = module.sub
+	local mul = module.mul
+	local load = module.load
+	local store = module.store
+
+	local v128 = {}
+	local splat = {}
+	local extract_lane = {}
+	local replace_lane = {}
+
+	ffi.cdef([[
+	union V128 {
+		int8_t i8[16];
+		int16_t i16[8];
+		int32_t i32[4];
+		int64_t i64[2];
+
+		uint8_t u8[16];
+		uint16_t u16[8];
+		uint32_t u32[4];
+
+		float f32[4];
+		double f64[2];
+	};
+	]])
+
+	local v128_t = ffi.typeof("union V128")
+	local alias_t = ffi.typeof("uint8_t *")
+	local cast = ffi.cast
+
+	local bit_and = bit.band
+	local bit_or = bit.bor
+	local bit_xor = bit.bxor
+	local bit_not = bit.bnot
+
+	local function clone(value)
+		local temp = v128_t()
+
+		ffi.copy(temp, value, 16)
+
+		return temp
+	end
+
+	v128.ZERO = v128_t()
+
+	function v128.from_u32(data_1, data_2, data_3, data_4)
+		local value = v128_t()
+
+		value.u32[0] = data_1
+		value.u32[1] = data_2
+		value.u32[2] = data_3
+		value.u32[3] = data_4
+
+		return value
+	end
+
+	function load.v128(memory, addr)
+		local value = v128_t()
+
+		ffi.copy(value, cast(alias_t, memory.data) + addr, 16)
+
+		return value
+	end
+
+	function store.v128(memory, addr, value)
+		ffi.copy(cast(alias_t, memory.data) + addr, value, 16)
+	end
+
+	function splat.i8x16(num)
+		local value = v128_t()
+
+		ffi.fill(value, 16, num)
+
+		return value
+	end
+
+	function splat.i16x8(num)
+		local value = v128_t()
+
+		for i = 0, 7 do
+			value.i16[i] = num
+		end
+
+		return value
+	end
+
+	function splat.i32x4(num)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = num
+		end
+
+		return value
+	end
+
+	function splat.i64x2(num)
+		local value = v128_t()
+
+		value.i64[0] = num
+		value.i64[1] = num
+
+		return value
+	end
+
+	function splat.f32x4(num)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.f32[i] = num
+		end
+
+		return value
+	end
+
+	function splat.f64x2(num)
+		local value = v128_t()
+
+		value.f64[0] = num
+		value.f64[1] = num
+
+		return value
+	end
+
+	function extract_lane.i32_i8x16(value, lane)
+		return value.i8[lane]
+	end
+
+	function extract_lane.i32_u8x16(value, lane)
+		return value.u8[lane]
+	end
+
+	function extract_lane.i32_i16x8(value, lane)
+		return value.i16[lane]
+	end
+
+	function extract_lane.i32_u16x8(value, lane)
+		return value.u16[lane]
+	end
+
+	function extract_lane.i32x4(value, lane)
+		return value.i32[lane]
+	end
+
+	function extract_lane.i64x2(value, lane)
+		return value.i64[lane]
+	end
+
+	function extract_lane.f32x4(value, lane)
+		return value.f32[lane]
+	end
+
+	function extract_lane.f64x2(value, lane)
+		return value.f64[lane]
+	end
+
+	function replace_lane.i8x16(value, lane, num)
+		local temp = clone(value)
+
+		temp.i8[lane] = num
+
+		return temp
+	end
+
+	function replace_lane.i16x8(value, lane, num)
+		local temp = clone(value)
+
+		temp.i16[lane] = num
+
+		return temp
+	end
+
+	function replace_lane.i32x4(value, lane, num)
+		local temp = clone(value)
+
+		temp.i32[lane] = num
+
+		return temp
+	end
+
+	function replace_lane.i64x2(value, lane, num)
+		local temp = clone(value)
+
+		temp.i64[lane] = num
+
+		return temp
+	end
+
+	function replace_lane.f32x4(value, lane, num)
+		local temp = clone(value)
+
+		temp.f32[lane] = num
+
+		return temp
+	end
+
+	function replace_lane.f64x2(value, lane, num)
+		local temp = clone(value)
+
+		temp.f64[lane] = num
+
+		return temp
+	end
+
+	function v128.band(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = bit_and(lhs.i32[i], rhs.i32[i])
+		end
+
+		return value
+	end
+
+	function v128.bandnot(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = bit_and(lhs.i32[i], bit_not(rhs.i32[i]))
+		end
+
+		return value
+	end
+
+	function v128.bor(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = bit_or(lhs.i32[i], rhs.i32[i])
+		end
+
+		return value
+	end
+
+	function v128.bxor(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = bit_xor(lhs.i32[i], rhs.i32[i])
+		end
+
+		return value
+	end
+
+	function v128.bnot(num)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = bit_not(num.i32[i])
+		end
+
+		return value
+	end
+
+	function add.i8x16(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 15 do
+			value.u8[i] = bit_and(lhs.u8[i] + rhs.u8[i], 0xFF)
+		end
+
+		return value
+	end
+
+	function sub.i8x16(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 15 do
+			value.u8[i] = bit_and(lhs.u8[i] - rhs.u8[i], 0xFF)
+		end
+
+		return value
+	end
+
+	function add.i16x8(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 7 do
+			value.u16[i] = bit_and(lhs.u16[i] + rhs.u16[i], 0xFFFF)
+		end
+
+		return value
+	end
+
+	function sub.i16x8(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 7 do
+			value.u16[i] = bit_and(lhs.u16[i] - rhs.u16[i], 0xFFFF)
+		end
+
+		return value
+	end
+
+	function mul.i16x8(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 7 do
+			value.u16[i] = bit_and(lhs.u16[i] * rhs.u16[i], 0xFFFF)
+		end
+
+		return value
+	end
+
+	function add.i32x4(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = add.i32(lhs.i32[i], rhs.i32[i])
+		end
+
+		return value
+	end
+
+	function sub.i32x4(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = sub.i32(lhs.i32[i], rhs.i32[i])
+		end
+
+		return value
+	end
+
+	function mul.i32x4(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 3 do
+			value.i32[i] = mul.i32(lhs.i32[i], rhs.i32[i])
+		end
+
+		return value
+	end
+
+	function add.i64x2(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 1 do
+			value.i64[i] = lhs.i64[i] + rhs.i64[i]
+		end
+
+		return value
+	end
+
+	function sub.i64x2(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 1 do
+			value.i64[i] = lhs.i64[i] - rhs.i64[i]
+		end
+
+		return value
+	end
+
+	function mul.i64x2(lhs, rhs)
+		local value = v128_t()
+
+		for i = 0, 1 do
+			value.i64[i] = lhs.i64[i] * rhs.i64[i]
+		end
+
+		return value
+	end
+
+	module.v128 = v128
+	module.splat = splat
+	module.extract_lane = extract_lane
+	module.replace_lane = replace_lane
+end
+
+-- References are the only values that can be `nil` or `false`, so
+-- choosing between them cannot be written with `and` and `or`
+function module.select(condition, on_true, on_false)
+	if condition then
+		return on_true
+	else
+		return on_false
+	end
+end
+
+-- Imports are looked up as `imports[module][name]`, the shape used by
+-- the WebAssembly JS API. Instances work as a namespace too, through
+-- their `*_list` tables. Plain values given for globals are read and
+-- written through the table they came from, so both sides see changes.
+function module.import(imports, module_name, name, list)
+	local namespace = imports[module_name]
+	local value = nil
+
+	if namespace ~= nil then
+		value = namespace[name]
+
+		if value == nil and namespace[list] ~= nil then
+			namespace = namespace[list]
+			value = namespace[name]
+		end
+	end
+
+	if value == nil then
+		error(string.format("missing import `%s.%s`", module_name, name), 0)
+	elseif list == "global_list" and type(value) ~= "table" then
+		value = setmetatable({}, {
+			__index = function()
+				return namespace[name]
+			end,
+			__newindex = function(_, _, new)
+				namespace[name] = new
+			end,
+		})
+	end
+
+	return value
+end
+
+do
+	local tbl = {}
+
+	-- Indices are signed here, so negative ones wrapped around
+	-- from the top of the `i32` range and are out of bounds too
+	local function check_index(data, index)
+		if index < 0 or index >= data.min then
+			error(trap.out_of_bounds_table_access)
+		end
+	end
+
+	function tbl.get(data, index)
+		check_index(data, index)
+
+		return data.data[index]
+	end
+
+	function tbl.set(data, index, value)
+		check_index(data, index)
+
+		data.data[index] = value
+	end
+
+	-- Dropped segments are `nil` and behave as if they were empty
+	function tbl.init(data, element, dest, offset, size)
+		local len = element and element.len or 0
+
+		dest = dest % 0x100000000
+		offset = offset % 0x100000000
+		size = size % 0x100000000
+
+		if offset + size > len or dest + size > data.min then
+			error(trap.out_of_bounds_table_access)
+		end
+
+		if size ~= 0 then
+			table.move(element.data, offset + 1, offset + size, dest, data.data)
+		end
+	end
+
+	-- Growing by a `nil` value only moves the bound, since missing
+	-- elements already read as `nil`
+	function tbl.grow(data, value, size)
+		size = size % 0x100000000
+
+		local old = data.min
+		local new = old + size
+
+		if new > data.max then
+			return -1
+		end
+
+		if value ~= nil then
+			for i = old, new - 1 do
+				data.data[i] = value
+			end
+		end
+
+		data.min = new
+
+		return old
+	end
+
+	function tbl.fill(data, dest, value, size)
+		dest = dest % 0x100000000
+		size = size % 0x100000000
+
+		if dest + size > data.min then
+			error(trap.out_of_bounds_table_access)
+		end
+
+		for i = dest, dest + size - 1 do
+			data.data[i] = value
+		end
+	end
+
+	-- `table.move` picks the copy direction itself, so overlapping
+	-- ranges within the same table come out right
+	function tbl.copy(target, source, dest, offset, size)
+		dest = dest % 0x100000000
+		offset = offset % 0x100000000
+		size = size % 0x100000000
+
+		if dest + size > target.min or offset + size > source.min then
+			error(trap.out_of_bounds_table_access)
+		end
+
+		if size ~= 0 then
+			table.move(source.data, offset, offset + size - 1, dest, target.data)
+		end
+	end
+
+	-- Functions are tagged with their signature as modules are set up,
+	-- so `call_indirect` can check it before calling through a table.
+	-- A host function imported under another signature than it already
+	-- has gets a wrapper of its own, which is what should be stored.
+	local signature_list = setmetatable({}, { __mode = "k" })
+
+	function tbl.register(func, signature)
+		if func == nil then
+			return nil
+		end
+
+		local old = signature_list[func]
+
+		if old == nil then
+			signature_list[func] = signature
+		elseif old ~= signature then
+			local inner = func
+
+			func = function(...)
+				return inner(...)
+			end
+
+			signature_list[func] = signature
+		end
+
+		return func
+	end
+
+	function tbl.indirect(data, index, signature)
+		if index < 0 or index >= data.min then
+			error(trap.undefined_element)
+		end
+
+		local func = data.data[index]
+
+		if func == nil then
+			error(trap.uninitialized_element)
+		elseif signature_list[func] ~= signature then
+			error(trap.indirect_call_type_mismatch)
+		end
+
+		return func
+	end
+
+	module.table = tbl
+end
+
 return module
@@ -1,4 +1,6 @@
-use wasm_ast::node::{BinOpType, CmpOpType, LoadType, StoreType, UnOpType};
+use wasm_ast::node::{
+	BinOpType, CmpOpType, ExtractLaneType, LoadType, ReplaceLaneType, StoreType, UnOpType,
+};
 
 pub trait IntoName {
 	#[must_use]
@@ -22,6 +24,7 @@ impl IntoName for LoadType {
 			Self::I64_U16 => "i64_u16",
 			Self::I64_I32 => "i64_i32",
 			Self::I64_U32 => "i64_u32",
+			Self::V128 => "v128",
 		}
 	}
 }
@@ -38,6 +41,35 @@ impl IntoName for StoreType {
 			Self::I64_N8 => "i64_n8",
 			Self::I64_N16 => "i64_n16",
 			Self::I64_N32 => "i64_n32",
+			Self::V128 => "v128",
+		}
+	}
+}
+
+impl IntoName for ExtractLaneType {
+	fn into_name(self) -> &'static str {
+		match self {
+			Self::I32_I8X16 => "i32_i8x16",
+			Self::I32_U8X16 => "i32_u8x16",
+			Self::I32_I16X8 => "i32_i16x8",
+			Self::I32_U16X8 => "i32_u16x8",
+			Self::I32X4 => "i32x4",
+			Self::I64X2 => "i64x2",
+			Self::F32X4 => "f32x4",
+			Self::F64X2 => "f64x2",
+		}
+	}
+}
+
+impl IntoName for ReplaceLaneType {
+	fn into_name(self) -> &'static str {
+		match self {
+			Self::I8X16 => "i8x16",
+			Self::I16X8 => "i16x8",
+			Self::I32X4 => "i32x4",
+			Self::I64X2 => "i64x2",
+			Self::F32X4 => "f32x4",
+			Self::F64X2 => "f64x2",
 		}
 	}
 }
@@ -108,6 +140,13 @@ impl IntoNameTuple for UnOpType {
 			Self::Reinterpret_I64_F64 => ("reinterpret", "i64_f64"),
 			Self::Reinterpret_F32_I32 => ("reinterpret", "f32_i32"),
 			Self::Reinterpret_F64_I64 => ("reinterpret", "f64_i64"),
+			Self::Splat_I8X16 => ("splat", "i8x16"),
+			Self::Splat_I16X8 => ("splat", "i16x8"),
+			Self::Splat_I32X4 => ("splat", "i32x4"),
+			Self::Splat_I64X2 => ("splat", "i64x2"),
+			Self::Splat_F32X4 => ("splat", "f32x4"),
+			Self::Splat_F64X2 => ("splat", "f64x2"),
+			Self::Not_V128 => ("v128", "bnot"),
 		}
 	}
 }
@@ -159,6 +198,21 @@ impl IntoNameTuple for BinOpType {
 			Self::Min_F64 => ("min", "f64"),
 			Self::Max_F64 => ("max", "f64"),
 			Self::Copysign_F64 => ("copysign", "f64"),
+			Self::And_V128 => ("v128", "band"),
+			Self::AndNot_V128 => ("v128", "bandnot"),
+			Self::Or_V128 => ("v128", "bor"),
+			Self::Xor_V128 => ("v128", "bxor"),
+			Self::Add_I8X16 => ("add", "i8x16"),
+			Self::Sub_I8X16 => ("sub", "i8x16"),
+			Self::Add_I16X8 => ("add", "i16x8"),
+			Self::Sub_I16X8 => ("sub", "i16x8"),
+			Self::Mul_I16X8 => ("mul", "i16x8"),
+			Self::Add_I32X4 => ("add", "i32x4"),
+			Self::Sub_I32X4 => ("sub", "i32x4"),
+			Self::Mul_I32X4 => ("mul", "i32x4"),
+			Self::Add_I64X2 => ("add", "i64x2"),
+			Self::Sub_I64X2 => ("sub", "i64x2"),
+			Self::Mul_I64X2 => ("mul", "i64x2"),
 		}
 	}
 }
@@ -2,11 +2,13 @@ use std::collections::BTreeSet;
 
 use wasm_ast::{
 	node::{
-		BinOp, CmpOp, FuncData, LoadAt, MemoryCopy, MemoryFill, MemoryGrow, MemorySize, StoreAt,
-		UnOp,
+		BinOp, CallIndirect, CmpOp, ExtractLane, FuncData, LoadAt, MemoryCopy, MemoryFill,
+		MemoryGrow, MemoryInit, MemorySize, ReplaceLane, StoreAt, TableCopy, TableFill, TableGet,
+		TableGrow, TableInit, TableSet, UnOp, Value,
 	},
 	visit::{Driver, Visitor},
 };
+use wasmparser::ValType;
 
 use super::into_string::{IntoName, IntoNameTuple, TryIntoSymbol};
 
@@ -30,6 +32,12 @@ impl Visitor for Visit {
 		self.local_set.insert(("store", name));
 	}
 
+	fn visit_value(&mut self, v: Value) {
+		if let Value::V128(_) = v {
+			self.local_set.insert(("v128", "from_u32"));
+		}
+	}
+
 	fn visit_un_op(&mut self, v: &UnOp) {
 		let name = v.op_type().into_name_tuple();
 
@@ -56,6 +64,18 @@ impl Visitor for Visit {
 		self.local_set.insert(name);
 	}
 
+	fn visit_extract_lane(&mut self, v: &ExtractLane) {
+		let name = v.op_type().into_name();
+
+		self.local_set.insert(("extract_lane", name));
+	}
+
+	fn visit_replace_lane(&mut self, v: &ReplaceLane) {
+		let name = v.op_type().into_name();
+
+		self.local_set.insert(("replace_lane", name));
+	}
+
 	fn visit_memory_size(&mut self, m: &MemorySize) {
 		self.memory_set.insert(m.memory());
 	}
@@ -72,6 +92,42 @@ impl Visitor for Visit {
 	fn visit_memory_fill(&mut self, m: &MemoryFill) {
 		self.memory_set.insert(m.destination().memory());
 	}
+
+	fn visit_memory_init(&mut self, m: &MemoryInit) {
+		self.memory_set.insert(m.destination().memory());
+	}
+
+	fn visit_unreachable(&mut self) {
+		self.local_set.insert(("trap", "unreachable"));
+	}
+
+	fn visit_call_indirect(&mut self, _: &CallIndirect) {
+		self.local_set.insert(("table", "indirect"));
+	}
+
+	fn visit_table_get(&mut self, _: &TableGet) {
+		self.local_set.insert(("table", "get"));
+	}
+
+	fn visit_table_set(&mut self, _: &TableSet) {
+		self.local_set.insert(("table", "set"));
+	}
+
+	fn visit_table_init(&mut self, _: &TableInit) {
+		self.local_set.insert(("table", "init"));
+	}
+
+	fn visit_table_grow(&mut self, _: &TableGrow) {
+		self.local_set.insert(("table", "grow"));
+	}
+
+	fn visit_table_fill(&mut self, _: &TableFill) {
+		self.local_set.insert(("table", "fill"));
+	}
+
+	fn visit_table_copy(&mut self, _: &TableCopy) {
+		self.local_set.insert(("table", "copy"));
+	}
 }
 
 pub fn visit(ast: &FuncData) -> (BTreeSet<(&'static str, &'static str)>, BTreeSet<usize>) {
@@ -80,6 +136,10 @@ pub fn visit(ast: &FuncData) -> (BTreeSet<(&'static str, &'static str)>, BTreeSe
 		memory_set: BTreeSet::new(),
 	};
 
+	if ast.local_data().contains(&ValType::V128) {
+		visit.local_set.insert(("v128", "ZERO"));
+	}
+
 	ast.accept(&mut visit);
 
 	(visit.local_set, visit.memory_set)
@@ -4,9 +4,12 @@ use std::{
 };
 
 use wasm_ast::node::{
-	BinOp, CmpOp, Expression, GetGlobal, LoadAt, Local, MemorySize, Select, Temporary, UnOp, Value,
+	BinOp, CmpOp, Expression, ExtractLane, GetGlobal, LoadAt, Local, MemorySize, RefIsNull,
+	ReplaceLane, Select, TableGet, TableSize, Temporary, UnOp, Value,
 };
 
+use wasmparser::ValType;
+
 use crate::analyzer::into_string::{IntoName, IntoNameTuple, TryIntoSymbol};
 
 use super::manager::{write_separated, Driver, Manager};
@@ -27,6 +30,17 @@ macro_rules! impl_write_number {
 
 impl Driver for Select {
 	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		if let Some(ValType::Ref(_)) = self.ty() {
+			write!(w, "rt.select(")?;
+			Condition(self.condition()).write(mng, w)?;
+			write!(w, ", ")?;
+			self.on_true().write(mng, w)?;
+			write!(w, ", ")?;
+			self.on_false().write(mng, w)?;
+
+			return write!(w, ")");
+		}
+
 		write!(w, "(")?;
 		Condition(self.condition()).write(mng, w)?;
 		write!(w, " and ")?;
@@ -89,6 +103,16 @@ impl Driver for MemorySize {
 	}
 }
 
+fn write_v128(number: u128, w: &mut dyn Write) -> Result<()> {
+	write!(w, "v128_from_u32(")?;
+	write_separated(
+		(0..128).step_by(32),
+		|i, w| write!(w, "{}", (number >> i) as u32),
+		w,
+	)?;
+	write!(w, ")")
+}
+
 impl_write_number!(write_f32, f32);
 impl_write_number!(write_f64, f64);
 
@@ -99,6 +123,9 @@ impl Driver for Value {
 			Self::I64(i) => write!(w, "{i}LL"),
 			Self::F32(f) => write_f32(*f, w),
 			Self::F64(f) => write_f64(*f, w),
+			Self::V128(v) => write_v128(*v, w),
+			Self::RefNull => write!(w, "nil"),
+			Self::RefFunc(f) => write!(w, "FUNC_LIST[{f}]"),
 		}
 	}
 }
@@ -132,6 +159,28 @@ impl Driver for BinOp {
 	}
 }
 
+impl Driver for ExtractLane {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		let name = self.op_type().into_name();
+
+		write!(w, "extract_lane_{name}(")?;
+		self.rhs().write(mng, w)?;
+		write!(w, ", {})", self.lane())
+	}
+}
+
+impl Driver for ReplaceLane {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		let name = self.op_type().into_name();
+
+		write!(w, "replace_lane_{name}(")?;
+		self.lhs().write(mng, w)?;
+		write!(w, ", {}, ", self.lane())?;
+		self.rhs().write(mng, w)?;
+		write!(w, ")")
+	}
+}
+
 struct CmpOpBoolean<'a>(&'a CmpOp);
 
 impl Driver for CmpOpBoolean<'_> {
@@ -162,15 +211,42 @@ impl Driver for CmpOp {
 	}
 }
 
+impl Driver for RefIsNull {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		write!(w, "(")?;
+		self.value().write(mng, w)?;
+		write!(w, " == nil and 1 or 0)")
+	}
+}
+
+impl Driver for TableGet {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		write!(w, "table_get(TABLE_LIST[{}], ", self.table())?;
+		self.index().write(mng, w)?;
+		write!(w, ")")
+	}
+}
+
+impl Driver for TableSize {
+	fn write(&self, _mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		write!(w, "TABLE_LIST[{}].min", self.table())
+	}
+}
+
 pub struct Condition<'a>(pub &'a Expression);
 
 impl Driver for Condition<'_> {
 	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		if let Expression::CmpOp(node) = self.0 {
-			CmpOpBoolean(node).write(mng, w)
-		} else {
-			self.0.write(mng, w)?;
-			write!(w, " ~= 0")
+		match self.0 {
+			Expression::CmpOp(node) => CmpOpBoolean(node).write(mng, w),
+			Expression::RefIsNull(node) => {
+				node.value().write(mng, w)?;
+				write!(w, " == nil")
+			}
+			_ => {
+				self.0.write(mng, w)?;
+				write!(w, " ~= 0")
+			}
 		}
 	}
 }
@@ -185,9 +261,14 @@ impl Driver for Expression {
 			Self::LoadAt(e) => e.write(mng, w),
 			Self::MemorySize(e) => e.write(mng, w),
 			Self::Value(e) => e.write(mng, w),
+			Self::RefIsNull(e) => e.write(mng, w),
+			Self::TableGet(e) => e.write(mng, w),
+			Self::TableSize(e) => e.write(mng, w),
 			Self::UnOp(e) => e.write(mng, w),
 			Self::BinOp(e) => e.write(mng, w),
 			Self::CmpOp(e) => e.write(mng, w),
+			Self::ExtractLane(e) => e.write(mng, w),
+			Self::ReplaceLane(e) => e.write(mng, w),
 		}
 	}
 }
@@ -4,8 +4,9 @@ use std::{
 };
 
 use wasm_ast::node::{
-	Block, Br, BrIf, BrTable, Call, CallIndirect, FuncData, If, LabelType, MemoryCopy, MemoryFill,
-	MemoryGrow, ResultList, SetGlobal, SetLocal, SetTemporary, Statement, StoreAt, Terminator,
+	Block, Br, BrIf, BrTable, Call, CallIndirect, DataDrop, ElemDrop, FuncData, If, LabelType,
+	MemoryCopy, MemoryFill, MemoryGrow, MemoryInit, ResultList, SetGlobal, SetLocal, SetTemporary,
+	Statement, StoreAt, TableCopy, TableFill, TableGrow, TableInit, TableSet, Terminator,
 };
 use wasmparser::ValType;
 
@@ -135,9 +136,19 @@ impl Driver for BrTable {
 impl Driver for Terminator {
 	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
 		match self {
-			Self::Unreachable => line!(mng, w, r#"error("out of code bounds")"#),
+			Self::Unreachable => line!(mng, w, "error(trap_unreachable)"),
 			Self::Br(s) => s.write(mng, w),
 			Self::BrTable(s) => s.write(mng, w),
+			Self::ReturnCall(s) => {
+				indented!(mng, w, "do return ")?;
+				s.write(mng, w)?;
+				writeln!(w, " end")
+			}
+			Self::ReturnCallIndirect(s) => {
+				indented!(mng, w, "do return ")?;
+				s.write(mng, w)?;
+				writeln!(w, " end")
+			}
 		}
 	}
 }
@@ -236,9 +247,12 @@ impl Driver for CallIndirect {
 			write!(w, " = ")?;
 		}
 
-		write!(w, "TABLE_LIST[{}].data[", self.table())?;
+		let table = self.table();
+		let ty = self.ty();
+
+		write!(w, "table_indirect(TABLE_LIST[{table}], ")?;
 		self.index().write(mng, w)?;
-		write!(w, "](")?;
+		write!(w, ", TYPE_LIST[{ty}])(")?;
 		self.param_list().write(mng, w)?;
 		write!(w, ")")
 	}
@@ -286,6 +300,80 @@ impl Driver for StoreAt {
 	}
 }
 
+impl Driver for TableSet {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		write!(w, "table_set(TABLE_LIST[{}], ", self.table())?;
+		self.index().write(mng, w)?;
+		write!(w, ", ")?;
+		self.value().write(mng, w)?;
+		write!(w, ")")
+	}
+}
+
+impl Driver for TableInit {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		let table = self.table();
+		let element = self.element();
+
+		write!(w, "table_init(TABLE_LIST[{table}], ELEM_LIST[{element}], ")?;
+		self.destination().write(mng, w)?;
+		write!(w, ", ")?;
+		self.offset().write(mng, w)?;
+		write!(w, ", ")?;
+		self.size().write(mng, w)?;
+		write!(w, ")")
+	}
+}
+
+impl Driver for TableGrow {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		let table = self.table();
+
+		self.result().write(mng, w)?;
+		write!(w, " = table_grow(TABLE_LIST[{table}], ")?;
+		self.value().write(mng, w)?;
+		write!(w, ", ")?;
+		self.size().write(mng, w)?;
+		write!(w, ")")
+	}
+}
+
+impl Driver for TableFill {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		write!(w, "table_fill(TABLE_LIST[{}], ", self.table())?;
+		self.destination().write(mng, w)?;
+		write!(w, ", ")?;
+		self.value().write(mng, w)?;
+		write!(w, ", ")?;
+		self.size().write(mng, w)?;
+		write!(w, ")")
+	}
+}
+
+impl Driver for TableCopy {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		let destination = self.destination_table();
+		let source = self.source_table();
+
+		write!(
+			w,
+			"table_copy(TABLE_LIST[{destination}], TABLE_LIST[{source}], "
+		)?;
+		self.destination().write(mng, w)?;
+		write!(w, ", ")?;
+		self.source().write(mng, w)?;
+		write!(w, ", ")?;
+		self.size().write(mng, w)?;
+		write!(w, ")")
+	}
+}
+
+impl Driver for ElemDrop {
+	fn write(&self, _mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		write!(w, "ELEM_LIST[{}] = nil", self.element())
+	}
+}
+
 impl Driver for MemoryGrow {
 	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
 		let memory = self.memory();
@@ -326,6 +414,26 @@ impl Driver for MemoryFill {
 	}
 }
 
+impl Driver for MemoryInit {
+	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		let memory = self.destination().memory();
+
+		write!(w, "rt.store.init(memory_at_{memory}, ")?;
+		self.destination().pointer().write(mng, w)?;
+		write!(w, ", DATA_LIST[{}], ", self.data())?;
+		self.offset().write(mng, w)?;
+		write!(w, ", ")?;
+		self.size().write(mng, w)?;
+		write!(w, ")")
+	}
+}
+
+impl Driver for DataDrop {
+	fn write(&self, _mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+		write!(w, "DATA_LIST[{}] = nil", self.data())
+	}
+}
+
 fn write_stat(stat: &dyn Driver, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
 	indentation!(mng, w)?;
 	stat.write(mng, w)?;
@@ -347,6 +455,14 @@ impl Driver for Statement {
 			Self::MemoryGrow(s) => write_stat(s, mng, w),
 			Self::MemoryCopy(s) => write_stat(s, mng, w),
 			Self::MemoryFill(s) => write_stat(s, mng, w),
+			Self::MemoryInit(s) => write_stat(s, mng, w),
+			Self::DataDrop(s) => write_stat(s, mng, w),
+			Self::TableSet(s) => write_stat(s, mng, w),
+			Self::TableInit(s) => write_stat(s, mng, w),
+			Self::TableGrow(s) => write_stat(s, mng, w),
+			Self::TableFill(s) => write_stat(s, mng, w),
+			Self::TableCopy(s) => write_stat(s, mng, w),
+			Self::ElemDrop(s) => write_stat(s, mng, w),
 		}
 	}
 }
@@ -361,6 +477,8 @@ const fn type_to_zero(typ: ValType) -> &'static str {
 	match typ {
 		ValType::F32 | ValType::F64 => "0.0",
 		ValType::I64 => "0LL",
+		ValType::Ref(_) => "nil",
+		ValType::V128 => "v128_ZERO",
 		_ => "0",
 	}
 }
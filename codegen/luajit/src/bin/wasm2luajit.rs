@@ -1,6 +1,6 @@
 use std::io::{ErrorKind, Result, Write};
 
-use wasm_ast::module::Module;
+use wasm_ast::{error::Error, module::Module};
 
 fn load_arg_source() -> Result<Vec<u8>> {
 	let mut arguments = std::env::args();
@@ -28,7 +28,7 @@ fn do_runtime(lock: &mut dyn Write) -> Result<()> {
 
 fn main() -> Result<()> {
 	let data = load_arg_source()?;
-	let wasm = Module::try_from_data(&data).unwrap();
+	let wasm = Module::try_from_data(&data).map_err(Error::from)?;
 
 	let lock = &mut std::io::stdout().lock();
 
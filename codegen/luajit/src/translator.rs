@@ -4,13 +4,14 @@ use std::{
 };
 
 use wasm_ast::{
+	error::Error,
 	factory::Factory,
 	module::{External, Module, TypeInfo},
 	node::{FuncData, Statement},
 };
 use wasmparser::{
-	ConstExpr, Data, DataKind, Element, ElementItems, ElementKind, Export, Import, Operator,
-	OperatorsReader,
+	ConstExpr, Data, DataKind, Element, ElementItems, ElementKind, Export, FuncType, Import,
+	Operator, OperatorsReader, Type, ValType,
 };
 
 use crate::{
@@ -34,10 +35,10 @@ impl AsIEName for External {
 	}
 }
 
-fn reader_to_code(reader: OperatorsReader) -> Vec<Operator> {
+fn reader_to_code(reader: OperatorsReader) -> Result<Vec<Operator>> {
 	let parsed: std::result::Result<_, _> = reader.into_iter().collect();
 
-	parsed.unwrap()
+	parsed.map_err(|error| Error::from(error).into())
 }
 
 fn write_named_array(name: &str, len: usize, w: &mut dyn Write) -> Result<()> {
@@ -47,8 +48,8 @@ fn write_named_array(name: &str, len: usize, w: &mut dyn Write) -> Result<()> {
 }
 
 fn write_constant(init: &ConstExpr, type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	let code = reader_to_code(init.get_operators_reader());
-	let func = Factory::from_type_info(type_info).create_anonymous(&code);
+	let code = reader_to_code(init.get_operators_reader())?;
+	let func = Factory::from_type_info(type_info).create_anonymous(&code)?;
 
 	if let Some(Statement::SetTemporary(stat)) = func.code().code().last() {
 		stat.value().write(&mut Manager::empty(), w)
@@ -66,8 +67,14 @@ fn write_import_of(list: &[Import], wanted: External, w: &mut dyn Write) -> Resu
 		.filter(|v| External::from(v.ty) == wanted)
 		.enumerate()
 	{
+		let module = module.as_bytes().escape_ascii();
+		let name = name.as_bytes().escape_ascii();
+
 		write!(w, "\t")?;
-		writeln!(w, r#"{upper}[{i}] = wasm["{module}"].{lower}["{name}"]"#)?;
+		writeln!(
+			w,
+			r#"{upper}[{i}] = rt.import(imports, "{module}", "{name}", "{lower}")"#
+		)?;
 	}
 
 	Ok(())
@@ -80,6 +87,8 @@ fn write_export_of(list: &[Export], wanted: External, w: &mut dyn Write) -> Resu
 	writeln!(w, "\t\t{lower} = {{")?;
 
 	for Export { name, index, .. } in list.iter().filter(|v| External::from(v.kind) == wanted) {
+		let name = name.as_bytes().escape_ascii();
+
 		write!(w, "\t\t\t")?;
 		writeln!(w, r#"["{name}"] = {upper}[{index}],"#)?;
 	}
@@ -101,6 +110,45 @@ fn write_export_list(list: &[Export], w: &mut dyn Write) -> Result<()> {
 	write_export_of(list, External::Global, w)
 }
 
+// Types are matched by structure, even across modules, so each
+// signature is spelled out instead of going by its type index
+fn write_func_type(ty: &FuncType, w: &mut dyn Write) -> Result<()> {
+	write!(w, "\"")?;
+
+	for param in ty.params() {
+		write!(w, "{param} ")?;
+	}
+
+	write!(w, "->")?;
+
+	for result in ty.results() {
+		write!(w, " {result}")?;
+	}
+
+	write!(w, "\"")
+}
+
+fn write_signature_list(wasm: &Module, type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
+	for (i, ty) in wasm.type_section().iter().enumerate() {
+		let Type::Func(ty) = ty else {
+			continue;
+		};
+
+		write!(w, "\tTYPE_LIST[{i}] = ")?;
+		write_func_type(ty, w)?;
+		writeln!(w)?;
+	}
+
+	for index in 0..wasm.function_space() {
+		write!(w, "\tFUNC_LIST[{index}] = ")?;
+		write!(w, "rt.table.register(FUNC_LIST[{index}], ")?;
+		write_func_type(type_info.func_type(index), w)?;
+		writeln!(w, ")")?;
+	}
+
+	Ok(())
+}
+
 fn write_table_list(wasm: &Module, w: &mut dyn Write) -> Result<()> {
 	let offset = wasm.import_count(External::Table);
 	let table = wasm.table_section();
@@ -108,7 +156,7 @@ fn write_table_list(wasm: &Module, w: &mut dyn Write) -> Result<()> {
 	for (i, table) in table.iter().enumerate() {
 		let index = offset + i;
 		let min = table.ty.initial;
-		let max = table.ty.maximum.unwrap_or(0xFFFF);
+		let max = table.ty.maximum.unwrap_or(0xFFFF_FFFF);
 
 		writeln!(
 			w,
@@ -126,7 +174,14 @@ fn write_memory_list(wasm: &Module, w: &mut dyn Write) -> Result<()> {
 	for (i, ty) in memory.iter().enumerate() {
 		let index = offset + i;
 		let min = ty.initial;
-		let max = ty.maximum.unwrap_or(0xFFFF);
+		let max = ty.maximum.unwrap_or(0x1_0000);
+
+		if ty.shared {
+			writeln!(
+				w,
+				"\t-- shared memory, atomics are lowered to plain accesses (single-threaded approximation)"
+			)?;
+		}
 
 		writeln!(w, "\tMEMORY_LIST[{index}] = rt.allocator.new({min}, {max})")?;
 	}
@@ -149,13 +204,57 @@ fn write_global_list(wasm: &Module, type_info: &TypeInfo, w: &mut dyn Write) ->
 	Ok(())
 }
 
+fn write_element_items(element: &Element, type_info: &TypeInfo, w: &mut dyn Write) -> Result<u32> {
+	write!(w, "{{ ")?;
+
+	match element.items.clone() {
+		ElementItems::Functions(functions) => {
+			for index in functions {
+				let index = index.map_err(Error::from)?;
+				write!(w, "FUNC_LIST[{index}],")?;
+			}
+		}
+		ElementItems::Expressions(expressions) => {
+			for init in expressions {
+				let init = init.map_err(Error::from)?;
+				write_constant(&init, type_info, w)?;
+				write!(w, ",")?;
+			}
+		}
+	}
+
+	write!(w, " }}")?;
+
+	// Null references leave holes in the list, so the length has
+	// to come from the section instead of `#data`
+	let len = match &element.items {
+		ElementItems::Functions(list) => list.count(),
+		ElementItems::Expressions(list) => list.count(),
+	};
+
+	Ok(len)
+}
+
+// Passive segments are kept around for `table.init`, while active and
+// declared ones count as dropped once the module is instantiated
 fn write_element_list(list: &[Element], type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	for element in list {
-		let ElementKind::Active { table_index: index, offset_expr: init } = element.kind else {
-			unimplemented!("passive elements not supported")
-		};
+	for (i, element) in list.iter().enumerate() {
+		let (index, init) = match element.kind {
+			ElementKind::Passive => {
+				write!(w, "\tELEM_LIST[{i}] = {{ data = ")?;
 
-		let index = index.unwrap_or(0);
+				let len = write_element_items(element, type_info, w)?;
+
+				writeln!(w, ", len = {len} }}")?;
+
+				continue;
+			}
+			ElementKind::Declared => continue,
+			ElementKind::Active {
+				table_index,
+				offset_expr,
+			} => (table_index.unwrap_or(0), offset_expr),
+		};
 
 		writeln!(w, "\tdo")?;
 		writeln!(w, "\t\tlocal target = TABLE_LIST[{index}].data")?;
@@ -164,35 +263,51 @@ fn write_element_list(list: &[Element], type_info: &TypeInfo, w: &mut dyn Write)
 		write_constant(&init, type_info, w)?;
 
 		writeln!(w)?;
-		write!(w, "\t\tlocal data = {{ ")?;
-
-		match element.items.clone() {
-			ElementItems::Functions(functions) => {
-				for index in functions {
-					let index = index.unwrap();
-					write!(w, "FUNC_LIST[{index}],")?;
-				}
-			}
-			ElementItems::Expressions(expressions) => {
-				for init in expressions {
-					let init = init.unwrap();
-					write_constant(&init, type_info, w)?;
-				}
-			}
-		}
+		write!(w, "\t\tlocal data = ")?;
 
-		writeln!(w, " }}")?;
-		writeln!(w, "\t\ttable.move(data, 1, #data, offset, target)")?;
+		let len = write_element_items(element, type_info, w)?;
+
+		writeln!(w)?;
+		writeln!(w, "\t\ttable.move(data, 1, {len}, offset, target)")?;
 		writeln!(w, "\tend")?;
 	}
 
 	Ok(())
 }
 
+// Lua 5.1 has no `\x` escapes, so bytes outside of printable ASCII are
+// written in decimal, padded out only when a digit follows them
+fn to_data_string(data: &[u8]) -> String {
+	let mut result = String::with_capacity(data.len() + 2);
+
+	result.push('"');
+
+	for (i, &byte) in data.iter().enumerate() {
+		match byte {
+			b'"' | b'\\' => {
+				result.push('\\');
+				result.push(byte.into());
+			}
+			b' '..=b'~' => result.push(byte.into()),
+			_ if data.get(i + 1).is_some_and(u8::is_ascii_digit) => {
+				result.push_str(&format!("\\{byte:03}"));
+			}
+			_ => result.push_str(&format!("\\{byte}")),
+		}
+	}
+
+	result.push('"');
+	result
+}
+
 fn write_data_list(list: &[Data], type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	for data in list {
+	for (i, data) in list.iter().enumerate() {
 		let (index, init) = match data.kind {
-			DataKind::Passive => unimplemented!("passive data not supported"),
+			DataKind::Passive => {
+				writeln!(w, "\tDATA_LIST[{i}] = {}", to_data_string(data.data))?;
+
+				continue;
+			}
 			DataKind::Active {
 				memory_index,
 				offset_expr,
@@ -201,20 +316,20 @@ fn write_data_list(list: &[Data], type_info: &TypeInfo, w: &mut dyn Write) -> Re
 
 		write!(w, "\trt.store.string(MEMORY_LIST[{index}], ")?;
 		write_constant(&init, type_info, w)?;
-		writeln!(w, r#","{}")"#, data.data.escape_ascii())?;
+		writeln!(w, ",{})", to_data_string(data.data))?;
 	}
 
 	Ok(())
 }
 
-fn build_func_list(wasm: &Module, type_info: &TypeInfo) -> Vec<FuncData> {
+fn build_func_list(wasm: &Module, type_info: &TypeInfo) -> wasm_ast::error::Result<Vec<FuncData>> {
 	let offset = wasm.import_count(External::Func);
 	let mut builder = Factory::from_type_info(type_info);
 
 	wasm.code_section()
 		.iter()
 		.enumerate()
-		.map(|f| builder.create_indexed(f.0 + offset, f.1).unwrap())
+		.map(|f| builder.create_indexed(f.0 + offset, f.1))
 		.collect()
 }
 
@@ -237,10 +352,23 @@ fn write_local_operation(head: &str, tail: &str, w: &mut dyn Write) -> Result<()
 	writeln!(w)
 }
 
-fn write_localize_used(func_list: &[FuncData], w: &mut dyn Write) -> Result<BTreeSet<usize>> {
+fn write_localize_used(
+	wasm: &Module,
+	func_list: &[FuncData],
+	w: &mut dyn Write,
+) -> Result<BTreeSet<usize>> {
 	let mut loc_set = BTreeSet::new();
 	let mut mem_set = BTreeSet::new();
 
+	let has_global_v128 = wasm
+		.global_section()
+		.iter()
+		.any(|g| g.ty.content_type == ValType::V128);
+
+	if has_global_v128 {
+		loc_set.insert(("v128", "from_u32"));
+	}
+
 	for (loc, mem) in func_list.iter().map(localize::visit) {
 		loc_set.extend(loc);
 		mem_set.extend(mem);
@@ -284,6 +412,7 @@ fn write_module_start(
 	w: &mut dyn Write,
 ) -> Result<()> {
 	writeln!(w, "local function run_init_code()")?;
+	write_signature_list(wasm, type_info, w)?;
 	write_table_list(wasm, w)?;
 	write_memory_list(wasm, w)?;
 	write_global_list(wasm, type_info, w)?;
@@ -291,7 +420,7 @@ fn write_module_start(
 	write_data_list(wasm.data_section(), type_info, w)?;
 	writeln!(w, "end")?;
 
-	writeln!(w, "return function(wasm)")?;
+	writeln!(w, "return function(imports)")?;
 	write_import_list(wasm.import_section(), w)?;
 	writeln!(w, "\trun_init_code()")?;
 
@@ -310,31 +439,38 @@ fn write_module_start(
 }
 
 /// # Errors
-/// Returns `Err` if writing to `Write` failed.
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
 pub fn from_inst_list(code: &[Operator], type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	let ast = Factory::from_type_info(type_info).create_anonymous(code);
+	let ast = Factory::from_type_info(type_info).create_anonymous(code)?;
 
 	ast.write(&mut Manager::function(&ast), w)
 }
 
 /// # Errors
-/// Returns `Err` if writing to `Write` failed.
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
 pub fn from_module_typed(wasm: &Module, type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	let func_list = build_func_list(wasm, type_info);
-	let mem_set = write_localize_used(&func_list, w)?;
+	let func_list = build_func_list(wasm, type_info)?;
+	let mem_set = write_localize_used(wasm, &func_list, w)?;
 
 	writeln!(w, "local table_new = require(\"table.new\")")?;
+	write_named_array("TYPE_LIST", wasm.type_section().len(), w)?;
 	write_named_array("FUNC_LIST", wasm.function_space(), w)?;
 	write_named_array("TABLE_LIST", wasm.table_space(), w)?;
 	write_named_array("MEMORY_LIST", wasm.memory_space(), w)?;
 	write_named_array("GLOBAL_LIST", wasm.global_space(), w)?;
+	write_named_array("ELEM_LIST", wasm.element_space(), w)?;
+	write_named_array("DATA_LIST", wasm.data_space(), w)?;
 
 	write_func_list(wasm, &func_list, w)?;
 	write_module_start(wasm, type_info, &mem_set, w)
 }
 
 /// # Errors
-/// Returns `Err` if writing to `Write` failed.
+/// Returns `Err` if writing to `Write` failed, the code contains an
+/// instruction that cannot be translated, or the code is structurally
+/// malformed in a way validation would have caught.
 pub fn from_module_untyped(wasm: &Module, w: &mut dyn Write) -> Result<()> {
 	let type_info = TypeInfo::from_module(wasm);
 
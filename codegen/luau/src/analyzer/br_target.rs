@@ -1,13 +1,14 @@
 use std::collections::HashMap;
 
 use wasm_ast::{
-	node::{Br, BrIf, BrTable, FuncData},
+	node::{Block, Br, BrIf, BrTable, FuncData, LabelType},
 	visit::{Driver, Visitor},
 };
 
 struct Visit {
 	br_map: HashMap<usize, usize>,
 	has_branch: bool,
+	has_loop: bool,
 }
 
 impl Visit {
@@ -19,6 +20,12 @@ impl Visit {
 }
 
 impl Visitor for Visit {
+	fn visit_block(&mut self, block: &Block) {
+		if block.label_type() == Some(LabelType::Backward) {
+			self.has_loop = true;
+		}
+	}
+
 	fn visit_br(&mut self, stat: Br) {
 		self.set_branch(stat);
 	}
@@ -45,13 +52,14 @@ impl Visitor for Visit {
 	}
 }
 
-pub fn visit(ast: &FuncData) -> (HashMap<usize, usize>, bool) {
+pub fn visit(ast: &FuncData) -> (HashMap<usize, usize>, bool, bool) {
 	let mut visit = Visit {
 		br_map: HashMap::new(),
 		has_branch: false,
+		has_loop: false,
 	};
 
 	ast.accept(&mut visit);
 
-	(visit.br_map, visit.has_branch)
+	(visit.br_map, visit.has_branch, visit.has_loop)
 }
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use wasm_ast::{
+	node::{Block, Br, FuncData, LabelType, Local, Statement, Terminator},
+	visit::{Driver, Visitor},
+};
+use wasmparser::ValType;
+
+#[derive(Clone, PartialEq, Eq)]
+struct LocalSet {
+	data: Vec<u64>,
+}
+
+impl LocalSet {
+	fn new(len: usize) -> Self {
+		Self {
+			data: vec![0; len.div_ceil(64)],
+		}
+	}
+
+	fn contains(&self, index: usize) -> bool {
+		self.data[index / 64] & (1 << (index % 64)) != 0
+	}
+
+	fn insert(&mut self, index: usize) {
+		self.data[index / 64] |= 1 << (index % 64);
+	}
+
+	fn remove(&mut self, index: usize) {
+		self.data[index / 64] &= !(1 << (index % 64));
+	}
+
+	fn union(&mut self, other: &Self) {
+		for (a, b) in self.data.iter_mut().zip(&other.data) {
+			*a |= b;
+		}
+	}
+
+	fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+		self.data.iter().enumerate().flat_map(|(i, &word)| {
+			(0..64)
+				.filter(move |bit| word & (1 << bit) != 0)
+				.map(move |bit| i * 64 + bit)
+		})
+	}
+}
+
+struct Uses<'a> {
+	live: &'a mut LocalSet,
+}
+
+impl Visitor for Uses<'_> {
+	fn visit_get_local(&mut self, local: Local) {
+		self.live.insert(local.var());
+	}
+}
+
+// Liveness is found backwards over the structured code. Branches read the
+// set live at their label, which for a loop is its entry and only settles
+// after iterating, so the entries found so far are kept between passes.
+struct Liveness {
+	len: usize,
+	label_list: Vec<LocalSet>,
+	loop_map: HashMap<usize, LocalSet>,
+	interference: Vec<LocalSet>,
+}
+
+impl Liveness {
+	fn add_uses<T>(live: &mut LocalSet, node: &T)
+	where
+		for<'a> T: Driver<Uses<'a>>,
+	{
+		node.accept(&mut Uses { live });
+	}
+
+	fn add_interference(&mut self, var: usize, live: &LocalSet) {
+		for other in live.iter().filter(|&v| v != var) {
+			self.interference[var].insert(other);
+			self.interference[other].insert(var);
+		}
+	}
+
+	fn target_of(&self, br: Br) -> &LocalSet {
+		&self.label_list[self.label_list.len() - 1 - br.target()]
+	}
+
+	fn visit_terminator(&self, last: &Terminator) -> LocalSet {
+		match last {
+			Terminator::Unreachable => LocalSet::new(self.len),
+			Terminator::Br(br) => self.target_of(*br).clone(),
+			Terminator::BrTable(table) => {
+				let mut live = self.target_of(table.default()).clone();
+
+				for &br in table.data() {
+					live.union(self.target_of(br));
+				}
+
+				Self::add_uses(&mut live, table.condition());
+				live
+			}
+			Terminator::ReturnCall(call) => {
+				let mut live = LocalSet::new(self.len);
+
+				Self::add_uses(&mut live, call);
+				live
+			}
+			Terminator::ReturnCallIndirect(call) => {
+				let mut live = LocalSet::new(self.len);
+
+				Self::add_uses(&mut live, call);
+				live
+			}
+		}
+	}
+
+	fn visit_statement(&mut self, stat: &Statement, mut live: LocalSet) -> LocalSet {
+		match stat {
+			Statement::Block(block) => return self.visit_block(block, &live),
+			Statement::BrIf(stat) => {
+				live.union(self.target_of(stat.target()));
+
+				Self::add_uses(&mut live, stat.condition());
+			}
+			Statement::If(stat) => {
+				let mut result = self.visit_block(stat.on_true(), &live);
+
+				if let Some(on_false) = stat.on_false() {
+					result.union(&self.visit_block(on_false, &live));
+				} else {
+					result.union(&live);
+				}
+
+				Self::add_uses(&mut result, stat.condition());
+
+				return result;
+			}
+			Statement::SetLocal(stat) => {
+				let var = stat.var().var();
+
+				self.add_interference(var, &live);
+				live.remove(var);
+
+				Self::add_uses(&mut live, stat.value());
+			}
+			_ => Self::add_uses(&mut live, stat),
+		}
+
+		live
+	}
+
+	fn visit_code(&mut self, block: &Block, live_out: &LocalSet) -> LocalSet {
+		let mut live = match block.last() {
+			Some(last) => self.visit_terminator(last),
+			None => live_out.clone(),
+		};
+
+		for stat in block.code().iter().rev() {
+			live = self.visit_statement(stat, live);
+		}
+
+		live
+	}
+
+	fn visit_block(&mut self, block: &Block, live_out: &LocalSet) -> LocalSet {
+		if block.label_type() != Some(LabelType::Backward) {
+			self.label_list.push(live_out.clone());
+
+			let live = self.visit_code(block, live_out);
+
+			self.label_list.pop();
+
+			return live;
+		}
+
+		let id = std::ptr::from_ref(block) as usize;
+		let mut entry = self
+			.loop_map
+			.get(&id)
+			.cloned()
+			.unwrap_or_else(|| LocalSet::new(self.len));
+
+		loop {
+			self.label_list.push(entry.clone());
+
+			let live = self.visit_code(block, live_out);
+
+			self.label_list.pop();
+
+			if live == entry {
+				break;
+			}
+
+			entry = live;
+		}
+
+		self.loop_map.insert(id, entry.clone());
+
+		entry
+	}
+}
+
+fn find_interference(ast: &FuncData, len: usize) -> Vec<LocalSet> {
+	let mut liveness = Liveness {
+		len,
+		label_list: Vec::new(),
+		loop_map: HashMap::new(),
+		interference: vec![LocalSet::new(len); len],
+	};
+
+	let live_in = liveness.visit_block(ast.code(), &LocalSet::new(len));
+
+	// Parameters and the zeroed locals read before being set all start
+	// out at function entry
+	for var in (0..ast.num_param()).chain(live_in.iter()) {
+		liveness.add_interference(var, &live_in);
+	}
+
+	liveness.interference
+}
+
+/// Assigns every local a slot, letting locals of the same type share one
+/// whenever they are never live at the same time. Parameters keep their
+/// own slots, and the types of the slots after them are returned as well.
+#[must_use]
+pub fn visit(ast: &FuncData) -> (Vec<usize>, Vec<ValType>) {
+	let num_param = ast.num_param();
+	let len = num_param + ast.local_data().len();
+	let interference = find_interference(ast, len);
+
+	let mut slot_list: Vec<(ValType, Vec<usize>)> = ast
+		.param_data()
+		.iter()
+		.enumerate()
+		.map(|(i, &typ)| (typ, vec![i]))
+		.collect();
+
+	let mut local_map: Vec<usize> = (0..num_param).collect();
+
+	for (var, &typ) in ast.local_data().iter().enumerate() {
+		let var = num_param + var;
+		let slot = slot_list.iter().position(|(other, list)| {
+			*other == typ && list.iter().all(|&v| !interference[var].contains(v))
+		});
+
+		let slot = slot.unwrap_or_else(|| {
+			slot_list.push((typ, Vec::new()));
+			slot_list.len() - 1
+		});
+
+		slot_list[slot].1.push(var);
+		local_map.push(slot);
+	}
+
+	let type_list = slot_list.iter().skip(num_param).map(|v| v.0).collect();
+
+	(local_map, type_list)
+}
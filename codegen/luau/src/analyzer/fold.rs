@@ -0,0 +1,126 @@
+use wasm_ast::node::{BinOp, BinOpType, Expression, UnOp, UnOpType, Value};
+
+// Only operations the runtime carries out exactly are folded. `f32` math
+// is done in double precision there, and any NaN result is left alone
+// since its sign and payload depend on the machine.
+fn value_of(expression: &Expression) -> Option<Value> {
+	match expression {
+		Expression::Value(value) => Some(*value),
+		Expression::UnOp(op) => un_op(op),
+		Expression::BinOp(op) => bin_op(op),
+		_ => None,
+	}
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn un_op_i32(op_type: UnOpType, rhs: i32) -> Option<Value> {
+	let result = match op_type {
+		UnOpType::Clz_I32 => rhs.leading_zeros() as i32,
+		UnOpType::Ctz_I32 => rhs.trailing_zeros() as i32,
+		UnOpType::Popcnt_I32 => rhs.count_ones() as i32,
+		UnOpType::Extend_I32_N8 => i32::from(rhs as i8),
+		UnOpType::Extend_I32_N16 => i32::from(rhs as i16),
+		UnOpType::Extend_I64_I32 => return Some(Value::I64(rhs.into())),
+		UnOpType::Extend_I64_U32 => return Some(Value::I64((rhs as u32).into())),
+		_ => return None,
+	};
+
+	Some(Value::I32(result))
+}
+
+fn un_op_i64(op_type: UnOpType, rhs: i64) -> Option<Value> {
+	let result = match op_type {
+		UnOpType::Clz_I64 => rhs.leading_zeros().into(),
+		UnOpType::Ctz_I64 => rhs.trailing_zeros().into(),
+		UnOpType::Popcnt_I64 => rhs.count_ones().into(),
+		UnOpType::Extend_I64_N8 => i64::from(rhs as i8),
+		UnOpType::Extend_I64_N16 => i64::from(rhs as i16),
+		UnOpType::Extend_I64_N32 => i64::from(rhs as i32),
+		UnOpType::Wrap_I32_I64 => return Some(Value::I32(rhs as i32)),
+		_ => return None,
+	};
+
+	Some(Value::I64(result))
+}
+
+/// Evaluates `op` if its operand folds down to a constant.
+pub fn un_op(op: &UnOp) -> Option<Value> {
+	match value_of(op.rhs())? {
+		Value::I32(rhs) => un_op_i32(op.op_type(), rhs),
+		Value::I64(rhs) => un_op_i64(op.op_type(), rhs),
+		_ => None,
+	}
+}
+
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+fn bin_op_i32(op_type: BinOpType, lhs: i32, rhs: i32) -> Option<i32> {
+	let shift = rhs as u32;
+	let result = match op_type {
+		BinOpType::Add_I32 => lhs.wrapping_add(rhs),
+		BinOpType::Sub_I32 => lhs.wrapping_sub(rhs),
+		BinOpType::Mul_I32 => lhs.wrapping_mul(rhs),
+		BinOpType::DivS_I32 => lhs.checked_div(rhs)?,
+		BinOpType::DivU_I32 => (lhs as u32).checked_div(rhs as u32)? as i32,
+		BinOpType::RemS_I32 => lhs.checked_rem(rhs).or((rhs == -1).then_some(0))?,
+		BinOpType::RemU_I32 => (lhs as u32).checked_rem(rhs as u32)? as i32,
+		BinOpType::And_I32 => lhs & rhs,
+		BinOpType::Or_I32 => lhs | rhs,
+		BinOpType::Xor_I32 => lhs ^ rhs,
+		BinOpType::Shl_I32 => lhs.wrapping_shl(shift),
+		BinOpType::ShrS_I32 => lhs.wrapping_shr(shift),
+		BinOpType::ShrU_I32 => (lhs as u32).wrapping_shr(shift) as i32,
+		BinOpType::Rotl_I32 => lhs.rotate_left(shift),
+		BinOpType::Rotr_I32 => lhs.rotate_right(shift),
+		_ => return None,
+	};
+
+	Some(result)
+}
+
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+fn bin_op_i64(op_type: BinOpType, lhs: i64, rhs: i64) -> Option<i64> {
+	let shift = rhs as u32;
+	let result = match op_type {
+		BinOpType::Add_I64 => lhs.wrapping_add(rhs),
+		BinOpType::Sub_I64 => lhs.wrapping_sub(rhs),
+		BinOpType::Mul_I64 => lhs.wrapping_mul(rhs),
+		BinOpType::DivS_I64 => lhs.checked_div(rhs)?,
+		BinOpType::DivU_I64 => (lhs as u64).checked_div(rhs as u64)? as i64,
+		BinOpType::RemS_I64 => lhs.checked_rem(rhs).or((rhs == -1).then_some(0))?,
+		BinOpType::RemU_I64 => (lhs as u64).checked_rem(rhs as u64)? as i64,
+		BinOpType::And_I64 => lhs & rhs,
+		BinOpType::Or_I64 => lhs | rhs,
+		BinOpType::Xor_I64 => lhs ^ rhs,
+		BinOpType::Shl_I64 => lhs.wrapping_shl(shift),
+		BinOpType::ShrS_I64 => lhs.wrapping_shr(shift),
+		BinOpType::ShrU_I64 => (lhs as u64).wrapping_shr(shift) as i64,
+		BinOpType::Rotl_I64 => lhs.rotate_left(shift),
+		BinOpType::Rotr_I64 => lhs.rotate_right(shift),
+		_ => return None,
+	};
+
+	Some(result)
+}
+
+fn bin_op_f64(op_type: BinOpType, lhs: f64, rhs: f64) -> Option<f64> {
+	let result = match op_type {
+		BinOpType::Add_F64 => lhs + rhs,
+		BinOpType::Sub_F64 => lhs - rhs,
+		BinOpType::Mul_F64 => lhs * rhs,
+		BinOpType::Div_F64 => lhs / rhs,
+		_ => return None,
+	};
+
+	(!result.is_nan()).then_some(result)
+}
+
+/// Evaluates `op` if both of its operands fold down to constants.
+/// Division and remainder are kept when they would trap.
+pub fn bin_op(op: &BinOp) -> Option<Value> {
+	match (value_of(op.lhs())?, value_of(op.rhs())?) {
+		(Value::I32(lhs), Value::I32(rhs)) => bin_op_i32(op.op_type(), lhs, rhs).map(Value::I32),
+		(Value::I64(lhs), Value::I64(rhs)) => bin_op_i64(op.op_type(), lhs, rhs).map(Value::I64),
+		(Value::F64(lhs), Value::F64(rhs)) => bin_op_f64(op.op_type(), lhs, rhs).map(Value::F64),
+		_ => None,
+	}
+}
@@ -0,0 +1,23 @@
+use wasm_ast::node::{Block, LabelType, Statement};
+
+// Each statement costs one unit, and both arms of an `if` are charged
+// up front. Loops only count their entry as they charge every iteration
+// themselves.
+pub fn block_cost(block: &Block) -> usize {
+	let inner: usize = block.code().iter().map(statement_cost).sum();
+
+	inner + usize::from(block.last().is_some())
+}
+
+fn statement_cost(stat: &Statement) -> usize {
+	match stat {
+		Statement::Block(v) if v.label_type() == Some(LabelType::Backward) => 1,
+		Statement::Block(v) => block_cost(v),
+		Statement::If(v) => {
+			let on_false = v.on_false().map_or(0, block_cost);
+
+			1 + block_cost(v.on_true()) + on_false
+		}
+		_ => 1,
+	}
+}
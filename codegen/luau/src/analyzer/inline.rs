@@ -0,0 +1,72 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use wasm_ast::{
+	node::{BrIf, Expression, FuncData, Statement, Terminator, Value},
+	visit::{Driver, Visitor},
+};
+
+#[derive(Default)]
+struct Visit {
+	size: usize,
+	is_leaf: bool,
+	reference_set: BTreeSet<usize>,
+}
+
+impl Visitor for Visit {
+	fn visit_value(&mut self, v: Value) {
+		if let Value::RefFunc(function) = v {
+			self.reference_set.insert(function);
+		}
+	}
+
+	fn visit_expression(&mut self, _: &Expression) {
+		self.size += 1;
+	}
+
+	fn visit_terminator(&mut self, v: &Terminator) {
+		self.size += 1;
+		self.is_leaf &= matches!(v, Terminator::Unreachable);
+	}
+
+	fn visit_br_if(&mut self, _: &BrIf) {
+		self.is_leaf = false;
+	}
+
+	fn visit_statement(&mut self, v: &Statement) {
+		self.size += 1;
+		self.is_leaf &= !matches!(v, Statement::Call(_) | Statement::CallIndirect(_));
+	}
+}
+
+// Only straight line functions qualify, as they never call, branch or
+// return early. This also rules out recursion. Functions that may be
+// stored in a table are left alone, along with any that `ref.func` names
+// anywhere, so indirect calls keep seeing the same function as direct ones.
+pub fn visit(
+	func_list: &[(usize, FuncData)],
+	indirect_list: impl IntoIterator<Item = usize>,
+	threshold: usize,
+) -> BTreeMap<usize, &FuncData> {
+	let mut excluded: BTreeSet<_> = indirect_list.into_iter().collect();
+	let mut candidate_list = Vec::new();
+
+	for (index, func) in func_list {
+		let mut visit = Visit {
+			is_leaf: true,
+			..Visit::default()
+		};
+
+		func.accept(&mut visit);
+
+		excluded.extend(visit.reference_set);
+
+		if visit.is_leaf && visit.size <= threshold {
+			candidate_list.push((*index, func));
+		}
+	}
+
+	candidate_list
+		.into_iter()
+		.filter(|v| !excluded.contains(&v.0))
+		.collect()
+}
@@ -1,4 +1,8 @@
-use wasm_ast::node::{BinOpType, CmpOpType, LoadType, StoreType, UnOpType};
+use wasm_ast::node::{
+	BinOpType, CmpOpType, ExtractLaneType, LoadType, ReplaceLaneType, StoreType, UnOpType,
+};
+
+use crate::ast::BinaryOp;
 
 pub trait IntoName {
 	#[must_use]
@@ -22,6 +26,7 @@ impl IntoName for LoadType {
 			Self::I64_U16 => "i64_u16",
 			Self::I64_I32 => "i64_i32",
 			Self::I64_U32 => "i64_u32",
+			Self::V128 => "v128",
 		}
 	}
 }
@@ -38,6 +43,35 @@ impl IntoName for StoreType {
 			Self::I64_N8 => "i64_n8",
 			Self::I64_N16 => "i64_n16",
 			Self::I64_N32 => "i64_n32",
+			Self::V128 => "v128",
+		}
+	}
+}
+
+impl IntoName for ExtractLaneType {
+	fn into_name(self) -> &'static str {
+		match self {
+			Self::I32_I8X16 => "i32_i8x16",
+			Self::I32_U8X16 => "i32_u8x16",
+			Self::I32_I16X8 => "i32_i16x8",
+			Self::I32_U16X8 => "i32_u16x8",
+			Self::I32X4 => "i32x4",
+			Self::I64X2 => "i64x2",
+			Self::F32X4 => "f32x4",
+			Self::F64X2 => "f64x2",
+		}
+	}
+}
+
+impl IntoName for ReplaceLaneType {
+	fn into_name(self) -> &'static str {
+		match self {
+			Self::I8X16 => "i8x16",
+			Self::I16X8 => "i16x8",
+			Self::I32X4 => "i32x4",
+			Self::I64X2 => "i64x2",
+			Self::F32X4 => "f32x4",
+			Self::F64X2 => "f64x2",
 		}
 	}
 }
@@ -79,13 +113,13 @@ impl IntoNameTuple for UnOpType {
 			Self::Truncate_I64_F64 => ("rt_truncate", "i64_f64"),
 			Self::Truncate_U64_F32 => ("rt_truncate", "u64_f32"),
 			Self::Truncate_U64_F64 => ("rt_truncate", "u64_f64"),
-			Self::Saturate_I32_F32 => ("rt_saturate", "i32_f32"),
+			Self::Saturate_I32_F32 => ("rt_saturate", "i32_f64"),
 			Self::Saturate_I32_F64 => ("rt_saturate", "i32_f64"),
-			Self::Saturate_U32_F32 => ("rt_saturate", "u32_f32"),
+			Self::Saturate_U32_F32 => ("rt_saturate", "u32_f64"),
 			Self::Saturate_U32_F64 => ("rt_saturate", "u32_f64"),
-			Self::Saturate_I64_F32 => ("rt_saturate", "i64_f32"),
+			Self::Saturate_I64_F32 => ("rt_saturate", "i64_f64"),
 			Self::Saturate_I64_F64 => ("rt_saturate", "i64_f64"),
-			Self::Saturate_U64_F32 => ("rt_saturate", "u64_f32"),
+			Self::Saturate_U64_F32 => ("rt_saturate", "u64_f64"),
 			Self::Saturate_U64_F64 => ("rt_saturate", "u64_f64"),
 			Self::Extend_I32_N8 => ("rt_extend", "i32_n8"),
 			Self::Extend_I32_N16 => ("rt_extend", "i32_n16"),
@@ -108,6 +142,13 @@ impl IntoNameTuple for UnOpType {
 			Self::Reinterpret_I64_F64 => ("rt_reinterpret", "i64_f64"),
 			Self::Reinterpret_F32_I32 => ("rt_reinterpret", "f32_i32"),
 			Self::Reinterpret_F64_I64 => ("rt_reinterpret", "f64_i64"),
+			Self::Splat_I8X16 => ("rt_v128.splat", "i8x16"),
+			Self::Splat_I16X8 => ("rt_v128.splat", "i16x8"),
+			Self::Splat_I32X4 => ("rt_v128.splat", "i32x4"),
+			Self::Splat_I64X2 => ("rt_v128.splat", "i64x2"),
+			Self::Splat_F32X4 => ("rt_v128.splat", "f32x4"),
+			Self::Splat_F64X2 => ("rt_v128.splat", "f64x2"),
+			Self::Not_V128 => ("rt_v128.bit", "not"),
 		}
 	}
 }
@@ -159,6 +200,21 @@ impl IntoNameTuple for BinOpType {
 			Self::Min_F64 => ("rt_min", "f64"),
 			Self::Max_F64 => ("rt_max", "f64"),
 			Self::Copysign_F64 => ("rt_copysign", "f64"),
+			Self::And_V128 => ("rt_v128.bit", "and"),
+			Self::AndNot_V128 => ("rt_v128.bit", "andnot"),
+			Self::Or_V128 => ("rt_v128.bit", "or"),
+			Self::Xor_V128 => ("rt_v128.bit", "xor"),
+			Self::Add_I8X16 => ("rt_v128.add", "i8x16"),
+			Self::Sub_I8X16 => ("rt_v128.sub", "i8x16"),
+			Self::Add_I16X8 => ("rt_v128.add", "i16x8"),
+			Self::Sub_I16X8 => ("rt_v128.sub", "i16x8"),
+			Self::Mul_I16X8 => ("rt_v128.mul", "i16x8"),
+			Self::Add_I32X4 => ("rt_v128.add", "i32x4"),
+			Self::Sub_I32X4 => ("rt_v128.sub", "i32x4"),
+			Self::Mul_I32X4 => ("rt_v128.mul", "i32x4"),
+			Self::Add_I64X2 => ("rt_v128.add", "i64x2"),
+			Self::Sub_I64X2 => ("rt_v128.sub", "i64x2"),
+			Self::Mul_I64X2 => ("rt_v128.mul", "i64x2"),
 		}
 	}
 }
@@ -204,17 +260,16 @@ impl IntoNameTuple for CmpOpType {
 
 pub trait TryIntoSymbol {
 	#[must_use]
-	fn try_into_symbol(self) -> Option<&'static str>;
+	fn try_into_symbol(self) -> Option<BinaryOp>;
 }
 
 impl TryIntoSymbol for BinOpType {
-	fn try_into_symbol(self) -> Option<&'static str> {
+	fn try_into_symbol(self) -> Option<BinaryOp> {
 		let result = match self {
-			Self::Add_F32 | Self::Add_F64 => "+",
-			Self::Sub_F32 | Self::Sub_F64 => "-",
-			Self::Mul_F32 | Self::Mul_F64 => "*",
-			Self::Div_F32 | Self::Div_F64 => "/",
-			Self::RemU_I32 => "%",
+			Self::Add_F32 | Self::Add_F64 => BinaryOp::Add,
+			Self::Sub_F32 | Self::Sub_F64 => BinaryOp::Sub,
+			Self::Mul_F32 | Self::Mul_F64 => BinaryOp::Mul,
+			Self::Div_F32 | Self::Div_F64 => BinaryOp::Div,
 			_ => return None,
 		};
 
@@ -223,14 +278,14 @@ impl TryIntoSymbol for BinOpType {
 }
 
 impl TryIntoSymbol for CmpOpType {
-	fn try_into_symbol(self) -> Option<&'static str> {
+	fn try_into_symbol(self) -> Option<BinaryOp> {
 		let result = match self {
-			Self::Eq_I32 | Self::Eq_F32 | Self::Eq_F64 => "==",
-			Self::Ne_I32 | Self::Ne_F32 | Self::Ne_F64 => "~=",
-			Self::LtU_I32 | Self::Lt_F32 | Self::Lt_F64 => "<",
-			Self::GtU_I32 | Self::Gt_F32 | Self::Gt_F64 => ">",
-			Self::LeU_I32 | Self::Le_F32 | Self::Le_F64 => "<=",
-			Self::GeU_I32 | Self::Ge_F32 | Self::Ge_F64 => ">=",
+			Self::Eq_I32 | Self::Eq_F32 | Self::Eq_F64 => BinaryOp::Eq,
+			Self::Ne_I32 | Self::Ne_F32 | Self::Ne_F64 => BinaryOp::Ne,
+			Self::LtU_I32 | Self::Lt_F32 | Self::Lt_F64 => BinaryOp::Lt,
+			Self::GtU_I32 | Self::Gt_F32 | Self::Gt_F64 => BinaryOp::Gt,
+			Self::LeU_I32 | Self::Le_F32 | Self::Le_F64 => BinaryOp::Le,
+			Self::GeU_I32 | Self::Ge_F32 | Self::Ge_F64 => BinaryOp::Ge,
 			_ => return None,
 		};
 
@@ -2,8 +2,8 @@ use std::collections::BTreeSet;
 
 use wasm_ast::{
 	node::{
-		BinOp, CmpOp, FuncData, LoadAt, MemoryCopy, MemoryFill, MemoryGrow, MemorySize, StoreAt,
-		UnOp, Value,
+		BinOp, CmpOp, ExtractLane, FuncData, LoadAt, MemoryCopy, MemoryFill, MemoryGrow,
+		MemoryInit, MemorySize, ReplaceLane, StoreAt, UnOp, Value,
 	},
 	visit::{Driver, Visitor},
 };
@@ -33,13 +33,14 @@ impl Visitor for Visit {
 
 	fn visit_value(&mut self, v: Value) {
 		let name = match v {
-			Value::I64(0) => "ZERO",
-			Value::I64(1) => "ONE",
-			Value::I64(_) => "from_u32",
+			Value::I64(0) => ("i64", "ZERO"),
+			Value::I64(1) => ("i64", "ONE"),
+			Value::I64(_) => ("i64", "from_u32"),
+			Value::V128(_) => ("v128", "from_u32"),
 			_ => return,
 		};
 
-		self.local_set.insert(("i64", name));
+		self.local_set.insert(name);
 	}
 
 	fn visit_un_op(&mut self, v: &UnOp) {
@@ -68,6 +69,18 @@ impl Visitor for Visit {
 		self.local_set.insert(name);
 	}
 
+	fn visit_extract_lane(&mut self, v: &ExtractLane) {
+		let name = v.op_type().into_name();
+
+		self.local_set.insert(("extract_lane", name));
+	}
+
+	fn visit_replace_lane(&mut self, v: &ReplaceLane) {
+		let name = v.op_type().into_name();
+
+		self.local_set.insert(("replace_lane", name));
+	}
+
 	fn visit_memory_size(&mut self, m: &MemorySize) {
 		self.memory_set.insert(m.memory());
 	}
@@ -84,6 +97,10 @@ impl Visitor for Visit {
 	fn visit_memory_fill(&mut self, m: &MemoryFill) {
 		self.memory_set.insert(m.destination().memory());
 	}
+
+	fn visit_memory_init(&mut self, m: &MemoryInit) {
+		self.memory_set.insert(m.destination().memory());
+	}
 }
 
 pub fn visit(ast: &FuncData) -> (BTreeSet<(&'static str, &'static str)>, BTreeSet<usize>) {
@@ -92,10 +109,14 @@ pub fn visit(ast: &FuncData) -> (BTreeSet<(&'static str, &'static str)>, BTreeSe
 		memory_set: BTreeSet::new(),
 	};
 
-	if ast.local_data().iter().any(|&v| v == ValType::I64) {
+	if ast.local_data().contains(&ValType::I64) {
 		visit.local_set.insert(("i64", "ZERO"));
 	}
 
+	if ast.local_data().contains(&ValType::V128) {
+		visit.local_set.insert(("v128", "ZERO"));
+	}
+
 	ast.accept(&mut visit);
 
 	(visit.local_set, visit.memory_set)
@@ -1,3 +1,9 @@
 pub mod br_target;
+pub mod coalesce;
+pub mod fold;
+pub mod fuel;
+pub mod inline;
 pub mod into_string;
 pub mod localize;
+pub mod reachable;
+pub mod split;
@@ -0,0 +1,54 @@
+use std::collections::BTreeSet;
+
+use wasm_ast::{
+	node::{Call, FuncData, Value},
+	visit::{Driver, Visitor},
+};
+
+struct Visit {
+	function_set: BTreeSet<usize>,
+}
+
+impl Visitor for Visit {
+	fn visit_call(&mut self, v: &Call) {
+		self.function_set.insert(v.function());
+	}
+
+	fn visit_value(&mut self, v: Value) {
+		if let Value::RefFunc(function) = v {
+			self.function_set.insert(function);
+		}
+	}
+}
+
+fn visit_function(ast: &FuncData) -> BTreeSet<usize> {
+	let mut visit = Visit {
+		function_set: BTreeSet::new(),
+	};
+
+	ast.accept(&mut visit);
+
+	visit.function_set
+}
+
+// Walks the direct calls and `ref.func` uses outwards from the roots,
+// which must already include everything a table can hold for `call_indirect`
+pub fn visit(
+	func_list: &[(usize, FuncData)],
+	root_list: impl IntoIterator<Item = usize>,
+) -> BTreeSet<usize> {
+	let mut live_set = BTreeSet::new();
+	let mut pending: Vec<_> = root_list.into_iter().collect();
+
+	while let Some(index) = pending.pop() {
+		if !live_set.insert(index) {
+			continue;
+		}
+
+		if let Ok(position) = func_list.binary_search_by_key(&index, |v| v.0) {
+			pending.extend(visit_function(&func_list[position].1));
+		}
+	}
+
+	live_set
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeSet;
+
+use wasm_ast::{
+	node::{
+		Block, Br, BrIf, BrTable, Call, CallIndirect, Expression, FuncData, Local, MemoryGrow,
+		SetLocal, SetTemporary, Statement, TableGrow, Temporary, Terminator,
+	},
+	visit::{Driver, Visitor},
+};
+
+#[derive(Default)]
+struct Size {
+	size: usize,
+}
+
+impl Visitor for Size {
+	fn visit_expression(&mut self, _: &Expression) {
+		self.size += 1;
+	}
+
+	fn visit_statement(&mut self, _: &Statement) {
+		self.size += 1;
+	}
+}
+
+struct Info {
+	size: usize,
+	lowest: usize,
+	has_tail_call: bool,
+}
+
+// A block can only be moved out when every branch in it stays inside and
+// nothing in it returns on behalf of the function. Blocks are visited
+// inside out, so the list ends up with the innermost ones first.
+struct Visit<'a, F> {
+	threshold: usize,
+	can_split: F,
+	split_list: Vec<&'a Block>,
+}
+
+impl<'a, F: Fn(&Block) -> bool> Visit<'a, F> {
+	fn visit_target(info: &mut Info, level: usize, br: Br) {
+		info.lowest = info.lowest.min(level - br.target());
+	}
+
+	fn visit_block(&mut self, block: &'a Block, level: usize) -> Info {
+		let mut info = Info {
+			size: 0,
+			lowest: usize::MAX,
+			has_tail_call: false,
+		};
+
+		let mut candidate_list = Vec::new();
+
+		for stat in block.code() {
+			match stat {
+				Statement::Block(inner) => {
+					let inner_info = self.visit_block(inner, level + 1);
+
+					if inner_info.lowest > level && !inner_info.has_tail_call {
+						candidate_list.push((inner_info.size, inner));
+					}
+
+					info.size += inner_info.size;
+					info.lowest = info.lowest.min(inner_info.lowest);
+					info.has_tail_call |= inner_info.has_tail_call;
+				}
+				Statement::If(stat) => {
+					let list = std::iter::once(stat.on_true()).chain(stat.on_false());
+
+					for inner in list {
+						let inner_info = self.visit_block(inner, level + 1);
+
+						if inner_info.lowest > level && !inner_info.has_tail_call {
+							candidate_list.push((inner_info.size, inner));
+						}
+
+						info.size += inner_info.size;
+						info.lowest = info.lowest.min(inner_info.lowest);
+						info.has_tail_call |= inner_info.has_tail_call;
+					}
+
+					info.size += Self::size_of(stat.condition());
+				}
+				Statement::BrIf(stat) => {
+					Self::visit_target(&mut info, level, stat.target());
+
+					info.size += Self::size_of(stat.condition());
+				}
+				_ => info.size += Self::size_of(stat),
+			}
+		}
+
+		match block.last() {
+			Some(Terminator::Br(br)) => Self::visit_target(&mut info, level, *br),
+			Some(Terminator::BrTable(table)) => {
+				for &br in table.data().iter().chain(std::iter::once(&table.default())) {
+					Self::visit_target(&mut info, level, br);
+				}
+			}
+			Some(Terminator::ReturnCall(_) | Terminator::ReturnCallIndirect(_)) => {
+				info.has_tail_call = true;
+			}
+			Some(Terminator::Unreachable) | None => {}
+		}
+
+		info.size += block.last().map_or(0, Self::size_of);
+
+		candidate_list.sort_by_key(|v| std::cmp::Reverse(v.0));
+
+		for (size, inner) in candidate_list {
+			if info.size <= self.threshold {
+				break;
+			}
+
+			if !(self.can_split)(inner) {
+				continue;
+			}
+
+			info.size = info.size - size + 1;
+
+			self.split_list.push(inner);
+		}
+
+		info
+	}
+
+	fn size_of<T: Driver<Size>>(node: &T) -> usize {
+		let mut size = Size::default();
+
+		node.accept(&mut size);
+
+		size.size.max(1)
+	}
+}
+
+/// Picks the blocks to write as separate functions so that `ast` and
+/// every block taken out of it stay around `threshold` statements and
+/// expressions, where possible. Blocks rejected by `can_split` stay in
+/// place and the next biggest one is tried instead.
+#[must_use]
+pub fn visit(ast: &FuncData, threshold: usize, can_split: impl Fn(&Block) -> bool) -> Vec<&Block> {
+	let mut visit = Visit {
+		threshold,
+		can_split,
+		split_list: Vec::new(),
+	};
+
+	visit.visit_block(ast.code(), 0);
+
+	visit.split_list
+}
+
+/// Locals and temporaries that a block touches, where the written ones
+/// are also listed in `read_*` so they can be passed back unchanged.
+#[derive(Default)]
+pub struct Variables {
+	pub read_local: BTreeSet<usize>,
+	pub read_temporary: BTreeSet<usize>,
+	pub write_local: BTreeSet<usize>,
+	pub write_temporary: BTreeSet<usize>,
+}
+
+impl Variables {
+	fn add_temporary(&mut self, list: impl Iterator<Item = Temporary>) {
+		for var in list.map(Temporary::var) {
+			self.read_temporary.insert(var);
+			self.write_temporary.insert(var);
+		}
+	}
+
+	fn add_br(&mut self, br: Br) {
+		let align = br.align();
+
+		if !align.is_aligned() {
+			let old_range = align.old_range().iter().map(Temporary::var);
+
+			self.read_temporary.extend(old_range);
+			self.add_temporary(align.new_range().iter());
+		}
+	}
+}
+
+impl Visitor for Variables {
+	fn visit_get_temporary(&mut self, temporary: Temporary) {
+		self.read_temporary.insert(temporary.var());
+	}
+
+	fn visit_get_local(&mut self, local: Local) {
+		self.read_local.insert(local.var());
+	}
+
+	fn visit_br(&mut self, br: Br) {
+		self.add_br(br);
+	}
+
+	fn visit_br_if(&mut self, stat: &BrIf) {
+		self.add_br(stat.target());
+	}
+
+	fn visit_br_table(&mut self, table: &BrTable) {
+		self.add_br(table.default());
+
+		for &br in table.data() {
+			self.add_br(br);
+		}
+	}
+
+	fn visit_call(&mut self, call: &Call) {
+		self.add_temporary(call.result_list().iter());
+	}
+
+	fn visit_call_indirect(&mut self, call: &CallIndirect) {
+		self.add_temporary(call.result_list().iter());
+	}
+
+	fn visit_set_temporary(&mut self, stat: &SetTemporary) {
+		self.add_temporary(std::iter::once(stat.var()));
+	}
+
+	fn visit_set_local(&mut self, stat: &SetLocal) {
+		let var = stat.var().var();
+
+		self.read_local.insert(var);
+		self.write_local.insert(var);
+	}
+
+	fn visit_memory_grow(&mut self, stat: &MemoryGrow) {
+		self.add_temporary(std::iter::once(stat.result()));
+	}
+
+	fn visit_table_grow(&mut self, stat: &TableGrow) {
+		self.add_temporary(std::iter::once(stat.result()));
+	}
+}
+
+#[must_use]
+pub fn variable_list(block: &Block) -> Variables {
+	let mut variables = Variables::default();
+
+	block.accept(&mut variables);
+
+	variables
+}
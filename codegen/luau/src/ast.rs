@@ -0,0 +1,483 @@
+//! Luau syntax tree built for the code of every translated function
+//! before it is written out. The tree can be walked and rewritten before
+//! serializing it, see [`crate::from_module_with_rewrite`].
+//!
+//! Names and numbers are kept as the text they are written as, so any
+//! identifier or literal can be swapped out in place.
+
+use std::io::{Result, Write};
+
+fn write_indentation(level: usize, w: &mut dyn Write) -> Result<()> {
+	(0..level).try_for_each(|_| write!(w, "\t"))
+}
+
+fn write_list<T>(
+	list: &[T],
+	mut func: impl FnMut(&T, &mut dyn Write) -> Result<()>,
+	w: &mut dyn Write,
+) -> Result<()> {
+	for (i, v) in list.iter().enumerate() {
+		if i != 0 {
+			write!(w, ", ")?;
+		}
+
+		func(v, w)?;
+	}
+
+	Ok(())
+}
+
+fn write_code(code: &[Statement], level: usize, w: &mut dyn Write) -> Result<()> {
+	code.iter().try_for_each(|v| v.write(level, w))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnaryOp {
+	Not,
+	Negate,
+}
+
+impl UnaryOp {
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Not => "not ",
+			Self::Negate => "-",
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BinaryOp {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Mod,
+	Eq,
+	Ne,
+	Lt,
+	Gt,
+	Le,
+	Ge,
+	And,
+	Or,
+}
+
+impl BinaryOp {
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Add => "+",
+			Self::Sub => "-",
+			Self::Mul => "*",
+			Self::Div => "/",
+			Self::Mod => "%",
+			Self::Eq => "==",
+			Self::Ne => "~=",
+			Self::Lt => "<",
+			Self::Gt => ">",
+			Self::Le => "<=",
+			Self::Ge => ">=",
+			Self::And => "and",
+			Self::Or => "or",
+		}
+	}
+}
+
+/// A local or parameter being declared, optionally with a type.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Variable {
+	pub name: String,
+	pub annotation: Option<String>,
+}
+
+impl Variable {
+	#[must_use]
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			annotation: None,
+		}
+	}
+
+	fn write(&self, w: &mut dyn Write) -> Result<()> {
+		write!(w, "{}", self.name)?;
+
+		match &self.annotation {
+			Some(annotation) => write!(w, ": {annotation}"),
+			None => Ok(()),
+		}
+	}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Call {
+	pub function: Box<Expression>,
+	pub argument_list: Vec<Expression>,
+}
+
+impl Call {
+	#[must_use]
+	pub fn new(function: Expression, argument_list: Vec<Expression>) -> Self {
+		Self {
+			function: function.into(),
+			argument_list,
+		}
+	}
+
+	/// # Errors
+	/// Returns `Err` if writing to `Write` failed.
+	pub fn write(&self, level: usize, w: &mut dyn Write) -> Result<()> {
+		self.function.write(level, w)?;
+		write!(w, "(")?;
+		write_list(&self.argument_list, |v, w| v.write(level, w), w)?;
+		write!(w, ")")
+	}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Function {
+	pub is_native: bool,
+	pub parameter_list: Vec<Variable>,
+	pub code: Vec<Statement>,
+}
+
+impl Function {
+	/// Writes the function with its body one level deeper than `level`
+	/// and the closing `end` at `level`, without a line break after it.
+	///
+	/// # Errors
+	/// Returns `Err` if writing to `Write` failed.
+	pub fn write(&self, level: usize, w: &mut dyn Write) -> Result<()> {
+		if self.is_native {
+			write!(w, "@native ")?;
+		}
+
+		write!(w, "function(")?;
+		write_list(&self.parameter_list, Variable::write, w)?;
+		writeln!(w, ")")?;
+		write_code(&self.code, level + 1, w)?;
+		write_indentation(level, w)?;
+		write!(w, "end")
+	}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TableField {
+	Positional(Expression),
+	Keyed(Expression, Expression),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Expression {
+	Nil,
+	Boolean(bool),
+	Number(String),
+	Name(String),
+	Index(Box<Expression>, Box<Expression>),
+	Field(Box<Expression>, String),
+	Call(Call),
+	Function(Function),
+	Table(Vec<TableField>),
+	Unary(UnaryOp, Box<Expression>),
+	Binary(Box<Expression>, BinaryOp, Box<Expression>),
+	IfElse {
+		condition: Box<Expression>,
+		on_true: Box<Expression>,
+		on_false: Box<Expression>,
+	},
+	Paren(Box<Expression>),
+	/// Written as the expression followed by a `--[[ comment ]]`.
+	Commented(Box<Expression>, String),
+}
+
+impl Expression {
+	#[must_use]
+	pub fn name(name: impl Into<String>) -> Self {
+		Self::Name(name.into())
+	}
+
+	#[must_use]
+	pub fn number(number: impl std::fmt::Display) -> Self {
+		Self::Number(number.to_string())
+	}
+
+	#[must_use]
+	pub fn index(self, key: Self) -> Self {
+		Self::Index(self.into(), key.into())
+	}
+
+	#[must_use]
+	pub fn field(self, name: impl Into<String>) -> Self {
+		Self::Field(self.into(), name.into())
+	}
+
+	#[must_use]
+	pub fn call(self, argument_list: Vec<Self>) -> Self {
+		Self::Call(Call::new(self, argument_list))
+	}
+
+	#[must_use]
+	pub fn unary(op: UnaryOp, rhs: Self) -> Self {
+		Self::Unary(op, rhs.into())
+	}
+
+	#[must_use]
+	pub fn binary(lhs: Self, op: BinaryOp, rhs: Self) -> Self {
+		Self::Binary(lhs.into(), op, rhs.into())
+	}
+
+	#[must_use]
+	pub fn paren(self) -> Self {
+		Self::Paren(self.into())
+	}
+
+	/// # Errors
+	/// Returns `Err` if writing to `Write` failed.
+	pub fn write(&self, level: usize, w: &mut dyn Write) -> Result<()> {
+		match self {
+			Self::Nil => write!(w, "nil"),
+			Self::Boolean(value) => write!(w, "{value}"),
+			Self::Number(text) | Self::Name(text) => write!(w, "{text}"),
+			Self::Index(object, key) => {
+				object.write(level, w)?;
+				write!(w, "[")?;
+				key.write(level, w)?;
+				write!(w, "]")
+			}
+			Self::Field(object, name) => {
+				object.write(level, w)?;
+				write!(w, ".{name}")
+			}
+			Self::Call(call) => call.write(level, w),
+			Self::Function(function) => function.write(level, w),
+			Self::Table(list) if list.is_empty() => write!(w, "{{}}"),
+			Self::Table(list) => {
+				write!(w, "{{ ")?;
+
+				for field in list {
+					match field {
+						TableField::Positional(value) => value.write(level, w)?,
+						TableField::Keyed(key, value) => {
+							write!(w, "[")?;
+							key.write(level, w)?;
+							write!(w, "] = ")?;
+							value.write(level, w)?;
+						}
+					}
+
+					write!(w, ", ")?;
+				}
+
+				write!(w, "}}")
+			}
+			Self::Unary(op, rhs) => {
+				write!(w, "{}", op.as_str())?;
+				rhs.write(level, w)
+			}
+			Self::Binary(lhs, op, rhs) => {
+				lhs.write(level, w)?;
+				write!(w, " {} ", op.as_str())?;
+				rhs.write(level, w)
+			}
+			Self::IfElse {
+				condition,
+				on_true,
+				on_false,
+			} => {
+				write!(w, "if ")?;
+				condition.write(level, w)?;
+				write!(w, " then ")?;
+				on_true.write(level, w)?;
+				write!(w, " else ")?;
+				on_false.write(level, w)
+			}
+			Self::Paren(inner) => {
+				write!(w, "(")?;
+				inner.write(level, w)?;
+				write!(w, ")")
+			}
+			Self::Commented(inner, comment) => {
+				inner.write(level, w)?;
+				write!(w, " --[[ {comment} ]]")
+			}
+		}
+	}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Statement {
+	Local {
+		variable_list: Vec<Variable>,
+		value_list: Vec<Expression>,
+	},
+	Assign {
+		target_list: Vec<Expression>,
+		value_list: Vec<Expression>,
+	},
+	Call(Call),
+	/// Written as `if`, followed by an `elseif` for every branch after
+	/// the first.
+	If {
+		branch_list: Vec<(Expression, Vec<Statement>)>,
+		on_false: Option<Vec<Statement>>,
+	},
+	While {
+		condition: Expression,
+		code: Vec<Statement>,
+	},
+	Do(Vec<Statement>),
+	Break,
+	Continue,
+	Return(Vec<Expression>),
+	/// Written as `do return ... end` so that it can end any block.
+	ReturnCall(Call),
+	Comment(String),
+}
+
+// Luau does not promise to reuse the frame for calls in tail position,
+// so deep tail recursion can still overflow the stack
+const TAIL_CALL_NOTE: &str = "tail call, stack depth may still grow in Luau";
+
+impl Statement {
+	/// Writes the statement on its own lines indented by `level`.
+	///
+	/// # Errors
+	/// Returns `Err` if writing to `Write` failed.
+	pub fn write(&self, level: usize, w: &mut dyn Write) -> Result<()> {
+		write_indentation(level, w)?;
+
+		match self {
+			Self::Local {
+				variable_list,
+				value_list,
+			} => {
+				write!(w, "local ")?;
+				write_list(variable_list, Variable::write, w)?;
+
+				if !value_list.is_empty() {
+					write!(w, " = ")?;
+					write_list(value_list, |v, w| v.write(level, w), w)?;
+				}
+			}
+			Self::Assign {
+				target_list,
+				value_list,
+			} => {
+				write_list(target_list, |v, w| v.write(level, w), w)?;
+				write!(w, " = ")?;
+				write_list(value_list, |v, w| v.write(level, w), w)?;
+			}
+			Self::Call(call) => call.write(level, w)?,
+			Self::If {
+				branch_list,
+				on_false,
+			} => {
+				for (i, (condition, code)) in branch_list.iter().enumerate() {
+					if i != 0 {
+						write_indentation(level, w)?;
+						write!(w, "else")?;
+					}
+
+					write!(w, "if ")?;
+					condition.write(level, w)?;
+					writeln!(w, " then")?;
+					write_code(code, level + 1, w)?;
+				}
+
+				if let Some(code) = on_false {
+					write_indentation(level, w)?;
+					writeln!(w, "else")?;
+					write_code(code, level + 1, w)?;
+				}
+
+				write_indentation(level, w)?;
+				write!(w, "end")?;
+			}
+			Self::While { condition, code } => {
+				write!(w, "while ")?;
+				condition.write(level, w)?;
+				writeln!(w, " do")?;
+				write_code(code, level + 1, w)?;
+				write_indentation(level, w)?;
+				write!(w, "end")?;
+			}
+			Self::Do(code) => {
+				writeln!(w, "do")?;
+				write_code(code, level + 1, w)?;
+				write_indentation(level, w)?;
+				write!(w, "end")?;
+			}
+			Self::Break => write!(w, "break")?,
+			Self::Continue => write!(w, "continue")?,
+			Self::Return(list) => {
+				write!(w, "return")?;
+
+				if !list.is_empty() {
+					write!(w, " ")?;
+					write_list(list, |v, w| v.write(level, w), w)?;
+				}
+			}
+			Self::ReturnCall(call) => {
+				write!(w, "do return ")?;
+				call.write(level, w)?;
+				write!(w, " end -- {TAIL_CALL_NOTE}")?;
+			}
+			Self::Comment(text) => write!(w, "-- {text}")?,
+		}
+
+		writeln!(w)
+	}
+}
+
+/// The `FUNC_LIST` entry written for one function, along with the blocks
+/// that were split out of it into `SPLIT_LIST`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Definition {
+	pub index: usize,
+	pub name: Option<String>,
+	pub split_list: Vec<Function>,
+	pub function: Function,
+}
+
+impl Definition {
+	fn write_start(&self, w: &mut dyn Write) -> Result<()> {
+		write!(w, "FUNC_LIST[{}] = ", self.index)?;
+
+		match &self.name {
+			Some(name) => write!(w, "--[[ {name} ]] "),
+			None => Ok(()),
+		}
+	}
+
+	/// Writes the assignment to `FUNC_LIST`, wrapped in a `do` block that
+	/// declares `SPLIT_LIST` when anything was split out.
+	///
+	/// # Errors
+	/// Returns `Err` if writing to `Write` failed.
+	pub fn write(&self, w: &mut dyn Write) -> Result<()> {
+		if self.split_list.is_empty() {
+			self.write_start(w)?;
+			self.function.write(0, w)?;
+
+			return writeln!(w);
+		}
+
+		writeln!(w, "do")?;
+		writeln!(w, "\tlocal SPLIT_LIST = {{}}")?;
+
+		for (i, function) in self.split_list.iter().enumerate() {
+			write!(w, "\tSPLIT_LIST[{i}] = ")?;
+			function.write(1, w)?;
+			writeln!(w)?;
+		}
+
+		write!(w, "\t")?;
+		self.write_start(w)?;
+		self.function.write(1, w)?;
+		writeln!(w)?;
+
+		writeln!(w, "end")
+	}
+}
@@ -1,219 +1,399 @@
-use std::{
-	io::{Result, Write},
-	num::FpCategory,
-};
+use std::num::FpCategory;
 
 use wasm_ast::node::{
-	BinOp, CmpOp, Expression, GetGlobal, LoadAt, Local, MemorySize, Select, Temporary, UnOp, Value,
+	BinOp, BinOpType, CmpOp, Expression, ExtractLane, GetGlobal, LoadAt, Local, MemorySize,
+	RefIsNull, ReplaceLane, Select, TableGet, TableSize, Temporary, UnOp, UnOpType, Value,
 };
 
-use crate::analyzer::into_string::{IntoName, IntoNameTuple, TryIntoSymbol};
+use crate::{
+	analyzer::{
+		fold,
+		into_string::{IntoName, IntoNameTuple, TryIntoSymbol},
+	},
+	ast::{self, BinaryOp, UnaryOp},
+};
 
-use super::manager::{write_separated, Driver, Manager};
+use super::manager::{Manager, ToExpression};
 
-macro_rules! impl_write_number {
+macro_rules! impl_build_number {
 	($name:tt, $numeric:ty) => {
-		fn $name(number: $numeric, w: &mut dyn Write) -> Result<()> {
+		fn $name(number: $numeric) -> ast::Expression {
+			let nan = || {
+				let zero = || ast::Expression::number("0.0");
+
+				ast::Expression::binary(zero(), BinaryOp::Div, zero()).paren()
+			};
+			let huge = || ast::Expression::name("math").field("huge");
+
 			match (number.classify(), number.is_sign_negative()) {
-				(FpCategory::Nan, true) => write!(w, "(0.0 / 0.0)"),
-				(FpCategory::Nan, false) => write!(w, "-(0.0 / 0.0)"),
-				(FpCategory::Infinite, true) => write!(w, "-math.huge"),
-				(FpCategory::Infinite, false) => write!(w, "math.huge"),
-				_ => write!(w, "{number:e}"),
+				(FpCategory::Nan, true) => nan(),
+				(FpCategory::Nan, false) => ast::Expression::unary(UnaryOp::Negate, nan()),
+				(FpCategory::Infinite, true) => ast::Expression::unary(UnaryOp::Negate, huge()),
+				(FpCategory::Infinite, false) => huge(),
+				_ => ast::Expression::number(format!("{number:e}")),
 			}
 		}
 	};
 }
 
-impl Driver for Select {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		write!(w, "(if ")?;
-		Condition(self.condition()).write(mng, w)?;
-		write!(w, " then ")?;
-		self.on_true().write(mng, w)?;
-		write!(w, " else ")?;
-		self.on_false().write(mng, w)?;
-		write!(w, ")")
+fn if_else(
+	condition: ast::Expression,
+	on_true: ast::Expression,
+	on_false: ast::Expression,
+) -> ast::Expression {
+	let inner = ast::Expression::IfElse {
+		condition: condition.into(),
+		on_true: on_true.into(),
+		on_false: on_false.into(),
+	};
+
+	inner.paren()
+}
+
+impl ToExpression for Select {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		if_else(
+			Condition(self.condition()).to_expression(mng),
+			self.on_true().to_expression(mng),
+			self.on_false().to_expression(mng),
+		)
 	}
 }
 
-impl Driver for Temporary {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+impl ToExpression for Temporary {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
 		let var = self.var();
 
 		if let Some(var) = var.checked_sub(mng.num_temp()) {
-			write!(w, "reg_spill[{}]", var + 1)
+			ast::Expression::name("reg_spill").index(ast::Expression::number(var + 1))
 		} else {
-			write!(w, "reg_{var}")
+			ast::Expression::name(mng.temporary_name(var))
 		}
 	}
 }
 
-impl Driver for Local {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		let var = self.var();
+impl ToExpression for Local {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		let var = mng.local_slot(self.var());
 
 		if let Some(var) = var.checked_sub(mng.num_local()) {
-			write!(w, "loc_spill[{}]", var + 1)
+			ast::Expression::name("loc_spill").index(ast::Expression::number(var + 1))
 		} else {
-			write!(w, "loc_{var}")
+			ast::Expression::name(mng.local_name(var))
 		}
 	}
 }
 
-impl Driver for GetGlobal {
-	fn write(&self, _mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		write!(w, "GLOBAL_LIST[{}].value", self.var())
+impl ToExpression for GetGlobal {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		mng.global_reference(self.var())
 	}
 }
 
-impl Driver for LoadAt {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		let name = self.load_type().into_name();
-		let memory = self.memory();
+pub fn memory_at(memory: usize) -> ast::Expression {
+	ast::Expression::name(format!("memory_at_{memory}"))
+}
 
-		write!(w, "rt_load_{name}(memory_at_{memory}, ")?;
-		self.pointer().write(mng, w)?;
+// Offsets are added to the pointer as written, so these need no parentheses
+pub fn pointer_of(pointer: &Expression, offset: u32, mng: &Manager) -> ast::Expression {
+	let pointer = pointer.to_expression(mng);
 
-		if self.offset() != 0 {
-			write!(w, " + {}", self.offset())?;
-		}
+	if offset == 0 {
+		pointer
+	} else {
+		ast::Expression::binary(pointer, BinaryOp::Add, ast::Expression::number(offset))
+	}
+}
 
-		write!(w, ")")
+impl ToExpression for LoadAt {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		let name = self.load_type().into_name();
+
+		ast::Expression::name(format!("rt_load_{name}")).call(vec![
+			memory_at(self.memory()),
+			pointer_of(self.pointer(), self.offset(), mng),
+		])
 	}
 }
 
-impl Driver for MemorySize {
-	fn write(&self, _mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		write!(w, "rt_allocator_size(memory_at_{})", self.memory())
+impl ToExpression for MemorySize {
+	fn to_expression(&self, _mng: &Manager) -> ast::Expression {
+		ast::Expression::name("rt_allocator_size").call(vec![memory_at(self.memory())])
 	}
 }
 
-pub fn write_i32(number: i32, w: &mut dyn Write) -> Result<()> {
+fn build_i32(number: i32) -> ast::Expression {
 	let list = number.to_ne_bytes();
 
-	write!(w, "{}", u32::from_ne_bytes(list))
+	ast::Expression::number(u32::from_ne_bytes(list))
 }
 
-fn write_i64(number: i64, w: &mut dyn Write) -> Result<()> {
+fn build_i64(number: i64) -> ast::Expression {
 	match number {
-		0 => write!(w, "rt_i64_ZERO"),
-		1 => write!(w, "rt_i64_ONE"),
+		0 => ast::Expression::name("rt_i64_ZERO"),
+		1 => ast::Expression::name("rt_i64_ONE"),
 		_ => {
 			let list = number.to_ne_bytes();
 			let a = u32::from_ne_bytes(list[0..4].try_into().unwrap());
 			let b = u32::from_ne_bytes(list[4..8].try_into().unwrap());
 
-			write!(w, "rt_i64_from_u32({a}, {b})")
+			ast::Expression::name("rt_i64_from_u32")
+				.call(vec![ast::Expression::number(a), ast::Expression::number(b)])
 		}
 	}
 }
 
-impl_write_number!(write_f32, f32);
-impl_write_number!(write_f64, f64);
+fn build_v128(number: u128) -> ast::Expression {
+	let list = (0..128)
+		.step_by(32)
+		.map(|i| ast::Expression::number((number >> i) as u32))
+		.collect();
+
+	ast::Expression::name("rt_v128")
+		.field("from_u32")
+		.call(list)
+}
+
+impl_build_number!(build_f32, f32);
+impl_build_number!(build_f64, f64);
 
-impl Driver for Value {
-	fn write(&self, _mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+impl ToExpression for Value {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
 		match self {
-			Self::I32(i) => write_i32(*i, w),
-			Self::I64(i) => write_i64(*i, w),
-			Self::F32(f) => write_f32(*f, w),
-			Self::F64(f) => write_f64(*f, w),
+			Self::I32(i) => build_i32(*i),
+			Self::I64(i) => build_i64(*i),
+			Self::F32(f) => build_f32(*f),
+			Self::F64(f) => build_f64(*f),
+			Self::V128(v) => build_v128(*v),
+			Self::RefNull => ast::Expression::Nil,
+			Self::RefFunc(f) => mng.function_reference(*f),
 		}
 	}
 }
 
-impl Driver for UnOp {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+// Sign and bit manipulating operations are left out, as the spec
+// has them keep the payload of their operand
+const fn un_op_makes_nan(op: UnOpType) -> bool {
+	matches!(
+		op,
+		UnOpType::Ceil_F32
+			| UnOpType::Floor_F32
+			| UnOpType::Truncate_F32
+			| UnOpType::Nearest_F32
+			| UnOpType::Sqrt_F32
+			| UnOpType::Ceil_F64
+			| UnOpType::Floor_F64
+			| UnOpType::Truncate_F64
+			| UnOpType::Nearest_F64
+			| UnOpType::Sqrt_F64
+			| UnOpType::Demote_F32_F64
+			| UnOpType::Promote_F64_F32
+	)
+}
+
+const fn bin_op_makes_nan(op: BinOpType) -> bool {
+	matches!(
+		op,
+		BinOpType::Add_F32
+			| BinOpType::Sub_F32
+			| BinOpType::Mul_F32
+			| BinOpType::Div_F32
+			| BinOpType::Min_F32
+			| BinOpType::Max_F32
+			| BinOpType::Add_F64
+			| BinOpType::Sub_F64
+			| BinOpType::Mul_F64
+			| BinOpType::Div_F64
+			| BinOpType::Min_F64
+			| BinOpType::Max_F64
+	)
+}
+
+fn build_canonical(makes_nan: bool, mng: &Manager, inner: ast::Expression) -> ast::Expression {
+	if !makes_nan || !mng.config().canonicalize_nan {
+		return inner;
+	}
+
+	ast::Expression::name("rt_canonicalize").call(vec![inner])
+}
+
+impl ToExpression for UnOp {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		if let Some(value) = mng
+			.config()
+			.fold_constants
+			.then(|| fold::un_op(self))
+			.flatten()
+		{
+			return value.to_expression(mng);
+		}
+
 		let (a, b) = self.op_type().into_name_tuple();
+		let inner =
+			ast::Expression::name(format!("{a}_{b}")).call(vec![self.rhs().to_expression(mng)]);
 
-		write!(w, "{a}_{b}(")?;
-		self.rhs().write(mng, w)?;
-		write!(w, ")")
+		build_canonical(un_op_makes_nan(self.op_type()), mng, inner)
 	}
 }
 
-impl Driver for BinOp {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		if let Some(symbol) = self.op_type().try_into_symbol() {
-			write!(w, "(")?;
-			self.lhs().write(mng, w)?;
-			write!(w, " {symbol} ")?;
-		} else {
-			let (head, tail) = self.op_type().into_name_tuple();
+fn build_bin_op(op: &BinOp, mng: &Manager) -> ast::Expression {
+	let lhs = op.lhs().to_expression(mng);
+	let rhs = op.rhs().to_expression(mng);
+
+	if let Some(symbol) = op.op_type().try_into_symbol() {
+		ast::Expression::binary(lhs, symbol, rhs).paren()
+	} else {
+		let (head, tail) = op.op_type().into_name_tuple();
+
+		ast::Expression::name(format!("{head}_{tail}")).call(vec![lhs, rhs])
+	}
+}
 
-			write!(w, "{head}_{tail}(")?;
-			self.lhs().write(mng, w)?;
-			write!(w, ", ")?;
+impl ToExpression for BinOp {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		if let Some(value) = mng
+			.config()
+			.fold_constants
+			.then(|| fold::bin_op(self))
+			.flatten()
+		{
+			return value.to_expression(mng);
 		}
 
-		self.rhs().write(mng, w)?;
-		write!(w, ")")
+		build_canonical(
+			bin_op_makes_nan(self.op_type()),
+			mng,
+			build_bin_op(self, mng),
+		)
+	}
+}
+
+impl ToExpression for ExtractLane {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		let name = self.op_type().into_name();
+
+		ast::Expression::name("rt_v128")
+			.field(format!("extract_lane_{name}"))
+			.call(vec![
+				self.rhs().to_expression(mng),
+				ast::Expression::number(self.lane()),
+			])
+	}
+}
+
+impl ToExpression for ReplaceLane {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		let name = self.op_type().into_name();
+
+		ast::Expression::name("rt_v128")
+			.field(format!("replace_lane_{name}"))
+			.call(vec![
+				self.lhs().to_expression(mng),
+				ast::Expression::number(self.lane()),
+				self.rhs().to_expression(mng),
+			])
 	}
 }
 
 struct CmpOpBoolean<'a>(&'a CmpOp);
 
-impl Driver for CmpOpBoolean<'_> {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+impl ToExpression for CmpOpBoolean<'_> {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
 		let cmp = self.0;
+		let lhs = cmp.lhs().to_expression(mng);
+		let rhs = cmp.rhs().to_expression(mng);
 
 		if let Some(symbol) = cmp.op_type().try_into_symbol() {
-			cmp.lhs().write(mng, w)?;
-			write!(w, " {symbol} ")?;
-			cmp.rhs().write(mng, w)
+			ast::Expression::binary(lhs, symbol, rhs)
 		} else {
 			let (head, tail) = cmp.op_type().into_name_tuple();
 
-			write!(w, "{head}_{tail}(")?;
-			cmp.lhs().write(mng, w)?;
-			write!(w, ", ")?;
-			cmp.rhs().write(mng, w)?;
-			write!(w, ")")
+			ast::Expression::name(format!("{head}_{tail}")).call(vec![lhs, rhs])
 		}
 	}
 }
 
-impl Driver for CmpOp {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		write!(w, "(if ")?;
-		CmpOpBoolean(self).write(mng, w)?;
-		write!(w, " then 1 else 0)")
+fn build_boolean(condition: ast::Expression) -> ast::Expression {
+	if_else(
+		condition,
+		ast::Expression::number(1),
+		ast::Expression::number(0),
+	)
+}
+
+fn build_is_null(value: &Expression, mng: &Manager) -> ast::Expression {
+	ast::Expression::binary(value.to_expression(mng), BinaryOp::Eq, ast::Expression::Nil)
+}
+
+impl ToExpression for CmpOp {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		build_boolean(CmpOpBoolean(self).to_expression(mng))
+	}
+}
+
+impl ToExpression for RefIsNull {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		build_boolean(build_is_null(self.value(), mng))
+	}
+}
+
+pub fn table_at(table: usize) -> ast::Expression {
+	ast::Expression::name("TABLE_LIST").index(ast::Expression::number(table))
+}
+
+impl ToExpression for TableGet {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		ast::Expression::name("rt_table").field("get").call(vec![
+			table_at(self.table()),
+			self.index().to_expression(mng),
+		])
+	}
+}
+
+impl ToExpression for TableSize {
+	fn to_expression(&self, _mng: &Manager) -> ast::Expression {
+		table_at(self.table()).field("min")
 	}
 }
 
 pub struct Condition<'a>(pub &'a Expression);
 
-impl Driver for Condition<'_> {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		if let Expression::CmpOp(node) = self.0 {
-			CmpOpBoolean(node).write(mng, w)
-		} else {
-			self.0.write(mng, w)?;
-			write!(w, " ~= 0")
+impl ToExpression for Condition<'_> {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
+		match self.0 {
+			Expression::CmpOp(node) => CmpOpBoolean(node).to_expression(mng),
+			Expression::RefIsNull(node) => build_is_null(node.value(), mng),
+			_ => ast::Expression::binary(
+				self.0.to_expression(mng),
+				BinaryOp::Ne,
+				ast::Expression::number(0),
+			),
 		}
 	}
 }
 
-impl Driver for Expression {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+impl ToExpression for Expression {
+	fn to_expression(&self, mng: &Manager) -> ast::Expression {
 		match self {
-			Self::Select(e) => e.write(mng, w),
-			Self::GetTemporary(e) => e.write(mng, w),
-			Self::GetLocal(e) => e.write(mng, w),
-			Self::GetGlobal(e) => e.write(mng, w),
-			Self::LoadAt(e) => e.write(mng, w),
-			Self::MemorySize(e) => e.write(mng, w),
-			Self::Value(e) => e.write(mng, w),
-			Self::UnOp(e) => e.write(mng, w),
-			Self::BinOp(e) => e.write(mng, w),
-			Self::CmpOp(e) => e.write(mng, w),
+			Self::Select(e) => e.to_expression(mng),
+			Self::GetTemporary(e) => e.to_expression(mng),
+			Self::GetLocal(e) => e.to_expression(mng),
+			Self::GetGlobal(e) => e.to_expression(mng),
+			Self::LoadAt(e) => e.to_expression(mng),
+			Self::MemorySize(e) => e.to_expression(mng),
+			Self::Value(e) => e.to_expression(mng),
+			Self::RefIsNull(e) => e.to_expression(mng),
+			Self::TableGet(e) => e.to_expression(mng),
+			Self::TableSize(e) => e.to_expression(mng),
+			Self::UnOp(e) => e.to_expression(mng),
+			Self::BinOp(e) => e.to_expression(mng),
+			Self::CmpOp(e) => e.to_expression(mng),
+			Self::ExtractLane(e) => e.to_expression(mng),
+			Self::ReplaceLane(e) => e.to_expression(mng),
 		}
 	}
 }
 
-impl Driver for &[Expression] {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		write_separated(self.iter(), |e, w| e.write(mng, w), w)
-	}
+pub fn build_list<T: ToExpression>(list: &[T], mng: &Manager) -> Vec<ast::Expression> {
+	list.iter().map(|v| v.to_expression(mng)).collect()
 }
@@ -1,36 +1,21 @@
 use std::{
-	collections::HashMap,
+	collections::{BTreeMap, HashMap, HashSet},
 	io::{Result, Write},
 };
 
-use wasm_ast::node::{BrTable, FuncData, LabelType};
-
-use crate::analyzer::{br_target, localize};
-
-#[macro_export]
-macro_rules! indentation {
-	($mng:tt, $w:tt) => {{
-		let mut iter = 0..$mng.indentation();
-
-		iter.try_for_each(|_| write!($w, "\t"))
-	}};
-}
+use wasm_ast::{
+	module::Module,
+	node::{Block, BrTable, FuncData, LabelType},
+};
+use wasmparser::ValType;
 
-#[macro_export]
-macro_rules! indented {
-	($mng:tt, $w:tt, $($args:tt)*) => {{
-		indentation!($mng, $w)?;
-		write!($w, $($args)*)
-	}};
-}
+use crate::{
+	analyzer::{br_target, coalesce, localize},
+	ast::{Expression, Statement},
+	Config,
+};
 
-#[macro_export]
-macro_rules! line {
-	($mng:tt, $w:tt, $($args:tt)*) => {{
-		indentation!($mng, $w)?;
-		writeln!($w, $($args)*)
-	}};
-}
+const MAX_LOCAL_COUNT: usize = 180;
 
 fn get_pinned_registers(
 	upvalues: usize,
@@ -38,8 +23,6 @@ fn get_pinned_registers(
 	locals: usize,
 	temporaries: usize,
 ) -> (usize, usize) {
-	const MAX_LOCAL_COUNT: usize = 180;
-
 	let available = MAX_LOCAL_COUNT
 		.saturating_sub(upvalues)
 		.saturating_sub(params);
@@ -50,44 +33,236 @@ fn get_pinned_registers(
 	(params + locals, temporaries)
 }
 
-pub struct Manager {
+// Debug names can contain anything, so everything that is not valid in
+// an identifier is replaced
+fn sanitize_name(name: &str) -> String {
+	name.chars()
+		.map(|v| if v.is_ascii_alphanumeric() { v } else { '_' })
+		.collect()
+}
+
+pub struct Manager<'a> {
+	config: &'a Config,
+	module: Option<&'a Module<'a>>,
+	function_index: usize,
+	name_prefix: &'static str,
+	local_names: Vec<String>,
+	local_map: Vec<usize>,
+	local_type: Vec<ValType>,
 	table_map: HashMap<usize, usize>,
+	split_map: HashMap<usize, usize>,
+	inline_map: Option<&'a BTreeMap<usize, &'a FuncData>>,
+	import_name_list: &'a [String],
+	num_spare: usize,
 	has_branch: bool,
+	has_loop: bool,
 	num_local: usize,
 	num_temp: usize,
 	label_list: Vec<Option<LabelType>>,
-	indentation: usize,
 }
 
-impl Manager {
-	pub fn empty() -> Self {
+impl<'a> Manager<'a> {
+	pub fn empty(config: &'a Config) -> Self {
 		Self {
+			config,
+			module: None,
+			function_index: 0,
+			name_prefix: "",
+			local_names: Vec::new(),
+			local_map: Vec::new(),
+			local_type: Vec::new(),
 			table_map: HashMap::new(),
+			split_map: HashMap::new(),
+			inline_map: None,
+			import_name_list: &[],
+			num_spare: 0,
 			has_branch: false,
+			has_loop: false,
 			num_local: 0,
 			num_temp: usize::MAX,
 			label_list: Vec::new(),
-			indentation: 0,
 		}
 	}
 
-	pub fn function(ast: &FuncData) -> Self {
+	pub fn function(ast: &FuncData, function_index: usize, config: &'a Config) -> Self {
 		let (upvalues, memories) = localize::visit(ast);
-		let (table_map, has_branch) = br_target::visit(ast);
+		let (table_map, has_branch, has_loop) = br_target::visit(ast);
+		let (local_map, local_type) = if config.coalesce_locals {
+			coalesce::visit(ast)
+		} else {
+			let len = ast.num_param() + ast.local_data().len();
+
+			((0..len).collect(), ast.local_data().to_vec())
+		};
+
 		let (num_local, num_temp) = get_pinned_registers(
 			upvalues.len() + memories.len(),
 			ast.num_param(),
-			ast.local_data().len(),
+			local_type.len(),
 			ast.num_stack(),
 		);
 
 		Self {
+			config,
+			module: None,
+			function_index,
+			name_prefix: "",
+			local_names: Vec::new(),
+			local_map,
+			local_type,
 			table_map,
+			split_map: HashMap::new(),
+			inline_map: None,
+			import_name_list: &[],
+			num_spare: MAX_LOCAL_COUNT
+				.saturating_sub(upvalues.len() + memories.len())
+				.saturating_sub(num_local + num_temp),
 			has_branch,
+			has_loop,
 			num_local,
 			num_temp,
 			label_list: Vec::new(),
-			indentation: 0,
+		}
+	}
+
+	pub const fn config(&self) -> &Config {
+		self.config
+	}
+
+	pub const fn function_index(&self) -> usize {
+		self.function_index
+	}
+
+	// Pinned locals get an identifier derived from their debug name, with
+	// a numeric suffix whenever two of them would end up the same. Slots
+	// shared by several locals stay numbered.
+	pub fn set_debug_names(&mut self, module: &'a Module<'a>) {
+		let index = self.function_index.try_into().unwrap();
+		let names = module.local_name_section().get(&index);
+		let mut owner_list = vec![None; self.num_local];
+
+		for (var, &slot) in self.local_map.iter().enumerate() {
+			if let Some(owner) = owner_list.get_mut(slot) {
+				*owner = if owner.is_none() {
+					Some(Some(var))
+				} else {
+					Some(None)
+				};
+			}
+		}
+
+		let name_of = |i: usize| {
+			let var: usize = owner_list[i]??;
+
+			names?.get(&var.try_into().unwrap()).copied()
+		};
+
+		let mut used: HashSet<_> = (0..self.num_local)
+			.filter(|&i| name_of(i).is_none())
+			.map(|i| format!("loc_{i}"))
+			.collect();
+
+		used.insert("loc_spill".to_string());
+
+		self.module = Some(module);
+		self.local_names = (0..self.num_local)
+			.map(|i| {
+				let Some(name) = name_of(i) else {
+					return format!("loc_{i}");
+				};
+
+				let base = format!("loc_{}", sanitize_name(name));
+				let mut result = base.clone();
+				let mut suffix = 1;
+
+				while !used.insert(result.clone()) {
+					result = format!("{base}_{suffix}");
+					suffix += 1;
+				}
+
+				result
+			})
+			.collect();
+	}
+
+	pub fn local_name(&self, var: usize) -> String {
+		let prefix = self.name_prefix;
+
+		match self.local_names.get(var) {
+			Some(name) => format!("{prefix}{name}"),
+			None => format!("{prefix}loc_{var}"),
+		}
+	}
+
+	pub fn temporary_name(&self, var: usize) -> String {
+		format!("{}reg_{var}", self.name_prefix)
+	}
+
+	pub fn set_inline_map(&mut self, map: &'a BTreeMap<usize, &'a FuncData>) {
+		self.inline_map = Some(map);
+	}
+
+	// A body is only inlined when its locals, temporaries and upvalues all
+	// fit next to the ones the caller already pins
+	pub fn inline_target(&self, function: usize) -> Option<&'a FuncData> {
+		let ast = *self.inline_map?.get(&function)?;
+		let (upvalues, memories) = localize::visit(ast);
+		let used = upvalues.len()
+			+ memories.len()
+			+ ast.num_param()
+			+ ast.local_data().len()
+			+ ast.num_stack();
+
+		(used <= self.num_spare).then_some(ast)
+	}
+
+	// Inlined bodies get names of their own, so they cannot clash with
+	// the locals and temporaries of the caller
+	pub fn inline_manager(&self, ast: &FuncData, function: usize) -> Self {
+		let mut mng = Self::function(ast, function, self.config);
+
+		if let Some(module) = self.module {
+			mng.set_debug_names(module);
+		}
+
+		mng.name_prefix = "inline_";
+		mng
+	}
+
+	pub fn set_import_name_list(&mut self, list: &'a [String]) {
+		self.import_name_list = list;
+	}
+
+	// Functions and globals live in shared lists, so their names are
+	// only written as comments next to the reference. Imported functions
+	// given a local of their own are the exception.
+	pub fn function_reference(&self, index: usize) -> Expression {
+		if let Some(name) = self.import_name_list.get(index) {
+			return Expression::name(name);
+		}
+
+		let reference = Expression::name("FUNC_LIST").index(Expression::number(index));
+		let name = self
+			.module
+			.and_then(|v| v.name_section().get(&index.try_into().unwrap()));
+
+		match name {
+			Some(name) => Expression::Commented(reference.into(), sanitize_name(name)),
+			None => reference,
+		}
+	}
+
+	pub fn global_reference(&self, index: usize) -> Expression {
+		let reference = Expression::name("GLOBAL_LIST")
+			.index(Expression::number(index))
+			.field("value");
+		let name = self
+			.module
+			.and_then(|v| v.global_name_section().get(&index.try_into().unwrap()));
+
+		match name {
+			Some(name) => Expression::Commented(reference.into(), sanitize_name(name)),
+			None => reference,
 		}
 	}
 
@@ -97,6 +272,21 @@ impl Manager {
 		self.table_map[&id]
 	}
 
+	// Blocks in the list are written as calls to `SPLIT_LIST` by position
+	pub fn set_split_list(&mut self, list: &[&Block]) {
+		self.split_map = list
+			.iter()
+			.enumerate()
+			.map(|(i, &v)| (std::ptr::from_ref(v) as usize, i))
+			.collect();
+	}
+
+	pub fn split_index(&self, block: &Block) -> Option<usize> {
+		let id = std::ptr::from_ref(block) as usize;
+
+		self.split_map.get(&id).copied()
+	}
+
 	pub fn has_table(&self) -> bool {
 		!self.table_map.is_empty()
 	}
@@ -105,6 +295,21 @@ impl Manager {
 		self.has_branch
 	}
 
+	// Functions that loop are the ones worth compiling natively
+	pub const fn is_native(&self) -> bool {
+		self.config.native || (self.config.native_loops && self.has_loop)
+	}
+
+	// Slot that holds the local numbered `var` in the function.
+	pub fn local_slot(&self, var: usize) -> usize {
+		self.local_map[var]
+	}
+
+	// Types of the slots declared after the parameters.
+	pub fn local_type(&self) -> &[ValType] {
+		&self.local_type
+	}
+
 	pub const fn num_local(&self) -> usize {
 		self.num_local
 	}
@@ -117,6 +322,16 @@ impl Manager {
 		&self.label_list
 	}
 
+	// Blocks that are never branched to are not written as loops, so
+	// `break` and `continue` apply to the innermost labeled one instead
+	pub fn loop_level(&self) -> Option<usize> {
+		if self.config.loop_every_block {
+			self.label_list.len().checked_sub(1)
+		} else {
+			self.label_list.iter().rposition(Option::is_some)
+		}
+	}
+
 	pub fn push_label(&mut self, label: Option<LabelType>) {
 		self.label_list.push(label);
 	}
@@ -124,22 +339,14 @@ impl Manager {
 	pub fn pop_label(&mut self) {
 		self.label_list.pop().unwrap();
 	}
-
-	pub const fn indentation(&self) -> usize {
-		self.indentation
-	}
-
-	pub fn indent(&mut self) {
-		self.indentation += 1;
-	}
-
-	pub fn dedent(&mut self) {
-		self.indentation -= 1;
-	}
 }
 
 pub trait Driver {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()>;
+	fn build(&self, mng: &mut Manager, code: &mut Vec<Statement>);
+}
+
+pub trait ToExpression {
+	fn to_expression(&self, mng: &Manager) -> Expression;
 }
 
 pub fn write_separated<I, T, M>(mut iter: I, mut func: M, w: &mut dyn Write) -> Result<()>
@@ -0,0 +1,364 @@
+use std::{
+	collections::{HashMap, HashSet},
+	io::{Result, Write},
+};
+
+use crate::ast::{Call, Definition, Expression, Function, Statement, TableField, Variable};
+
+// Drops the indentation of every line along with blank lines and lines
+// holding only a comment. Nothing the backend writes spans lines inside
+// a string or a long comment, so this can go line by line.
+pub struct Minify<'a> {
+	inner: &'a mut dyn Write,
+	line: Vec<u8>,
+}
+
+impl<'a> Minify<'a> {
+	pub fn new(inner: &'a mut dyn Write) -> Self {
+		Self {
+			inner,
+			line: Vec::new(),
+		}
+	}
+
+	fn write_line(&mut self) -> Result<()> {
+		let start = self
+			.line
+			.iter()
+			.position(|&v| v != b'\t' && v != b' ')
+			.unwrap_or(self.line.len());
+
+		let text = &self.line[start..];
+		let is_comment = text.starts_with(b"--") && !text.starts_with(b"--[");
+
+		if !text.is_empty() && !is_comment {
+			self.inner.write_all(text)?;
+			self.inner.write_all(b"\n")?;
+		}
+
+		self.line.clear();
+
+		Ok(())
+	}
+
+	// Output normally ends with a line break, so this rarely writes anything
+	pub fn finish(mut self) -> Result<()> {
+		self.write_line()
+	}
+}
+
+impl Write for Minify<'_> {
+	fn write(&mut self, buf: &[u8]) -> Result<usize> {
+		for line in buf.split_inclusive(|&v| v == b'\n') {
+			match line.strip_suffix(b"\n") {
+				Some(line) => {
+					self.line.extend_from_slice(line);
+					self.write_line()?;
+				}
+				None => self.line.extend_from_slice(line),
+			}
+		}
+
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> Result<()> {
+		self.inner.flush()
+	}
+}
+
+const KEYWORD_LIST: [&str; 22] = [
+	"and", "break", "continue", "do", "else", "elseif", "end", "false", "for", "function", "if",
+	"in", "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+const FIRST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const REST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_";
+
+fn short_name(mut index: usize) -> String {
+	let mut name = vec![FIRST[index % FIRST.len()]];
+
+	index /= FIRST.len();
+
+	while index != 0 {
+		index -= 1;
+		name.push(REST[index % REST.len()]);
+		index /= REST.len();
+	}
+
+	String::from_utf8(name).unwrap()
+}
+
+#[derive(Default)]
+struct Names {
+	declared: Vec<String>,
+	used: HashSet<String>,
+}
+
+impl Names {
+	fn declare(&mut self, variable: &Variable) {
+		if !self.declared.contains(&variable.name) {
+			self.declared.push(variable.name.clone());
+		}
+	}
+
+	fn visit_call(&mut self, call: &Call) {
+		self.visit_expression(&call.function);
+		call.argument_list
+			.iter()
+			.for_each(|v| self.visit_expression(v));
+	}
+
+	fn visit_function(&mut self, function: &Function) {
+		function.parameter_list.iter().for_each(|v| self.declare(v));
+
+		self.visit_code(&function.code);
+	}
+
+	fn visit_expression(&mut self, expression: &Expression) {
+		match expression {
+			Expression::Nil | Expression::Boolean(_) | Expression::Number(_) => {}
+			Expression::Name(name) => {
+				self.used.insert(name.clone());
+			}
+			Expression::Index(lhs, rhs) | Expression::Binary(lhs, _, rhs) => {
+				self.visit_expression(lhs);
+				self.visit_expression(rhs);
+			}
+			Expression::Field(value, _)
+			| Expression::Unary(_, value)
+			| Expression::Paren(value)
+			| Expression::Commented(value, _) => self.visit_expression(value),
+			Expression::Call(call) => self.visit_call(call),
+			Expression::Function(function) => self.visit_function(function),
+			Expression::Table(field_list) => {
+				for field in field_list {
+					match field {
+						TableField::Positional(value) => self.visit_expression(value),
+						TableField::Keyed(key, value) => {
+							self.visit_expression(key);
+							self.visit_expression(value);
+						}
+					}
+				}
+			}
+			Expression::IfElse {
+				condition,
+				on_true,
+				on_false,
+			} => {
+				self.visit_expression(condition);
+				self.visit_expression(on_true);
+				self.visit_expression(on_false);
+			}
+		}
+	}
+
+	fn visit_code(&mut self, code: &[Statement]) {
+		for statement in code {
+			match statement {
+				Statement::Local {
+					variable_list,
+					value_list,
+				} => {
+					value_list.iter().for_each(|v| self.visit_expression(v));
+					variable_list.iter().for_each(|v| self.declare(v));
+				}
+				Statement::Assign {
+					target_list,
+					value_list,
+				} => {
+					target_list.iter().for_each(|v| self.visit_expression(v));
+					value_list.iter().for_each(|v| self.visit_expression(v));
+				}
+				Statement::Call(call) | Statement::ReturnCall(call) => self.visit_call(call),
+				Statement::If {
+					branch_list,
+					on_false,
+				} => {
+					for (condition, code) in branch_list {
+						self.visit_expression(condition);
+						self.visit_code(code);
+					}
+
+					if let Some(code) = on_false {
+						self.visit_code(code);
+					}
+				}
+				Statement::While { condition, code } => {
+					self.visit_expression(condition);
+					self.visit_code(code);
+				}
+				Statement::Do(code) => self.visit_code(code),
+				Statement::Return(list) => list.iter().for_each(|v| self.visit_expression(v)),
+				Statement::Break | Statement::Continue | Statement::Comment(_) => {}
+			}
+		}
+	}
+
+	// Short names skip anything the function refers to from outside,
+	// which keeps every upvalue and runtime helper visible
+	fn into_map(self) -> HashMap<String, String> {
+		let mut index = 0;
+		let mut next_name = || loop {
+			let name = short_name(index);
+
+			index += 1;
+
+			let is_taken = KEYWORD_LIST.contains(&name.as_str())
+				|| (self.used.contains(&name) && !self.declared.contains(&name));
+
+			if !is_taken {
+				break name;
+			}
+		};
+
+		let mut map = HashMap::new();
+
+		for name in &self.declared {
+			map.insert(name.clone(), next_name());
+		}
+
+		map
+	}
+}
+
+fn rename_call(call: &mut Call, map: &HashMap<String, String>) {
+	rename_expression(&mut call.function, map);
+	call.argument_list
+		.iter_mut()
+		.for_each(|v| rename_expression(v, map));
+}
+
+fn rename_function(function: &mut Function, map: &HashMap<String, String>) {
+	for variable in &mut function.parameter_list {
+		variable.name.clone_from(&map[&variable.name]);
+	}
+
+	rename_code(&mut function.code, map);
+}
+
+fn rename_expression(expression: &mut Expression, map: &HashMap<String, String>) {
+	match expression {
+		Expression::Nil | Expression::Boolean(_) | Expression::Number(_) => {}
+		Expression::Name(name) => {
+			if let Some(short) = map.get(name) {
+				name.clone_from(short);
+			}
+		}
+		Expression::Index(lhs, rhs) | Expression::Binary(lhs, _, rhs) => {
+			rename_expression(lhs, map);
+			rename_expression(rhs, map);
+		}
+		Expression::Field(value, _) | Expression::Unary(_, value) | Expression::Paren(value) => {
+			rename_expression(value, map);
+		}
+		Expression::Commented(value, _) => {
+			rename_expression(value, map);
+
+			*expression = std::mem::replace(&mut **value, Expression::Nil);
+		}
+		Expression::Call(call) => rename_call(call, map),
+		Expression::Function(function) => rename_function(function, map),
+		Expression::Table(field_list) => {
+			for field in field_list {
+				match field {
+					TableField::Positional(value) => rename_expression(value, map),
+					TableField::Keyed(key, value) => {
+						rename_expression(key, map);
+						rename_expression(value, map);
+					}
+				}
+			}
+		}
+		Expression::IfElse {
+			condition,
+			on_true,
+			on_false,
+		} => {
+			rename_expression(condition, map);
+			rename_expression(on_true, map);
+			rename_expression(on_false, map);
+		}
+	}
+}
+
+fn rename_code(code: &mut Vec<Statement>, map: &HashMap<String, String>) {
+	code.retain(|v| !matches!(v, Statement::Comment(_)));
+
+	for statement in code {
+		match statement {
+			Statement::Local {
+				variable_list,
+				value_list,
+			} => {
+				value_list
+					.iter_mut()
+					.for_each(|v| rename_expression(v, map));
+
+				for variable in variable_list {
+					variable.name.clone_from(&map[&variable.name]);
+				}
+			}
+			Statement::Assign {
+				target_list,
+				value_list,
+			} => {
+				target_list
+					.iter_mut()
+					.for_each(|v| rename_expression(v, map));
+				value_list
+					.iter_mut()
+					.for_each(|v| rename_expression(v, map));
+			}
+			Statement::Call(call) => rename_call(call, map),
+			// Written as a block of its own, which has no room for the note
+			// that follows a `ReturnCall`
+			Statement::ReturnCall(call) => {
+				rename_call(call, map);
+
+				let call = std::mem::replace(call, Call::new(Expression::Nil, Vec::new()));
+
+				*statement = Statement::Do(vec![Statement::Return(vec![Expression::Call(call)])]);
+			}
+			Statement::If {
+				branch_list,
+				on_false,
+			} => {
+				for (condition, code) in branch_list {
+					rename_expression(condition, map);
+					rename_code(code, map);
+				}
+
+				if let Some(code) = on_false {
+					rename_code(code, map);
+				}
+			}
+			Statement::While { condition, code } => {
+				rename_expression(condition, map);
+				rename_code(code, map);
+			}
+			Statement::Do(code) => rename_code(code, map),
+			Statement::Return(list) => list.iter_mut().for_each(|v| rename_expression(v, map)),
+			Statement::Break | Statement::Continue | Statement::Comment(_) => {}
+		}
+	}
+}
+
+fn shorten_function(function: &mut Function) {
+	let mut names = Names::default();
+
+	names.visit_function(function);
+
+	rename_function(function, &names.into_map());
+}
+
+// Gives the locals of every function the shortest names that stay clear
+// of keywords and of whatever the function refers to from outside, and
+// takes out the comments written as part of the syntax tree
+pub fn shorten(definition: &mut Definition) {
+	definition.name = None;
+	definition.split_list.iter_mut().for_each(shorten_function);
+
+	shorten_function(&mut definition.function);
+}
@@ -1,4 +1,9 @@
 pub mod manager;
+pub mod minify;
+pub mod reindent;
 
 mod expression;
+mod peephole;
 mod statement;
+
+pub use statement::{build_func, build_split, can_split};
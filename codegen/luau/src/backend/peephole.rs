@@ -0,0 +1,490 @@
+use crate::ast::{BinaryOp, Call, Expression, Statement, TableField};
+
+fn is_pure(expression: &Expression) -> bool {
+	match expression {
+		Expression::Nil | Expression::Boolean(_) | Expression::Number(_) | Expression::Name(_) => {
+			true
+		}
+		Expression::Unary(_, value)
+		| Expression::Paren(value)
+		| Expression::Commented(value, _) => is_pure(value),
+		Expression::Binary(lhs, _, rhs) => is_pure(lhs) && is_pure(rhs),
+		Expression::IfElse {
+			condition,
+			on_true,
+			on_false,
+		} => is_pure(condition) && is_pure(on_true) && is_pure(on_false),
+		Expression::Index(..)
+		| Expression::Field(..)
+		| Expression::Call(_)
+		| Expression::Function(_)
+		| Expression::Table(_) => false,
+	}
+}
+
+// Counts the reads and writes of `name`, or gives `None` if it is declared
+// again somewhere inside
+fn count_in_expression(expression: &Expression, name: &str) -> Option<usize> {
+	let count = match expression {
+		Expression::Nil | Expression::Boolean(_) | Expression::Number(_) => 0,
+		Expression::Name(other) => usize::from(other == name),
+		Expression::Index(value, key) | Expression::Binary(value, _, key) => {
+			count_in_expression(value, name)? + count_in_expression(key, name)?
+		}
+		Expression::Field(value, _)
+		| Expression::Unary(_, value)
+		| Expression::Paren(value)
+		| Expression::Commented(value, _) => count_in_expression(value, name)?,
+		Expression::Call(call) => count_in_call(call, name)?,
+		Expression::Function(function) => {
+			if function.parameter_list.iter().any(|v| v.name == name) {
+				return None;
+			}
+
+			count_in_code(&function.code, name)?
+		}
+		Expression::Table(field_list) => {
+			let mut count = 0;
+
+			for field in field_list {
+				count += match field {
+					TableField::Positional(value) => count_in_expression(value, name)?,
+					TableField::Keyed(key, value) => {
+						count_in_expression(key, name)? + count_in_expression(value, name)?
+					}
+				};
+			}
+
+			count
+		}
+		Expression::IfElse {
+			condition,
+			on_true,
+			on_false,
+		} => {
+			count_in_expression(condition, name)?
+				+ count_in_expression(on_true, name)?
+				+ count_in_expression(on_false, name)?
+		}
+	};
+
+	Some(count)
+}
+
+fn count_in_list(list: &[Expression], name: &str) -> Option<usize> {
+	list.iter().map(|v| count_in_expression(v, name)).sum()
+}
+
+fn count_in_call(call: &Call, name: &str) -> Option<usize> {
+	Some(count_in_expression(&call.function, name)? + count_in_list(&call.argument_list, name)?)
+}
+
+fn count_in_code(code: &[Statement], name: &str) -> Option<usize> {
+	let mut count = 0;
+
+	for statement in code {
+		count += match statement {
+			Statement::Local {
+				variable_list,
+				value_list,
+			} => {
+				if variable_list.iter().any(|v| v.name == name) {
+					return None;
+				}
+
+				count_in_list(value_list, name)?
+			}
+			Statement::Assign {
+				target_list,
+				value_list,
+			} => count_in_list(target_list, name)? + count_in_list(value_list, name)?,
+			Statement::Call(call) | Statement::ReturnCall(call) => count_in_call(call, name)?,
+			Statement::If {
+				branch_list,
+				on_false,
+			} => {
+				let mut count = 0;
+
+				for (condition, code) in branch_list {
+					count += count_in_expression(condition, name)? + count_in_code(code, name)?;
+				}
+
+				if let Some(code) = on_false {
+					count += count_in_code(code, name)?;
+				}
+
+				count
+			}
+			Statement::While { condition, code } => {
+				count_in_expression(condition, name)? + count_in_code(code, name)?
+			}
+			Statement::Do(code) => count_in_code(code, name)?,
+			Statement::Return(list) => count_in_list(list, name)?,
+			Statement::Break | Statement::Continue | Statement::Comment(_) => 0,
+		};
+	}
+
+	Some(count)
+}
+
+// Where `name` sits in an expression about to be evaluated, if it is
+// read exactly once and always evaluated along with it
+enum Found {
+	Missing,
+	Once { is_rest_pure: bool },
+}
+
+fn find_in_expression(expression: &Expression, name: &str) -> Option<Found> {
+	let list: Vec<&Expression> = match expression {
+		Expression::Name(other) if other == name => {
+			return Some(Found::Once { is_rest_pure: true });
+		}
+		Expression::Binary(lhs, BinaryOp::And | BinaryOp::Or, rhs) => {
+			if count_in_expression(rhs, name)? != 0 {
+				return None;
+			}
+
+			vec![lhs, rhs]
+		}
+		Expression::IfElse {
+			condition,
+			on_true,
+			on_false,
+		} => {
+			if count_in_expression(on_true, name)? + count_in_expression(on_false, name)? != 0 {
+				return None;
+			}
+
+			vec![condition, on_true, on_false]
+		}
+		Expression::Index(value, key) | Expression::Binary(value, _, key) => vec![value, key],
+		Expression::Field(value, _)
+		| Expression::Unary(_, value)
+		| Expression::Paren(value)
+		| Expression::Commented(value, _) => vec![value],
+		Expression::Call(call) => std::iter::once(&*call.function)
+			.chain(&call.argument_list)
+			.collect(),
+		Expression::Table(field_list) => field_list
+			.iter()
+			.flat_map(|field| match field {
+				TableField::Positional(value) => vec![value],
+				TableField::Keyed(key, value) => vec![key, value],
+			})
+			.collect(),
+		Expression::Nil
+		| Expression::Boolean(_)
+		| Expression::Number(_)
+		| Expression::Name(_)
+		| Expression::Function(_) => {
+			return (count_in_expression(expression, name)? == 0).then_some(Found::Missing);
+		}
+	};
+
+	find_in_list(list, name)
+}
+
+// Siblings may be evaluated in any order, so they all count as the rest
+fn find_in_list<'a>(list: impl IntoIterator<Item = &'a Expression>, name: &str) -> Option<Found> {
+	let mut result = Found::Missing;
+	let mut is_pure_so_far = true;
+
+	for expression in list {
+		match find_in_expression(expression, name)? {
+			Found::Missing => is_pure_so_far &= is_pure(expression),
+			Found::Once { is_rest_pure } => match result {
+				Found::Missing => result = Found::Once { is_rest_pure },
+				Found::Once { .. } => return None,
+			},
+		}
+	}
+
+	match result {
+		Found::Missing => Some(Found::Missing),
+		Found::Once { is_rest_pure } => Some(Found::Once {
+			is_rest_pure: is_rest_pure && is_pure_so_far,
+		}),
+	}
+}
+
+// The expressions a statement evaluates before anything else happens, in
+// the order they are written. Lists are marked since a call at their end
+// would pass on all of its results.
+fn head_of(statement: &mut Statement) -> Option<Vec<(&mut Expression, bool)>> {
+	fn with_tail(
+		list: &mut [Expression],
+		num_wanted: usize,
+	) -> impl Iterator<Item = (&mut Expression, bool)> {
+		let len = list.len();
+
+		list.iter_mut()
+			.enumerate()
+			.map(move |(i, v)| (v, i + 1 == len && num_wanted > len))
+	}
+
+	let head = match statement {
+		Statement::Local {
+			variable_list,
+			value_list,
+		} => with_tail(value_list, variable_list.len()).collect(),
+		Statement::Assign {
+			target_list,
+			value_list,
+		} => {
+			let num_wanted = target_list.len();
+			let mut head = Vec::new();
+
+			for target in target_list {
+				match target {
+					Expression::Name(_) => {}
+					Expression::Index(value, key) => {
+						head.push((&mut **value, false));
+						head.push((&mut **key, false));
+					}
+					Expression::Field(value, _) => head.push((&mut **value, false)),
+					_ => return None,
+				}
+			}
+
+			head.extend(with_tail(value_list, num_wanted));
+			head
+		}
+		Statement::Call(call) | Statement::ReturnCall(call) => {
+			std::iter::once((&mut *call.function, false))
+				.chain(with_tail(&mut call.argument_list, usize::MAX))
+				.collect()
+		}
+		Statement::If { branch_list, .. } => vec![(&mut branch_list[0].0, false)],
+		Statement::Return(list) => with_tail(list, usize::MAX).collect(),
+		Statement::While { .. }
+		| Statement::Do(_)
+		| Statement::Break
+		| Statement::Continue
+		| Statement::Comment(_) => return None,
+	};
+
+	Some(head)
+}
+
+fn replace_in_expression(
+	expression: &mut Expression,
+	name: &str,
+	value: &mut Option<Expression>,
+	is_tail: bool,
+) {
+	match expression {
+		Expression::Name(other) if other == name => {
+			let value = value.take().unwrap();
+
+			*expression = match value {
+				Expression::Call(_) if is_tail => Expression::Paren(value.into()),
+				_ => value,
+			};
+		}
+		Expression::Index(lhs, rhs) | Expression::Binary(lhs, _, rhs) => {
+			replace_in_expression(lhs, name, value, false);
+			replace_in_expression(rhs, name, value, false);
+		}
+		Expression::Field(inner, _)
+		| Expression::Unary(_, inner)
+		| Expression::Paren(inner)
+		| Expression::Commented(inner, _) => replace_in_expression(inner, name, value, false),
+		Expression::IfElse { condition, .. } => {
+			replace_in_expression(condition, name, value, false);
+		}
+		Expression::Call(call) => {
+			let len = call.argument_list.len();
+
+			replace_in_expression(&mut call.function, name, value, false);
+
+			for (i, argument) in call.argument_list.iter_mut().enumerate() {
+				replace_in_expression(argument, name, value, i + 1 == len);
+			}
+		}
+		Expression::Table(field_list) => {
+			let len = field_list.len();
+
+			for (i, field) in field_list.iter_mut().enumerate() {
+				match field {
+					TableField::Positional(inner) => {
+						replace_in_expression(inner, name, value, i + 1 == len);
+					}
+					TableField::Keyed(key, inner) => {
+						replace_in_expression(key, name, value, false);
+						replace_in_expression(inner, name, value, false);
+					}
+				}
+			}
+		}
+		Expression::Nil
+		| Expression::Boolean(_)
+		| Expression::Number(_)
+		| Expression::Name(_)
+		| Expression::Function(_) => {}
+	}
+}
+
+fn as_spill<'a>(statement: &'a Statement, name: &str) -> Option<&'a Expression> {
+	let Statement::Assign {
+		target_list,
+		value_list,
+	} = statement
+	else {
+		return None;
+	};
+
+	match (target_list.as_slice(), value_list.as_slice()) {
+		([Expression::Name(target)], [value]) if target == name => Some(value),
+		_ => None,
+	}
+}
+
+// Whether the value of `name` is gone once the statement is done, as it
+// either overwrites it or leaves the function
+fn is_last_use(statement: &Statement, name: &str) -> bool {
+	match statement {
+		Statement::Assign { target_list, .. } => target_list
+			.iter()
+			.any(|v| matches!(v, Expression::Name(target) if target == name)),
+		Statement::Return(_) | Statement::ReturnCall(_) => true,
+		_ => false,
+	}
+}
+
+// Moves the value written to `name` into the statement right after, which
+// has to be its only reader. That holds when nothing else reads `name` or
+// when the value is gone after that statement. Either the value or everything else
+// that statement evaluates first must be free of side effects, as the order
+// of evaluation between them is not fixed.
+fn fold_at(code: &mut Vec<Statement>, index: usize, name: &str, is_single_read: bool) -> bool {
+	let Some(value) = as_spill(&code[index], name) else {
+		return false;
+	};
+
+	let is_value_pure = is_pure(value);
+	let Some(next) = code.get_mut(index + 1) else {
+		return false;
+	};
+
+	if !is_single_read && !is_last_use(next, name) {
+		return false;
+	}
+
+	let Some(head) = head_of(next) else {
+		return false;
+	};
+
+	match find_in_list(head.iter().map(|v| &*v.0), name) {
+		Some(Found::Once { is_rest_pure }) if is_rest_pure || is_value_pure => {}
+		_ => return false,
+	}
+
+	let Statement::Assign { mut value_list, .. } = code.remove(index) else {
+		unreachable!()
+	};
+
+	let mut value = value_list.pop();
+
+	for (expression, is_tail) in head_of(&mut code[index]).unwrap() {
+		replace_in_expression(expression, name, &mut value, is_tail);
+	}
+
+	true
+}
+
+// Folds every pair found from `start` on and gives whether there was any
+fn fold_in_code(code: &mut Vec<Statement>, start: usize, name: &str, is_single_read: bool) -> bool {
+	let mut is_folded = false;
+	let mut index = start;
+
+	while index < code.len() {
+		if fold_at(code, index, name, is_single_read) {
+			is_folded = true;
+
+			continue;
+		}
+
+		match &mut code[index] {
+			Statement::If {
+				branch_list,
+				on_false,
+			} => {
+				for (_, code) in branch_list {
+					is_folded |= fold_in_code(code, 0, name, is_single_read);
+				}
+
+				if let Some(code) = on_false {
+					is_folded |= fold_in_code(code, 0, name, is_single_read);
+				}
+			}
+			Statement::While { code, .. } | Statement::Do(code) => {
+				is_folded |= fold_in_code(code, 0, name, is_single_read);
+			}
+			_ => {}
+		}
+
+		index += 1;
+	}
+
+	is_folded
+}
+
+fn is_spill_declaration(statement: &Statement) -> Option<&str> {
+	match statement {
+		Statement::Local {
+			variable_list,
+			value_list,
+		} if value_list.is_empty() => match variable_list.as_slice() {
+			[variable] if variable.annotation.is_none() => Some(&variable.name),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+// Values are pushed to a temporary and read back right away whenever the
+// lowering of one instruction cannot see the next. A temporary written
+// and then read by the statement right after is dropped for its value,
+// along with its declaration once nothing else is left of it.
+pub fn fold_spills(code: &mut Vec<Statement>) {
+	for statement in code.iter_mut() {
+		match statement {
+			Statement::If {
+				branch_list,
+				on_false,
+			} => {
+				for (_, code) in branch_list {
+					fold_spills(code);
+				}
+
+				if let Some(code) = on_false {
+					fold_spills(code);
+				}
+			}
+			Statement::While { code, .. } | Statement::Do(code) => fold_spills(code),
+			_ => {}
+		}
+	}
+
+	let mut index = 0;
+
+	while index < code.len() {
+		let Some(name) = is_spill_declaration(&code[index]).map(str::to_string) else {
+			index += 1;
+
+			continue;
+		};
+
+		if count_in_code(&code[index + 1..], &name).is_some() {
+			fold_in_code(code, index + 1, &name, false);
+		}
+
+		if count_in_code(&code[index + 1..], &name) == Some(2)
+			&& fold_in_code(code, index + 1, &name, true)
+		{
+			code.remove(index);
+		} else {
+			index += 1;
+		}
+	}
+}
@@ -0,0 +1,57 @@
+use std::io::{Result, Write};
+
+// Replaces the leading tabs of every line with a number of spaces,
+// so the rest of the backend can keep writing tabs unconditionally
+pub struct Reindent<'a> {
+	inner: &'a mut dyn Write,
+	width: usize,
+	line_start: bool,
+}
+
+impl<'a> Reindent<'a> {
+	pub fn new(inner: &'a mut dyn Write, width: usize) -> Self {
+		Self {
+			inner,
+			width,
+			line_start: true,
+		}
+	}
+}
+
+impl Write for Reindent<'_> {
+	fn write(&mut self, buf: &[u8]) -> Result<usize> {
+		let mut rest = buf;
+
+		while !rest.is_empty() {
+			if self.line_start {
+				let count = rest.iter().take_while(|&&v| v == b'\t').count();
+
+				for _ in 0..count * self.width {
+					self.inner.write_all(b" ")?;
+				}
+
+				rest = &rest[count..];
+
+				if rest.is_empty() {
+					break;
+				}
+			}
+
+			let end = rest
+				.iter()
+				.position(|&v| v == b'\n')
+				.map_or(rest.len(), |v| v + 1);
+
+			self.inner.write_all(&rest[..end])?;
+			self.line_start = rest[end - 1] == b'\n';
+
+			rest = &rest[end..];
+		}
+
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> Result<()> {
+		self.inner.flush()
+	}
+}
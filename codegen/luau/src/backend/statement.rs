@@ -1,50 +1,83 @@
-use std::{
-	io::{Result, Write},
-	ops::Range,
-};
+use std::{collections::BTreeSet, ops::Range};
 
 use wasm_ast::node::{
-	Block, Br, BrIf, BrTable, Call, CallIndirect, FuncData, If, LabelType, MemoryCopy, MemoryFill,
-	MemoryGrow, ResultList, SetGlobal, SetLocal, SetTemporary, Statement, StoreAt, Terminator,
+	Block, Br, BrIf, BrTable, Call, CallIndirect, DataDrop, ElemDrop, FuncData, If, LabelType,
+	MemoryCopy, MemoryFill, MemoryGrow, MemoryInit, ResultList, SetGlobal, SetLocal, SetTemporary,
+	Statement, StoreAt, TableCopy, TableFill, TableGrow, TableInit, TableSet, Terminator,
 };
 use wasmparser::ValType;
 
 use crate::{
-	analyzer::into_string::IntoName, backend::manager::write_separated, indentation, indented, line,
+	analyzer::{fuel, into_string::IntoName, split},
+	ast::{self, BinaryOp, TableField, UnaryOp, Variable},
 };
 
 use super::{
-	expression::Condition,
-	manager::{Driver, Manager},
+	expression::{build_list, memory_at, pointer_of, table_at, Condition},
+	manager::{Driver, Manager, ToExpression},
+	peephole,
 };
 
-impl Driver for ResultList {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		write_separated(self.iter(), |t, w| t.write(mng, w), w)
+fn build_result_list(list: ResultList, mng: &Manager) -> Vec<ast::Expression> {
+	list.iter().map(|v| v.to_expression(mng)).collect()
+}
+
+fn assign(target: ast::Expression, value: ast::Expression) -> ast::Statement {
+	ast::Statement::Assign {
+		target_list: vec![target],
+		value_list: vec![value],
+	}
+}
+
+fn declare(name: impl Into<String>, value: Option<ast::Expression>) -> ast::Statement {
+	ast::Statement::Local {
+		variable_list: vec![Variable::new(name)],
+		value_list: value.into_iter().collect(),
 	}
 }
 
+// Calls with results are assignments, and plain calls otherwise
+fn call_with(
+	result_list: Vec<ast::Expression>,
+	function: ast::Expression,
+	argument_list: Vec<ast::Expression>,
+) -> ast::Statement {
+	let call = ast::Call::new(function, argument_list);
+
+	if result_list.is_empty() {
+		ast::Statement::Call(call)
+	} else {
+		ast::Statement::Assign {
+			target_list: result_list,
+			value_list: vec![ast::Expression::Call(call)],
+		}
+	}
+}
+
+fn desired() -> ast::Expression {
+	ast::Expression::name("desired")
+}
+
 impl Driver for Br {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
 		if !self.align().is_aligned() {
-			indentation!(mng, w)?;
-			self.align().new_range().write(mng, w)?;
-			write!(w, " = ")?;
-			self.align().old_range().write(mng, w)?;
-			writeln!(w)?;
+			code.push(ast::Statement::Assign {
+				target_list: build_result_list(self.align().new_range(), mng),
+				value_list: build_result_list(self.align().old_range(), mng),
+			});
 		}
 
-		if self.target() == 0 {
-			if mng.label_list().last() == Some(&Some(LabelType::Backward)) {
-				line!(mng, w, "continue")
+		let level = mng.label_list().len() - 1 - self.target();
+
+		if mng.loop_level() == Some(level) {
+			if mng.label_list()[level] == Some(LabelType::Backward) {
+				code.push(ast::Statement::Continue);
 			} else {
-				line!(mng, w, "break")
+				code.push(ast::Statement::Break);
 			}
 		} else {
-			let level = mng.label_list().len() - 1 - self.target();
-
-			line!(mng, w, "desired = {level}")?;
-			line!(mng, w, "break")
+			code.push(assign(desired(), ast::Expression::number(level)));
+			code.push(ast::Statement::Break);
 		}
 	}
 }
@@ -61,73 +94,103 @@ fn to_ordered_table(list: &[Br], default: Br) -> Vec<Br> {
 	data
 }
 
-fn write_search_layer(
+fn build_search_layer(
 	range: Range<usize>,
 	list: &[Br],
 	mng: &mut Manager,
-	w: &mut dyn Write,
-) -> Result<()> {
+	code: &mut Vec<ast::Statement>,
+) {
 	if range.len() == 1 {
-		return list[range.start].write(mng, w);
+		return list[range.start].build(mng, code);
 	}
 
 	let center = range.start + range.len() / 2;
 	let br = list[center];
+	let compare = |op| {
+		let target = ast::Expression::number(br.target());
+
+		ast::Expression::binary(ast::Expression::name("temp"), op, target)
+	};
+
+	let mut branch_list = Vec::new();
 
 	if range.start != center {
-		line!(mng, w, "if temp < {} then", br.target())?;
-		mng.indent();
-		write_search_layer(range.start..center, list, mng, w)?;
-		mng.dedent();
-		indented!(mng, w, "else")?;
+		let mut inner = Vec::new();
+
+		build_search_layer(range.start..center, list, mng, &mut inner);
+		branch_list.push((compare(BinaryOp::Lt), inner));
 	}
 
 	if range.end != center + 1 {
-		writeln!(w, "if temp > {} then", br.target())?;
-		mng.indent();
-		write_search_layer(center + 1..range.end, list, mng, w)?;
-		mng.dedent();
-		indented!(mng, w, "else")?;
+		let mut inner = Vec::new();
+
+		build_search_layer(center + 1..range.end, list, mng, &mut inner);
+		branch_list.push((compare(BinaryOp::Gt), inner));
 	}
 
-	writeln!(w)?;
-	mng.indent();
-	br.write(mng, w)?;
-	mng.dedent();
-	line!(mng, w, "end")
+	let mut on_false = Vec::new();
+
+	br.build(mng, &mut on_false);
+
+	code.push(ast::Statement::If {
+		branch_list,
+		on_false: Some(on_false),
+	});
 }
 
-fn write_table_setup(table: &BrTable, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+fn build_table_setup(table: &BrTable, mng: &Manager, code: &mut Vec<ast::Statement>) {
 	let id = mng.get_table_index(table);
+	let br_map = || ast::Expression::name("br_map").index(ast::Expression::number(id));
 
-	line!(mng, w, "if not br_map[{id}] then")?;
-	mng.indent();
-	line!(mng, w, "br_map[{id}] = (function()")?;
-	mng.indent();
-	indented!(mng, w, "return {{ [0] = ")?;
-
-	table
+	let field_list = table
 		.data()
 		.iter()
-		.try_for_each(|v| write!(w, "{}, ", v.target()))?;
+		.enumerate()
+		.map(|(i, v)| {
+			let target = ast::Expression::number(v.target());
 
-	writeln!(w, "}}")?;
-	mng.dedent();
-	line!(mng, w, "end)()")?;
-	mng.dedent();
-	line!(mng, w, "end")?;
+			if i == 0 {
+				TableField::Keyed(ast::Expression::number(0), target)
+			} else {
+				TableField::Positional(target)
+			}
+		})
+		.collect();
 
-	indented!(mng, w, "temp = br_map[{id}][")?;
-	table.condition().write(mng, w)?;
-	writeln!(w, "] or {}", table.default().target())
+	let setup = ast::Function {
+		is_native: false,
+		parameter_list: Vec::new(),
+		code: vec![ast::Statement::Return(vec![ast::Expression::Table(
+			field_list,
+		)])],
+	};
+
+	code.push(ast::Statement::If {
+		branch_list: vec![(
+			ast::Expression::unary(UnaryOp::Not, br_map()),
+			vec![assign(
+				br_map(),
+				ast::Expression::Function(setup).paren().call(Vec::new()),
+			)],
+		)],
+		on_false: None,
+	});
+
+	let target = br_map().index(table.condition().to_expression(mng));
+	let default = ast::Expression::number(table.default().target());
+
+	code.push(assign(
+		ast::Expression::name("temp"),
+		ast::Expression::binary(target, BinaryOp::Or, default),
+	));
 }
 
 impl Driver for BrTable {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
 		if self.data().is_empty() {
 			// Our condition should be pure so we probably don't need
 			// to emit it in this case.
-			return self.default().write(mng, w);
+			return self.default().build(mng, code);
 		}
 
 		// `BrTable` is optimized by first mapping all indices to targets through
@@ -136,319 +199,751 @@ impl Driver for BrTable {
 		// and the appropriate jump is performed.
 		let list = to_ordered_table(self.data(), self.default());
 
-		write_table_setup(self, mng, w)?;
-		write_search_layer(0..list.len(), &list, mng, w)
+		build_table_setup(self, mng, code);
+		build_search_layer(0..list.len(), &list, mng, code);
 	}
 }
 
+fn build_call(call: &Call, mng: &Manager) -> ast::Call {
+	ast::Call::new(
+		mng.function_reference(call.function()),
+		build_list(call.param_list(), mng),
+	)
+}
+
+fn build_call_indirect(call: &CallIndirect, mng: &Manager) -> ast::Call {
+	let function = ast::Expression::name("rt_table")
+		.field("indirect")
+		.call(vec![
+			table_at(call.table()),
+			call.index().to_expression(mng),
+			ast::Expression::name("TYPE_LIST").index(ast::Expression::number(call.ty())),
+		]);
+
+	ast::Call::new(function, build_list(call.param_list(), mng))
+}
+
 impl Driver for Terminator {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
 		match self {
-			Self::Unreachable => line!(mng, w, r#"error("out of code bounds")"#),
-			Self::Br(s) => s.write(mng, w),
-			Self::BrTable(s) => s.write(mng, w),
+			Self::Unreachable => {
+				let error = ast::Expression::name("rt_trap").field("unreachable");
+
+				code.push(call_with(
+					Vec::new(),
+					ast::Expression::name("error"),
+					vec![error],
+				));
+			}
+			Self::Br(s) => s.build(mng, code),
+			Self::BrTable(s) => s.build(mng, code),
+			Self::ReturnCall(s) => code.push(ast::Statement::ReturnCall(build_call(s, mng))),
+			Self::ReturnCallIndirect(s) => {
+				code.push(ast::Statement::ReturnCall(build_call_indirect(s, mng)));
+			}
 		}
 	}
 }
 
-fn write_br_parent(mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+fn build_br_parent(mng: &Manager, code: &mut Vec<ast::Statement>) {
 	if !mng.has_branch() || mng.label_list().iter().all(Option::is_none) {
-		return Ok(());
+		return;
 	}
 
-	line!(mng, w, "if desired then")?;
-	mng.indent();
-
-	if let Some(last) = *mng.label_list().last().unwrap() {
-		let level = mng.label_list().len() - 1;
+	let level = mng.loop_level().unwrap();
+	let mut inner = Vec::new();
 
-		line!(mng, w, "if desired == {level} then")?;
-		mng.indent();
-		line!(mng, w, "desired = nil")?;
+	if let Some(last) = mng.label_list()[level] {
+		let mut matched = vec![assign(desired(), ast::Expression::Nil)];
 
 		if last == LabelType::Backward {
-			line!(mng, w, "continue")?;
+			matched.push(ast::Statement::Continue);
 		}
 
-		mng.dedent();
-		line!(mng, w, "end")?;
+		let condition =
+			ast::Expression::binary(desired(), BinaryOp::Eq, ast::Expression::number(level));
+
+		inner.push(ast::Statement::If {
+			branch_list: vec![(condition, matched)],
+			on_false: None,
+		});
 	}
 
-	line!(mng, w, "break")?;
+	inner.push(ast::Statement::Break);
 
-	mng.dedent();
-	line!(mng, w, "end")
+	code.push(ast::Statement::If {
+		branch_list: vec![(desired(), inner)],
+		on_false: None,
+	});
 }
 
-impl Driver for Block {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		mng.push_label(self.label_type());
+fn build_code(block: &Block, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+	if !mng.config().source_map {
+		return block.code().iter().for_each(|s| s.build(mng, code));
+	}
+
+	let mut last = None;
+
+	for (stat, &offset) in block.code().iter().zip(block.offsets()) {
+		if last != Some(offset) {
+			let index = mng.function_index();
+
+			code.push(ast::Statement::Comment(format!("@{index}:{offset:#x}")));
+
+			last = Some(offset);
+		}
+
+		stat.build(mng, code);
+	}
+}
+
+fn build_fuel(block: &Block, mng: &Manager, code: &mut Vec<ast::Statement>) {
+	let Some(callback) = &mng.config().fuel_callback else {
+		return;
+	};
+
+	let cost = ast::Expression::number(fuel::block_cost(block));
+
+	code.push(call_with(
+		Vec::new(),
+		ast::Expression::name(callback),
+		vec![cost],
+	));
+}
+
+// Builds a block nothing branches to straight into the surrounding scope,
+// returning `false` when it has to be a loop instead
+fn try_build_unlabeled(block: &Block, mng: &mut Manager, code: &mut Vec<ast::Statement>) -> bool {
+	if block.label_type().is_some() || mng.config().loop_every_block {
+		return false;
+	}
+
+	mng.push_label(None);
+	build_code(block, mng, code);
+
+	if let Some(v) = block.last() {
+		v.build(mng, code);
+	}
+
+	mng.pop_label();
+
+	true
+}
+
+fn build_scope(block: &Block, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+	if let Some(index) = mng.split_index(block) {
+		build_split_call(block, index, mng, code);
+	} else if !try_build_unlabeled(block, mng, code) {
+		build_block(block, mng, code);
+	}
+}
+
+fn build_block(block: &Block, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+	// A terminator has to end its scope, so it keeps a `do` around it
+	if block.last().is_none() && try_build_unlabeled(block, mng, code) {
+		return;
+	}
+
+	if block.label_type().is_none() && !mng.config().loop_every_block {
+		let mut inner = Vec::new();
+
+		try_build_unlabeled(block, mng, &mut inner);
+
+		return code.push(ast::Statement::Do(inner));
+	}
+
+	mng.push_label(block.label_type());
+
+	let mut inner = Vec::new();
 
-		line!(mng, w, "while true do")?;
-		mng.indent();
+	if block.label_type() == Some(LabelType::Backward) {
+		build_fuel(block, mng, &mut inner);
+	}
+
+	build_code(block, mng, &mut inner);
 
-		self.code().iter().try_for_each(|s| s.write(mng, w))?;
+	match block.last() {
+		Some(v) => v.build(mng, &mut inner),
+		None => inner.push(ast::Statement::Break),
+	}
 
-		match self.last() {
-			Some(v) => v.write(mng, w)?,
-			None => line!(mng, w, "break")?,
+	code.push(ast::Statement::While {
+		condition: ast::Expression::Boolean(true),
+		code: inner,
+	});
+
+	mng.pop_label();
+	build_br_parent(mng, code);
+}
+
+impl Driver for Block {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		if let Some(index) = mng.split_index(self) {
+			build_split_call(self, index, mng, code);
+		} else {
+			build_block(self, mng, code);
 		}
+	}
+}
+
+// Luau has 255 registers and a function pins up to 180 of them, while a
+// call into a split block needs one more for every value passed in or out
+const MAX_SPLIT_VARIABLES: usize = 64;
+
+// Split blocks take the pinned variables they touch as parameters and
+// return the ones they write, while spilled ones are shared through their
+// tables
+struct SplitVariables {
+	read_list: Vec<(usize, bool)>,
+	write_list: Vec<(usize, bool)>,
+	has_local_spill: bool,
+	has_temporary_spill: bool,
+}
+
+impl SplitVariables {
+	fn new(block: &Block, mng: &Manager) -> Self {
+		let variables = split::variable_list(block);
+		let to_slot = |set: &BTreeSet<usize>| -> BTreeSet<usize> {
+			set.iter().map(|&v| mng.local_slot(v)).collect()
+		};
+
+		let read_local = to_slot(&variables.read_local);
+		let write_local = to_slot(&variables.write_local);
+		let pinned_list = |local: &BTreeSet<usize>, temporary: &BTreeSet<usize>| {
+			let local = local.iter().filter(|&&v| v < mng.num_local());
+			let temporary = temporary.iter().filter(|&&v| v < mng.num_temp());
+
+			local
+				.map(|&v| (v, true))
+				.chain(temporary.map(|&v| (v, false)))
+				.collect()
+		};
+
+		Self {
+			read_list: pinned_list(&read_local, &variables.read_temporary),
+			write_list: pinned_list(&write_local, &variables.write_temporary),
+			has_local_spill: read_local.last().is_some_and(|&v| v >= mng.num_local()),
+			has_temporary_spill: variables
+				.read_temporary
+				.last()
+				.is_some_and(|&v| v >= mng.num_temp()),
+		}
+	}
+
+	fn pinned_names<'a>(
+		list: &'a [(usize, bool)],
+		mng: &'a Manager,
+	) -> impl Iterator<Item = String> + 'a {
+		list.iter().map(|&(v, is_local)| {
+			if is_local {
+				mng.local_name(v)
+			} else {
+				mng.temporary_name(v)
+			}
+		})
+	}
+
+	fn result_list(&self, mng: &Manager) -> Vec<ast::Expression> {
+		Self::pinned_names(&self.write_list, mng)
+			.map(ast::Expression::Name)
+			.collect()
+	}
+
+	// The written variables are also read, so there are never more
+	// results than parameters
+	fn num_parameter(&self) -> usize {
+		self.read_list.len()
+			+ usize::from(self.has_local_spill)
+			+ usize::from(self.has_temporary_spill)
+	}
+
+	fn parameter_list(&self, mng: &Manager) -> Vec<String> {
+		let spill_list = [
+			("loc_spill", self.has_local_spill),
+			("reg_spill", self.has_temporary_spill),
+		];
+
+		Self::pinned_names(&self.read_list, mng)
+			.chain(
+				spill_list
+					.into_iter()
+					.filter(|v| v.1)
+					.map(|v| v.0.to_string()),
+			)
+			.collect()
+	}
+}
+
+/// Tells whether `block` passes few enough variables to be moved out of
+/// its function.
+#[must_use]
+pub fn can_split(block: &Block, mng: &Manager) -> bool {
+	SplitVariables::new(block, mng).num_parameter() <= MAX_SPLIT_VARIABLES
+}
+
+fn build_split_call(block: &Block, index: usize, mng: &Manager, code: &mut Vec<ast::Statement>) {
+	let variables = SplitVariables::new(block, mng);
+	let function = ast::Expression::name("SPLIT_LIST").index(ast::Expression::number(index));
+	let argument_list = variables
+		.parameter_list(mng)
+		.into_iter()
+		.map(ast::Expression::Name)
+		.collect();
+
+	code.push(call_with(
+		variables.result_list(mng),
+		function,
+		argument_list,
+	));
+}
+
+fn build_function_start(mng: &Manager, code: &mut Vec<ast::Statement>) {
+	if mng.has_branch() {
+		code.push(declare("desired", None));
+	}
+
+	if mng.has_table() {
+		code.push(declare("br_map", Some(ast::Expression::Table(Vec::new()))));
+	}
+}
+
+/// Builds a block moved out of its function into `SPLIT_LIST` as the
+/// function taking the variables it needs.
+pub fn build_split(block: &Block, mng: &mut Manager) -> ast::Function {
+	let variables = SplitVariables::new(block, mng);
+	let mut code = Vec::new();
+
+	build_function_start(mng, &mut code);
+	build_block(block, mng, &mut code);
 
-		mng.dedent();
-		line!(mng, w, "end")?;
+	let result_list = variables.result_list(mng);
 
-		mng.pop_label();
-		write_br_parent(mng, w)
+	if !result_list.is_empty() {
+		code.push(ast::Statement::Return(result_list));
+	}
+
+	if mng.config().fold_spills {
+		peephole::fold_spills(&mut code);
+	}
+
+	ast::Function {
+		is_native: mng.is_native(),
+		parameter_list: variables
+			.parameter_list(mng)
+			.into_iter()
+			.map(Variable::new)
+			.collect(),
+		code,
 	}
 }
 
 impl Driver for BrIf {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		indented!(mng, w, "if ")?;
-		Condition(self.condition()).write(mng, w)?;
-		writeln!(w, " then")?;
-		mng.indent();
-		self.target().write(mng, w)?;
-		mng.dedent();
-		line!(mng, w, "end")
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		let mut inner = Vec::new();
+
+		self.target().build(mng, &mut inner);
+
+		code.push(ast::Statement::If {
+			branch_list: vec![(Condition(self.condition()).to_expression(mng), inner)],
+			on_false: None,
+		});
 	}
 }
 
 impl Driver for If {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		indented!(mng, w, "if ")?;
-		Condition(self.condition()).write(mng, w)?;
-		writeln!(w, " then")?;
-
-		mng.indent();
-		self.on_true().write(mng, w)?;
-		mng.dedent();
-
-		if let Some(v) = self.on_false() {
-			line!(mng, w, "else")?;
-			mng.indent();
-			v.write(mng, w)?;
-			mng.dedent();
-		}
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		let condition = Condition(self.condition()).to_expression(mng);
+		let mut on_true = Vec::new();
+
+		build_scope(self.on_true(), mng, &mut on_true);
+
+		let on_false = self.on_false().map(|v| {
+			let mut on_false = Vec::new();
+
+			build_scope(v, mng, &mut on_false);
+
+			on_false
+		});
 
-		line!(mng, w, "end")
+		code.push(ast::Statement::If {
+			branch_list: vec![(condition, on_true)],
+			on_false,
+		});
 	}
 }
 
+// The arguments are bound to the parameters of the callee as it starts,
+// and its results are moved onto the stack of the caller as it ends
+fn build_inline(call: &Call, ast: &FuncData, mng: &Manager, code: &mut Vec<ast::Statement>) {
+	let mut inner_mng = mng.inline_manager(ast, call.function());
+	let mut inner = Vec::new();
+
+	if ast.num_param() != 0 {
+		inner.push(ast::Statement::Local {
+			variable_list: build_parameter_list(ast, &inner_mng),
+			value_list: build_list(call.param_list(), mng),
+		});
+	}
+
+	build_variable_list(ast, &inner_mng, &mut inner);
+	build_fuel(ast.code(), &inner_mng, &mut inner);
+	build_scope(ast.code(), &mut inner_mng, &mut inner);
+
+	let result_list = build_result_list(call.result_list(), mng);
+
+	if !result_list.is_empty() {
+		inner.push(ast::Statement::Assign {
+			target_list: result_list,
+			value_list: build_result_list(ResultList::new(0, ast.num_result()), &inner_mng),
+		});
+	}
+
+	code.push(ast::Statement::Do(inner));
+}
+
 impl Driver for Call {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		if !self.result_list().is_empty() {
-			self.result_list().write(mng, w)?;
-			write!(w, " = ")?;
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		if let Some(ast) = mng.inline_target(self.function()) {
+			return build_inline(self, ast, mng, code);
 		}
 
-		write!(w, "FUNC_LIST[{}](", self.function())?;
-		self.param_list().write(mng, w)?;
-		write!(w, ")")
+		let call = build_call(self, mng);
+
+		code.push(call_with(
+			build_result_list(self.result_list(), mng),
+			*call.function,
+			call.argument_list,
+		));
 	}
 }
 
 impl Driver for CallIndirect {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		if !self.result_list().is_empty() {
-			self.result_list().write(mng, w)?;
-			write!(w, " = ")?;
-		}
-
-		write!(w, "TABLE_LIST[{}].data[", self.table())?;
-		self.index().write(mng, w)?;
-		write!(w, "](")?;
-		self.param_list().write(mng, w)?;
-		write!(w, ")")
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		let call = build_call_indirect(self, mng);
+
+		code.push(call_with(
+			build_result_list(self.result_list(), mng),
+			*call.function,
+			call.argument_list,
+		));
 	}
 }
 
 impl Driver for SetTemporary {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		self.var().write(mng, w)?;
-		write!(w, " = ")?;
-		self.value().write(mng, w)
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(assign(
+			self.var().to_expression(mng),
+			self.value().to_expression(mng),
+		));
 	}
 }
 
 impl Driver for SetLocal {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		self.var().write(mng, w)?;
-		write!(w, " = ")?;
-		self.value().write(mng, w)
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(assign(
+			self.var().to_expression(mng),
+			self.value().to_expression(mng),
+		));
 	}
 }
 
 impl Driver for SetGlobal {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		write!(w, "GLOBAL_LIST[{}].value = ", self.var())?;
-		self.value().write(mng, w)
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(assign(
+			mng.global_reference(self.var()),
+			self.value().to_expression(mng),
+		));
 	}
 }
 
 impl Driver for StoreAt {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
 		let name = self.store_type().into_name();
-		let memory = self.memory();
 
-		write!(w, "rt_store_{name}(memory_at_{memory}, ")?;
+		code.push(call_with(
+			Vec::new(),
+			ast::Expression::name(format!("rt_store_{name}")),
+			vec![
+				memory_at(self.memory()),
+				pointer_of(self.pointer(), self.offset(), mng),
+				self.value().to_expression(mng),
+			],
+		));
+	}
+}
 
-		self.pointer().write(mng, w)?;
+fn rt_table(name: &str) -> ast::Expression {
+	ast::Expression::name("rt_table").field(name)
+}
 
-		if self.offset() != 0 {
-			write!(w, " + {}", self.offset())?;
-		}
+impl Driver for TableSet {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(call_with(
+			Vec::new(),
+			rt_table("set"),
+			vec![
+				table_at(self.table()),
+				self.index().to_expression(mng),
+				self.value().to_expression(mng),
+			],
+		));
+	}
+}
 
-		write!(w, ", ")?;
-		self.value().write(mng, w)?;
-		write!(w, ")")
+impl Driver for TableInit {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		let element =
+			ast::Expression::name("ELEM_LIST").index(ast::Expression::number(self.element()));
+
+		code.push(call_with(
+			Vec::new(),
+			rt_table("init"),
+			vec![
+				table_at(self.table()),
+				element,
+				self.destination().to_expression(mng),
+				self.offset().to_expression(mng),
+				self.size().to_expression(mng),
+			],
+		));
 	}
 }
 
-impl Driver for MemoryGrow {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		let memory = self.memory();
+impl Driver for TableGrow {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(call_with(
+			vec![self.result().to_expression(mng)],
+			rt_table("grow"),
+			vec![
+				table_at(self.table()),
+				self.value().to_expression(mng),
+				self.size().to_expression(mng),
+			],
+		));
+	}
+}
 
-		self.result().write(mng, w)?;
-		write!(w, " = rt_allocator_grow(memory_at_{memory}, ")?;
-		self.size().write(mng, w)?;
-		write!(w, ")")
+impl Driver for TableFill {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(call_with(
+			Vec::new(),
+			rt_table("fill"),
+			vec![
+				table_at(self.table()),
+				self.destination().to_expression(mng),
+				self.value().to_expression(mng),
+				self.size().to_expression(mng),
+			],
+		));
 	}
 }
 
-impl Driver for MemoryCopy {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		let memory_1 = self.destination().memory();
-		let memory_2 = self.source().memory();
+impl Driver for TableCopy {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(call_with(
+			Vec::new(),
+			rt_table("copy"),
+			vec![
+				table_at(self.destination_table()),
+				table_at(self.source_table()),
+				self.destination().to_expression(mng),
+				self.source().to_expression(mng),
+				self.size().to_expression(mng),
+			],
+		));
+	}
+}
+
+impl Driver for ElemDrop {
+	fn build(&self, _mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		let element =
+			ast::Expression::name("ELEM_LIST").index(ast::Expression::number(self.element()));
+
+		code.push(assign(element, ast::Expression::Nil));
+	}
+}
+
+impl Driver for MemoryGrow {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(call_with(
+			vec![self.result().to_expression(mng)],
+			ast::Expression::name("rt_allocator_grow"),
+			vec![memory_at(self.memory()), self.size().to_expression(mng)],
+		));
+	}
+}
 
-		write!(w, "rt_store_copy(memory_at_{memory_1}, ")?;
-		self.destination().pointer().write(mng, w)?;
-		write!(w, ", memory_at_{memory_2}, ")?;
-		self.source().pointer().write(mng, w)?;
-		write!(w, ", ")?;
-		self.size().write(mng, w)?;
-		write!(w, ")")
+impl Driver for MemoryCopy {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(call_with(
+			Vec::new(),
+			ast::Expression::name("rt_store_copy"),
+			vec![
+				memory_at(self.destination().memory()),
+				self.destination().pointer().to_expression(mng),
+				memory_at(self.source().memory()),
+				self.source().pointer().to_expression(mng),
+				self.size().to_expression(mng),
+			],
+		));
 	}
 }
 
 impl Driver for MemoryFill {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		let memory = self.destination().memory();
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		code.push(call_with(
+			Vec::new(),
+			ast::Expression::name("rt_store_fill"),
+			vec![
+				memory_at(self.destination().memory()),
+				self.destination().pointer().to_expression(mng),
+				self.size().to_expression(mng),
+				self.value().to_expression(mng),
+			],
+		));
+	}
+}
 
-		write!(w, "rt_store_fill(memory_at_{memory}, ")?;
-		self.destination().pointer().write(mng, w)?;
-		write!(w, ", ")?;
-		self.size().write(mng, w)?;
-		write!(w, ", ")?;
-		self.value().write(mng, w)?;
-		write!(w, ")")
+impl Driver for MemoryInit {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		let data = ast::Expression::name("DATA_LIST").index(ast::Expression::number(self.data()));
+
+		code.push(call_with(
+			Vec::new(),
+			ast::Expression::name("rt_store_init"),
+			vec![
+				memory_at(self.destination().memory()),
+				self.destination().pointer().to_expression(mng),
+				data,
+				self.offset().to_expression(mng),
+				self.size().to_expression(mng),
+			],
+		));
 	}
 }
 
-fn write_stat(stat: &dyn Driver, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-	indentation!(mng, w)?;
-	stat.write(mng, w)?;
-	writeln!(w)
+impl Driver for DataDrop {
+	fn build(&self, _mng: &mut Manager, code: &mut Vec<ast::Statement>) {
+		let data = ast::Expression::name("DATA_LIST").index(ast::Expression::number(self.data()));
+
+		code.push(assign(data, ast::Expression::Nil));
+	}
 }
 
 impl Driver for Statement {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
+	fn build(&self, mng: &mut Manager, code: &mut Vec<ast::Statement>) {
 		match self {
-			Self::Block(s) => s.write(mng, w),
-			Self::BrIf(s) => s.write(mng, w),
-			Self::If(s) => s.write(mng, w),
-			Self::Call(s) => write_stat(s, mng, w),
-			Self::CallIndirect(s) => write_stat(s, mng, w),
-			Self::SetTemporary(s) => write_stat(s, mng, w),
-			Self::SetLocal(s) => write_stat(s, mng, w),
-			Self::SetGlobal(s) => write_stat(s, mng, w),
-			Self::StoreAt(s) => write_stat(s, mng, w),
-			Self::MemoryGrow(s) => write_stat(s, mng, w),
-			Self::MemoryCopy(s) => write_stat(s, mng, w),
-			Self::MemoryFill(s) => write_stat(s, mng, w),
+			Self::Block(s) => s.build(mng, code),
+			Self::BrIf(s) => s.build(mng, code),
+			Self::If(s) => s.build(mng, code),
+			Self::Call(s) => s.build(mng, code),
+			Self::CallIndirect(s) => s.build(mng, code),
+			Self::SetTemporary(s) => s.build(mng, code),
+			Self::SetLocal(s) => s.build(mng, code),
+			Self::SetGlobal(s) => s.build(mng, code),
+			Self::StoreAt(s) => s.build(mng, code),
+			Self::MemoryGrow(s) => s.build(mng, code),
+			Self::MemoryCopy(s) => s.build(mng, code),
+			Self::MemoryFill(s) => s.build(mng, code),
+			Self::MemoryInit(s) => s.build(mng, code),
+			Self::DataDrop(s) => s.build(mng, code),
+			Self::TableSet(s) => s.build(mng, code),
+			Self::TableInit(s) => s.build(mng, code),
+			Self::TableGrow(s) => s.build(mng, code),
+			Self::TableFill(s) => s.build(mng, code),
+			Self::TableCopy(s) => s.build(mng, code),
+			Self::ElemDrop(s) => s.build(mng, code),
 		}
 	}
 }
 
-fn write_parameter_list(ast: &FuncData, w: &mut dyn Write) -> Result<()> {
-	write!(w, "function(")?;
-	write_separated(0..ast.num_param(), |i, w| write!(w, "loc_{i}"), w)?;
-	writeln!(w, ")")
+// Native code generation specializes on `number`, so only the types
+// that are plain numbers in Luau get annotated
+fn type_of(typ: ValType, mng: &Manager) -> Option<String> {
+	let is_number = matches!(typ, ValType::I32 | ValType::F32 | ValType::F64);
+
+	(mng.is_native() && is_number).then(|| "number".to_string())
 }
 
-const fn type_to_zero(typ: ValType) -> &'static str {
+fn build_parameter_list(ast: &FuncData, mng: &Manager) -> Vec<Variable> {
+	(0..ast.num_param())
+		.map(|i| Variable {
+			name: mng.local_name(i),
+			annotation: ast.param_data().get(i).and_then(|&typ| type_of(typ, mng)),
+		})
+		.collect()
+}
+
+fn type_to_zero(typ: ValType) -> ast::Expression {
 	match typ {
-		ValType::F32 | ValType::F64 => "0.0",
-		ValType::I64 => "rt_i64_ZERO",
-		_ => "0",
+		ValType::F32 | ValType::F64 => ast::Expression::number("0.0"),
+		ValType::I64 => ast::Expression::name("rt_i64_ZERO"),
+		ValType::Ref(_) => ast::Expression::Nil,
+		ValType::V128 => ast::Expression::name("rt_v128").field("ZERO"),
+		_ => ast::Expression::number(0),
 	}
 }
 
-fn write_variable_list(ast: &FuncData, mng: &Manager, w: &mut dyn Write) -> Result<()> {
-	let mut locals = ast.local_data().iter().copied();
+fn build_variable_list(ast: &FuncData, mng: &Manager, code: &mut Vec<ast::Statement>) {
+	let mut locals = mng.local_type().iter().copied();
 	let num_local = mng.num_local() - ast.num_param();
 
 	for (i, typ) in locals.by_ref().enumerate().take(num_local) {
 		let index = ast.num_param() + i;
-		let zero = type_to_zero(typ);
 
-		line!(mng, w, "local loc_{index} = {zero}")?;
+		code.push(ast::Statement::Local {
+			variable_list: vec![Variable {
+				name: mng.local_name(index),
+				annotation: type_of(typ, mng),
+			}],
+			value_list: vec![type_to_zero(typ)],
+		});
 	}
 
 	if locals.len() != 0 {
-		indented!(mng, w, "local loc_spill = {{ ")?;
-
-		for typ in locals {
-			let zero = type_to_zero(typ);
-
-			write!(w, "{zero}, ")?;
-		}
+		let list = locals
+			.map(|typ| TableField::Positional(type_to_zero(typ)))
+			.collect();
 
-		writeln!(w, "}}")?;
+		code.push(declare("loc_spill", Some(ast::Expression::Table(list))));
 	}
 
 	let mut temporaries = 0..ast.num_stack();
 
 	for i in temporaries.by_ref().take(mng.num_temp()) {
-		line!(mng, w, "local reg_{i}")?;
+		code.push(declare(mng.temporary_name(i), None));
 	}
 
 	if !temporaries.is_empty() {
-		let len = temporaries.len();
+		let len = ast::Expression::number(temporaries.len());
+		let create = ast::Expression::name("table").field("create");
 
-		line!(mng, w, "local reg_spill = table.create({len})")?;
+		code.push(declare("reg_spill", Some(create.call(vec![len]))));
 	}
-
-	Ok(())
 }
 
-impl Driver for FuncData {
-	fn write(&self, mng: &mut Manager, w: &mut dyn Write) -> Result<()> {
-		mng.indent();
-
-		write_parameter_list(self, w)?;
-		write_variable_list(self, mng, w)?;
-
-		if mng.has_branch() {
-			line!(mng, w, "local desired")?;
-		}
-
-		if mng.has_table() {
-			line!(mng, w, "local br_map = {{}}")?;
-		}
-
-		self.code().write(mng, w)?;
+/// Builds the function that `FUNC_LIST` holds for `ast`.
+pub fn build_func(ast: &FuncData, mng: &mut Manager) -> ast::Function {
+	let mut code = Vec::new();
 
-		if self.num_result() != 0 {
-			indented!(mng, w, "return ")?;
+	build_variable_list(ast, mng, &mut code);
+	build_function_start(mng, &mut code);
+	build_fuel(ast.code(), mng, &mut code);
+	build_scope(ast.code(), mng, &mut code);
 
-			ResultList::new(0, self.num_result()).write(mng, w)?;
+	if ast.num_result() != 0 {
+		let result_list = build_result_list(ResultList::new(0, ast.num_result()), mng);
 
-			writeln!(w)?;
-		}
+		code.push(ast::Statement::Return(result_list));
+	}
 
-		mng.dedent();
+	if mng.config().fold_spills {
+		peephole::fold_spills(&mut code);
+	}
 
-		line!(mng, w, "end")
+	ast::Function {
+		is_native: mng.is_native(),
+		parameter_list: build_parameter_list(ast, mng),
+		code,
 	}
 }
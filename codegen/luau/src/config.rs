@@ -0,0 +1,108 @@
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
+pub enum Indentation {
+	#[default]
+	Tab,
+	Space(usize),
+}
+
+/// Names the local holding an imported function, given the module and
+/// field name of the import.
+pub type ImportName = dyn Fn(&str, &str) -> String;
+
+/// Knobs for tuning the generated code. The `Default` value produces
+/// the exact output of `from_module_typed`.
+#[derive(Default)]
+pub struct Config {
+	/// Unit written for each level of indentation.
+	pub indentation: Indentation,
+
+	/// Drops indentation, comments and blank lines, and gives locals the
+	/// shortest names available. Takes precedence over `indentation`.
+	pub minify: bool,
+
+	/// Marks every function with the `@native` attribute.
+	pub native: bool,
+
+	/// Marks only the functions containing a loop with `@native`.
+	pub native_loops: bool,
+
+	/// Writes `RUNTIME` ahead of the module so the output stands alone.
+	pub inline_runtime: bool,
+
+	/// Binds the runtime from `require(...)` with this argument instead, so
+	/// several modules can share one copy written by `write_runtime_module`.
+	/// The text is written verbatim, like `script.Parent.Runtime` or
+	/// `"./runtime"`, and takes precedence over `inline_runtime`.
+	pub runtime_require: Option<String>,
+
+	/// Adds an `exports` table to the instance holding every export under
+	/// its exact name, next to the `func_list` style tables split by kind.
+	pub flat_exports: bool,
+
+	/// Binds every imported function to a module level local, named by
+	/// calling this with the module and field name of the import, and calls
+	/// it through that local instead of `FUNC_LIST`. The names have to be
+	/// valid identifiers that stay clear of the rest of the output.
+	pub import_name: Option<Box<ImportName>>,
+
+	/// Writes a `-- @<function>:<offset>` comment ahead of the statements
+	/// translated from the instruction at that module byte offset.
+	pub source_map: bool,
+
+	/// Names locals after the name section and annotates references to
+	/// named functions and globals. Anything unnamed keeps its numeric name.
+	pub debug_names: bool,
+
+	/// Leaves out functions that no export, start function, element
+	/// segment or `ref.func` can reach. Hosts that call into `FUNC_LIST`
+	/// by index should keep this off.
+	pub strip_unreachable: bool,
+
+	/// Exports only the items named here, and leaves out the functions
+	/// that nothing else but the dropped exports could reach, the same way
+	/// as `strip_unreachable`. Naming an export the module does not have
+	/// is an error.
+	pub export_filter: Option<Vec<String>>,
+
+	/// Writes every block as a `while true do` loop, including the ones
+	/// nothing branches to. Only meant for debugging the control flow.
+	pub loop_every_block: bool,
+
+	/// Replaces any NaN coming out of float arithmetic with the canonical
+	/// quiet NaN, so sign and payload bits match the reference interpreter.
+	/// Costs an extra call per float operation.
+	pub canonicalize_nan: bool,
+
+	/// Evaluates integer and `f64` arithmetic on constants ahead of time
+	/// and writes the result instead. Anything that would trap, produce a
+	/// NaN or round differently at runtime is left as is.
+	pub fold_constants: bool,
+
+	/// Drops temporaries that are written and then read once by the very
+	/// next statement, writing the value there instead. Values are only
+	/// moved past code that has no side effects.
+	pub fold_spills: bool,
+
+	/// Lets locals of the same type share one Luau local when their values
+	/// are never needed at the same time, which keeps large functions under
+	/// the register limit without spilling.
+	pub coalesce_locals: bool,
+
+	/// Moves blocks out into their own functions once a function grows past
+	/// about this many statements and expressions, so it stays within the
+	/// Luau limits on a single function. Only blocks that nothing branches
+	/// out of are moved.
+	pub split_threshold: Option<usize>,
+
+	/// Writes the body of a function in place of each direct call to it when
+	/// it is at most this many statements and expressions, and neither calls,
+	/// branches nor returns early. Functions that a table or `ref.func` can
+	/// reach are always called.
+	pub inline_threshold: Option<usize>,
+
+	/// Calls this function with the cost of the code about to run at the
+	/// top of every function and loop iteration, so it can raise once a
+	/// budget is spent. The `rt_fuel.consume` runtime helper charges
+	/// `rt.fuel.remaining`.
+	pub fuel_callback: Option<String>,
+}
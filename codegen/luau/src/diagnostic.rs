@@ -0,0 +1,198 @@
+use std::{
+	fmt::{Display, Formatter},
+	io::Result,
+};
+
+use wasm_ast::{
+	error::Error,
+	module::{External, Module},
+};
+use wasmparser::Operator;
+
+use crate::Config;
+
+/// A way the output knowingly differs from what WebAssembly specifies.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Approximation {
+	/// Atomic accesses and fences are lowered to plain ones, as if there
+	/// were only ever one thread.
+	PlainAtomic,
+	/// Waiting returns at once, since no other thread could notify.
+	ImmediateWait,
+	/// Notifying wakes nobody, since nothing is ever waiting.
+	EmptyNotify,
+	/// Float results that are NaN keep whatever sign and payload Luau
+	/// gives them, see [`Config::canonicalize_nan`].
+	NonCanonicalNan,
+	/// Tail calls are written as plain calls in tail position, which Luau
+	/// may not run in constant stack space.
+	TailCall,
+}
+
+impl Display for Approximation {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let text = match self {
+			Self::PlainAtomic => "atomic access lowered to a plain one (single-threaded)",
+			Self::ImmediateWait => "wait returns at once, as no thread can notify",
+			Self::EmptyNotify => "notify wakes nobody, as nothing can be waiting",
+			Self::NonCanonicalNan => "NaN results are not canonicalized",
+			Self::TailCall => "tail call, stack depth may still grow in Luau",
+		};
+
+		f.write_str(text)
+	}
+}
+
+/// An approximation made for the instruction at byte `offset` of the
+/// module, inside the function at `function`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Diagnostic {
+	pub function: usize,
+	pub offset: usize,
+	pub approximation: Approximation,
+}
+
+impl Display for Diagnostic {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"@{}:{:#x}: {}",
+			self.function, self.offset, self.approximation
+		)
+	}
+}
+
+fn approximation_of(operator: &Operator, config: &Config) -> Option<Approximation> {
+	let approximation = match operator {
+		Operator::AtomicFence
+		| Operator::I32AtomicLoad { .. }
+		| Operator::I32AtomicLoad16U { .. }
+		| Operator::I32AtomicLoad8U { .. }
+		| Operator::I32AtomicRmw16AddU { .. }
+		| Operator::I32AtomicRmw16AndU { .. }
+		| Operator::I32AtomicRmw16CmpxchgU { .. }
+		| Operator::I32AtomicRmw16OrU { .. }
+		| Operator::I32AtomicRmw16SubU { .. }
+		| Operator::I32AtomicRmw16XchgU { .. }
+		| Operator::I32AtomicRmw16XorU { .. }
+		| Operator::I32AtomicRmw8AddU { .. }
+		| Operator::I32AtomicRmw8AndU { .. }
+		| Operator::I32AtomicRmw8CmpxchgU { .. }
+		| Operator::I32AtomicRmw8OrU { .. }
+		| Operator::I32AtomicRmw8SubU { .. }
+		| Operator::I32AtomicRmw8XchgU { .. }
+		| Operator::I32AtomicRmw8XorU { .. }
+		| Operator::I32AtomicRmwAdd { .. }
+		| Operator::I32AtomicRmwAnd { .. }
+		| Operator::I32AtomicRmwCmpxchg { .. }
+		| Operator::I32AtomicRmwOr { .. }
+		| Operator::I32AtomicRmwSub { .. }
+		| Operator::I32AtomicRmwXchg { .. }
+		| Operator::I32AtomicRmwXor { .. }
+		| Operator::I32AtomicStore { .. }
+		| Operator::I32AtomicStore16 { .. }
+		| Operator::I32AtomicStore8 { .. }
+		| Operator::I64AtomicLoad { .. }
+		| Operator::I64AtomicLoad16U { .. }
+		| Operator::I64AtomicLoad32U { .. }
+		| Operator::I64AtomicLoad8U { .. }
+		| Operator::I64AtomicRmw16AddU { .. }
+		| Operator::I64AtomicRmw16AndU { .. }
+		| Operator::I64AtomicRmw16CmpxchgU { .. }
+		| Operator::I64AtomicRmw16OrU { .. }
+		| Operator::I64AtomicRmw16SubU { .. }
+		| Operator::I64AtomicRmw16XchgU { .. }
+		| Operator::I64AtomicRmw16XorU { .. }
+		| Operator::I64AtomicRmw32AddU { .. }
+		| Operator::I64AtomicRmw32AndU { .. }
+		| Operator::I64AtomicRmw32CmpxchgU { .. }
+		| Operator::I64AtomicRmw32OrU { .. }
+		| Operator::I64AtomicRmw32SubU { .. }
+		| Operator::I64AtomicRmw32XchgU { .. }
+		| Operator::I64AtomicRmw32XorU { .. }
+		| Operator::I64AtomicRmw8AddU { .. }
+		| Operator::I64AtomicRmw8AndU { .. }
+		| Operator::I64AtomicRmw8CmpxchgU { .. }
+		| Operator::I64AtomicRmw8OrU { .. }
+		| Operator::I64AtomicRmw8SubU { .. }
+		| Operator::I64AtomicRmw8XchgU { .. }
+		| Operator::I64AtomicRmw8XorU { .. }
+		| Operator::I64AtomicRmwAdd { .. }
+		| Operator::I64AtomicRmwAnd { .. }
+		| Operator::I64AtomicRmwCmpxchg { .. }
+		| Operator::I64AtomicRmwOr { .. }
+		| Operator::I64AtomicRmwSub { .. }
+		| Operator::I64AtomicRmwXchg { .. }
+		| Operator::I64AtomicRmwXor { .. }
+		| Operator::I64AtomicStore { .. }
+		| Operator::I64AtomicStore16 { .. }
+		| Operator::I64AtomicStore32 { .. }
+		| Operator::I64AtomicStore8 { .. } => Approximation::PlainAtomic,
+		Operator::MemoryAtomicWait32 { .. } | Operator::MemoryAtomicWait64 { .. } => {
+			Approximation::ImmediateWait
+		}
+		Operator::MemoryAtomicNotify { .. } => Approximation::EmptyNotify,
+		Operator::F32Add
+		| Operator::F32Sub
+		| Operator::F32Mul
+		| Operator::F32Div
+		| Operator::F32Min
+		| Operator::F32Max
+		| Operator::F32Ceil
+		| Operator::F32Floor
+		| Operator::F32Trunc
+		| Operator::F32Nearest
+		| Operator::F32Sqrt
+		| Operator::F32DemoteF64
+		| Operator::F64Add
+		| Operator::F64Sub
+		| Operator::F64Mul
+		| Operator::F64Div
+		| Operator::F64Min
+		| Operator::F64Max
+		| Operator::F64Ceil
+		| Operator::F64Floor
+		| Operator::F64Trunc
+		| Operator::F64Nearest
+		| Operator::F64Sqrt
+		| Operator::F64PromoteF32
+			if !config.canonicalize_nan =>
+		{
+			Approximation::NonCanonicalNan
+		}
+		Operator::ReturnCall { .. } | Operator::ReturnCallIndirect { .. } => {
+			Approximation::TailCall
+		}
+		_ => return None,
+	};
+
+	Some(approximation)
+}
+
+/// Finds every approximation made while translating the functions of
+/// `wasm` with `config`, in the order they appear in the module.
+///
+/// # Errors
+/// Returns `Err` if the code section is malformed.
+pub fn from_module(wasm: &Module, config: &Config) -> Result<Vec<Diagnostic>> {
+	let offset = wasm.import_count(External::Func);
+	let mut list = Vec::new();
+
+	for (i, body) in wasm.code_section().iter().enumerate() {
+		let reader = body.get_operators_reader().map_err(Error::from)?;
+
+		for pair in reader.into_iter_with_offsets() {
+			let (operator, offset_in_module) = pair.map_err(Error::from)?;
+
+			if let Some(approximation) = approximation_of(&operator, config) {
+				list.push(Diagnostic {
+					function: offset + i,
+					offset: offset_in_module,
+					approximation,
+				});
+			}
+		}
+	}
+
+	Ok(list)
+}
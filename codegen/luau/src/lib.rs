@@ -1,8 +1,150 @@
 pub static RUNTIME: &str = include_str!("../runtime/runtime.luau");
 pub static EXPORT_RUNTIME: &str = include_str!("../runtime/export_runtime.luau");
 
-pub use translator::{from_inst_list, from_module_typed, from_module_untyped};
+/// Everything in [`RUNTIME`] that generated code can refer to, in the
+/// order it is defined there. New `rt_` helpers have to be added here too.
+pub static RUNTIME_NAME_LIST: &[&str] = &[
+	"rt_i64_ZERO",
+	"rt_trap",
+	"rt_i64_from_u32",
+	"rt_i64_is_zero",
+	"rt_i64_into_u32",
+	"rt_i64_from_u64",
+	"rt_convert_f64_u64",
+	"rt_i64_ONE",
+	"rt_add_i64",
+	"rt_sub_i64",
+	"rt_lt_u64",
+	"rt_mul_i64",
+	"rt_bit_or_i64",
+	"rt_shl_i64",
+	"rt_div_u64",
+	"rt_i64_is_negative",
+	"rt_i64_negate",
+	"rt_div_i64",
+	"rt_bit_and_i64",
+	"rt_bit_not_i64",
+	"rt_bit_xor_i64",
+	"rt_shr_u64",
+	"rt_shr_i64",
+	"rt_rotl_i64",
+	"rt_rotr_i64",
+	"rt_eq_i64",
+	"rt_gt_u64",
+	"rt_lt_i64",
+	"rt_gt_i64",
+	"rt_convert_f64_i32",
+	"rt_add_i32",
+	"rt_sub_i32",
+	"rt_mul_i32",
+	"rt_div_i32",
+	"rt_div_u32",
+	"rt_rem_i32",
+	"rt_rem_u32",
+	"rt_rem_i64",
+	"rt_rem_u64",
+	"rt_neg_f64",
+	"rt_min_f64",
+	"rt_max_f64",
+	"rt_min_f32",
+	"rt_max_f32",
+	"rt_copysign_f64",
+	"rt_copysign_f32",
+	"rt_nearest_f32",
+	"rt_popcnt_i32",
+	"rt_clz_i64",
+	"rt_ctz_i64",
+	"rt_popcnt_i64",
+	"rt_le_i32",
+	"rt_lt_i32",
+	"rt_ge_i32",
+	"rt_gt_i32",
+	"rt_ne_i64",
+	"rt_le_i64",
+	"rt_le_u64",
+	"rt_ge_i64",
+	"rt_ge_u64",
+	"rt_shl_i32",
+	"rt_shr_u32",
+	"rt_shr_i32",
+	"rt_rotl_i32",
+	"rt_rotr_i32",
+	"rt_truncate_f64",
+	"rt_wrap_i32_i64",
+	"rt_truncate_i32_f64",
+	"rt_truncate_i64_f64",
+	"rt_truncate_u64_f64",
+	"rt_saturate_i32_f64",
+	"rt_saturate_u32_f64",
+	"rt_saturate_i64_f64",
+	"rt_saturate_u64_f64",
+	"rt_extend_i32_n8",
+	"rt_extend_i32_n16",
+	"rt_extend_i64_n8",
+	"rt_extend_i64_n16",
+	"rt_extend_i64_n32",
+	"rt_extend_i64_i32",
+	"rt_extend_i64_u32",
+	"rt_convert_f64_i64",
+	"rt_reinterpret_i32_f32",
+	"rt_reinterpret_i64_f64",
+	"rt_reinterpret_f32_i32",
+	"rt_reinterpret_f64_i64",
+	"rt_canonicalize",
+	"rt_load_i32_i8",
+	"rt_load_i32_u8",
+	"rt_load_i32_i16",
+	"rt_load_i32_u16",
+	"rt_load_i32",
+	"rt_load_i64_i8",
+	"rt_load_i64_u8",
+	"rt_load_i64_i16",
+	"rt_load_i64_u16",
+	"rt_load_i64_i32",
+	"rt_load_i64_u32",
+	"rt_load_i64",
+	"rt_load_f32",
+	"rt_load_f64",
+	"rt_load_string",
+	"rt_store_i32_n8",
+	"rt_store_i32_n16",
+	"rt_store_i32",
+	"rt_store_i64_n8",
+	"rt_store_i64_n16",
+	"rt_store_i64_n32",
+	"rt_store_i64",
+	"rt_store_f32",
+	"rt_store_f64",
+	"rt_store_string",
+	"rt_store_copy",
+	"rt_store_fill",
+	"rt_store_init",
+	"rt_allocator_new",
+	"rt_allocator_size",
+	"rt_allocator_grow",
+	"rt_import",
+	"rt_table",
+	"rt_fuel",
+	"rt_load_v128",
+	"rt_store_v128",
+	"rt_v128",
+];
+
+pub use config::{Config, ImportName, Indentation};
+pub use diagnostic::Diagnostic;
+pub use manifest::Manifest;
+pub use translator::{
+	build_function, from_function, from_inst_list, from_module_typed,
+	from_module_typed_diagnostics, from_module_typed_with_manifest, from_module_untyped,
+	from_module_with_config, from_module_with_diagnostics, from_module_with_manifest,
+	from_module_with_rewrite, write_runtime_module,
+};
+
+pub mod ast;
+pub mod diagnostic;
+pub mod manifest;
 
 mod analyzer;
 mod backend;
+mod config;
 mod translator;
@@ -0,0 +1,258 @@
+use std::io::{Result, Write};
+
+use wasm_ast::module::{External, Module, TypeInfo};
+use wasmparser::{FuncType, MemoryType, TypeRef, ValType};
+
+use crate::Config;
+
+/// Parameter and result types of a function.
+pub struct Signature {
+	pub param_list: Vec<ValType>,
+	pub result_list: Vec<ValType>,
+}
+
+impl From<&FuncType> for Signature {
+	fn from(value: &FuncType) -> Self {
+		Self {
+			param_list: value.params().to_vec(),
+			result_list: value.results().to_vec(),
+		}
+	}
+}
+
+/// Something the host can reach under `name`, where `index` is its
+/// position among everything of the same kind.
+pub struct Export {
+	pub name: String,
+	pub kind: External,
+	pub index: usize,
+	pub signature: Option<Signature>,
+}
+
+/// Something the host has to provide as `imports[module][name]`.
+pub struct Import {
+	pub module: String,
+	pub name: String,
+	pub kind: External,
+	pub index: usize,
+	pub signature: Option<Signature>,
+}
+
+/// Size limits of a memory in pages, as declared.
+pub struct Memory {
+	pub index: usize,
+	pub min: u64,
+	pub max: Option<u64>,
+	pub is_shared: bool,
+}
+
+pub struct Global {
+	pub index: usize,
+	pub ty: ValType,
+	pub is_mutable: bool,
+}
+
+/// Everything that crosses the boundary of a module, so tooling does not
+/// have to parse it again. Memories and globals are listed whether they
+/// are imported or defined.
+pub struct Manifest {
+	pub export_list: Vec<Export>,
+	pub import_list: Vec<Import>,
+	pub memory_list: Vec<Memory>,
+	pub global_list: Vec<Global>,
+}
+
+const fn kind_name(kind: External) -> &'static str {
+	match kind {
+		External::Func => "func",
+		External::Table => "table",
+		External::Memory => "memory",
+		External::Global => "global",
+		External::Tag => "tag",
+	}
+}
+
+const fn to_memory(index: usize, ty: &MemoryType) -> Memory {
+	Memory {
+		index,
+		min: ty.initial,
+		max: ty.maximum,
+		is_shared: ty.shared,
+	}
+}
+
+fn write_type_list(name: &str, list: &[ValType], w: &mut dyn Write) -> Result<()> {
+	write!(w, "{name} = {{ ")?;
+
+	for ty in list {
+		write!(w, r#""{ty}", "#)?;
+	}
+
+	write!(w, "}}, ")
+}
+
+fn write_signature(signature: Option<&Signature>, w: &mut dyn Write) -> Result<()> {
+	let Some(signature) = signature else {
+		return Ok(());
+	};
+
+	write_type_list("params", &signature.param_list, w)?;
+	write_type_list("results", &signature.result_list, w)
+}
+
+impl Manifest {
+	/// Collects the manifest of `wasm` as translated with `config`, which
+	/// only lists the exports kept by [`Config::export_filter`].
+	#[must_use]
+	pub fn from_module(wasm: &Module, type_info: &TypeInfo, config: &Config) -> Self {
+		let mut import_list = Vec::new();
+		let mut memory_list = Vec::new();
+		let mut global_list = Vec::new();
+		let mut count = [0; 5];
+
+		for import in wasm.import_section() {
+			let kind = External::from(import.ty);
+			let index = count[kind as usize];
+
+			count[kind as usize] += 1;
+
+			match import.ty {
+				TypeRef::Memory(ty) => memory_list.push(to_memory(index, &ty)),
+				TypeRef::Global(ty) => global_list.push(Global {
+					index,
+					ty: ty.content_type,
+					is_mutable: ty.mutable,
+				}),
+				_ => {}
+			}
+
+			import_list.push(Import {
+				module: import.module.to_string(),
+				name: import.name.to_string(),
+				kind,
+				index,
+				signature: (kind == External::Func).then(|| type_info.func_type(index).into()),
+			});
+		}
+
+		let memory_offset = wasm.import_count(External::Memory);
+		let global_offset = wasm.import_count(External::Global);
+
+		for (i, ty) in wasm.memory_section().iter().enumerate() {
+			memory_list.push(to_memory(memory_offset + i, ty));
+		}
+
+		for (i, global) in wasm.global_section().iter().enumerate() {
+			global_list.push(Global {
+				index: global_offset + i,
+				ty: global.ty.content_type,
+				is_mutable: global.ty.mutable,
+			});
+		}
+
+		let export_list = wasm
+			.export_section()
+			.iter()
+			.filter(|export| {
+				config
+					.export_filter
+					.as_ref()
+					.is_none_or(|list| list.iter().any(|name| name == export.name))
+			})
+			.map(|export| {
+				let kind = External::from(export.kind);
+				let index = export.index.try_into().unwrap();
+
+				Export {
+					name: export.name.to_string(),
+					kind,
+					index,
+					signature: (kind == External::Func).then(|| type_info.func_type(index).into()),
+				}
+			})
+			.collect();
+
+		Self {
+			export_list,
+			import_list,
+			memory_list,
+			global_list,
+		}
+	}
+
+	/// Writes the manifest as a Lua chunk returning it as a table.
+	///
+	/// # Errors
+	/// Returns `Err` if writing to `Write` failed.
+	pub fn write(&self, w: &mut dyn Write) -> Result<()> {
+		writeln!(w, "return {{")?;
+		writeln!(w, "\texports = {{")?;
+
+		for Export {
+			name,
+			kind,
+			index,
+			signature,
+		} in &self.export_list
+		{
+			let name = name.as_bytes().escape_ascii();
+			let kind = kind_name(*kind);
+
+			write!(w, "\t\t{{ ")?;
+			write!(w, r#"name = "{name}", kind = "{kind}", index = {index}, "#)?;
+			write_signature(signature.as_ref(), w)?;
+			writeln!(w, "}},")?;
+		}
+
+		writeln!(w, "\t}},")?;
+		writeln!(w, "\timports = {{")?;
+
+		for Import {
+			module,
+			name,
+			kind,
+			index,
+			signature,
+		} in &self.import_list
+		{
+			let module = module.as_bytes().escape_ascii();
+			let name = name.as_bytes().escape_ascii();
+			let kind = kind_name(*kind);
+
+			write!(w, "\t\t{{ ")?;
+			write!(
+				w,
+				r#"module = "{module}", name = "{name}", kind = "{kind}", index = {index}, "#
+			)?;
+			write_signature(signature.as_ref(), w)?;
+			writeln!(w, "}},")?;
+		}
+
+		writeln!(w, "\t}},")?;
+		writeln!(w, "\tmemories = {{")?;
+
+		for memory in &self.memory_list {
+			write!(w, "\t\t{{ index = {}, min = {}, ", memory.index, memory.min)?;
+
+			if let Some(max) = memory.max {
+				write!(w, "max = {max}, ")?;
+			}
+
+			writeln!(w, "shared = {} }},", memory.is_shared)?;
+		}
+
+		writeln!(w, "\t}},")?;
+		writeln!(w, "\tglobals = {{")?;
+
+		for global in &self.global_list {
+			writeln!(
+				w,
+				r#"		{{ index = {}, type = "{}", mutable = {} }},"#,
+				global.index, global.ty, global.is_mutable
+			)?;
+		}
+
+		writeln!(w, "\t}},")?;
+		writeln!(w, "}}")
+	}
+}
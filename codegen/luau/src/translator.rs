@@ -1,21 +1,31 @@
 use std::{
-	collections::BTreeSet,
-	io::{Result, Write},
+	collections::{BTreeMap, BTreeSet},
+	io::{ErrorKind, Result, Write},
 };
 
 use wasm_ast::{
+	error::Error,
 	factory::Factory,
 	module::{External, Module, TypeInfo},
 	node::{FuncData, Statement},
 };
 use wasmparser::{
-	ConstExpr, Data, DataKind, Element, ElementItems, ElementKind, Export, Import, Operator,
-	OperatorsReader, ValType,
+	ConstExpr, Data, DataKind, Element, ElementItems, ElementKind, Export, FuncType, Import,
+	Operator, OperatorsReader, Type, ValType,
 };
 
 use crate::{
-	analyzer::localize,
-	backend::manager::{Driver, Manager},
+	analyzer::{inline, localize, reachable, split},
+	ast::Definition,
+	backend::{
+		build_func, build_split, can_split,
+		manager::{write_separated, Manager, ToExpression},
+		minify::{self, Minify},
+		reindent::Reindent,
+	},
+	diagnostic::{self, Diagnostic},
+	manifest::Manifest,
+	Config, Indentation,
 };
 
 trait AsIEName {
@@ -29,14 +39,15 @@ impl AsIEName for External {
 			Self::Table => "table_list",
 			Self::Memory => "memory_list",
 			Self::Global => "global_list",
+			Self::Tag => unimplemented!(),
 		}
 	}
 }
 
-fn reader_to_code(reader: OperatorsReader) -> Vec<Operator> {
+fn reader_to_code(reader: OperatorsReader) -> Result<Vec<Operator>> {
 	let parsed: std::result::Result<_, _> = reader.into_iter().collect();
 
-	parsed.unwrap()
+	parsed.map_err(|error| Error::from(error).into())
 }
 
 fn write_named_array(name: &str, len: usize, w: &mut dyn Write) -> Result<()> {
@@ -47,18 +58,59 @@ fn write_named_array(name: &str, len: usize, w: &mut dyn Write) -> Result<()> {
 	writeln!(w, "local {name} = table.create({len})")
 }
 
-fn write_constant(init: &ConstExpr, type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	let code = reader_to_code(init.get_operators_reader());
-	let func = Factory::from_type_info(type_info).create_anonymous(&code);
+fn write_constant(
+	init: &ConstExpr,
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<()> {
+	let code = reader_to_code(init.get_operators_reader())?;
+	let func = Factory::from_type_info(type_info).create_anonymous(&code)?;
 
 	if let Some(Statement::SetTemporary(stat)) = func.code().code().last() {
-		stat.value().write(&mut Manager::empty(), w)
+		let value = stat.value().to_expression(&Manager::empty(config));
+
+		value.write(0, w)
 	} else {
 		writeln!(w, r#"error("Valueless constant")"#)
 	}
 }
 
-fn write_import_of(list: &[Import], wanted: External, w: &mut dyn Write) -> Result<()> {
+const fn type_to_luau(typ: ValType) -> &'static str {
+	match typ {
+		ValType::I64 => "Vector3",
+		ValType::V128 => "buffer",
+		ValType::Ref(_) => "any",
+		_ => "number",
+	}
+}
+
+fn write_type_list(list: &[ValType], w: &mut dyn Write) -> Result<()> {
+	write!(w, "(")?;
+	write_separated(list.iter(), |&v, w| write!(w, "{}", type_to_luau(v)), w)?;
+	write!(w, ")")
+}
+
+// Functions crossing the module boundary are asserted to their WebAssembly
+// signature, so the host sees typed imports and exports
+fn write_signature(ty: &FuncType, w: &mut dyn Write) -> Result<()> {
+	write!(w, " :: ")?;
+	write_type_list(ty.params(), w)?;
+	write!(w, " -> ")?;
+
+	match ty.results() {
+		[result] => write!(w, "{}", type_to_luau(*result)),
+		results => write_type_list(results, w),
+	}
+}
+
+fn write_import_of(
+	list: &[Import],
+	wanted: External,
+	type_info: &TypeInfo,
+	import_name_list: &[String],
+	w: &mut dyn Write,
+) -> Result<()> {
 	let lower = wanted.as_ie_name();
 	let upper = lower.to_uppercase();
 
@@ -67,40 +119,161 @@ fn write_import_of(list: &[Import], wanted: External, w: &mut dyn Write) -> Resu
 		.filter(|v| External::from(v.ty) == wanted)
 		.enumerate()
 	{
+		let module = module.as_bytes().escape_ascii();
+		let name = name.as_bytes().escape_ascii();
+		let local = import_name_list.get(i).filter(|_| wanted == External::Func);
+
 		write!(w, "\t")?;
-		writeln!(w, r#"{upper}[{i}] = wasm["{module}"].{lower}["{name}"]"#)?;
+
+		match local {
+			Some(local) => write!(w, "{local}")?,
+			None => write!(w, "{upper}[{i}]")?,
+		}
+
+		write!(
+			w,
+			r#" = rt_import(imports, "{module}", "{name}", "{lower}")"#
+		)?;
+
+		if wanted == External::Func {
+			write_signature(type_info.func_type(i), w)?;
+		}
+
+		writeln!(w)?;
+
+		if let Some(local) = local {
+			writeln!(w, "\t{upper}[{i}] = {local}")?;
+		}
 	}
 
 	Ok(())
 }
 
-fn write_export_of(list: &[Export], wanted: External, w: &mut dyn Write) -> Result<()> {
-	let lower = wanted.as_ie_name();
-	let upper = lower.to_uppercase();
+// Names are written escaped, so they come out byte for byte even when
+// they are not valid identifiers
+fn write_export_entry(export: &Export, type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
+	let Export { name, kind, index } = *export;
+	let external = External::from(kind);
+	let upper = external.as_ie_name().to_uppercase();
+
+	write!(w, "\t\t\t")?;
+	write!(
+		w,
+		r#"["{}"] = {upper}[{index}]"#,
+		name.as_bytes().escape_ascii()
+	)?;
+
+	if external == External::Func {
+		write_signature(type_info.func_type(index.try_into().unwrap()), w)?;
+	}
+
+	writeln!(w, ",")
+}
+
+fn write_export_of(
+	list: &[Export],
+	wanted: External,
+	type_info: &TypeInfo,
+	w: &mut dyn Write,
+) -> Result<()> {
+	writeln!(w, "\t\t{} = {{", wanted.as_ie_name())?;
+
+	for export in list.iter().filter(|v| External::from(v.kind) == wanted) {
+		write_export_entry(export, type_info, w)?;
+	}
 
-	writeln!(w, "\t\t{lower} = {{")?;
+	writeln!(w, "\t\t}},")
+}
 
-	for Export { name, index, .. } in list.iter().filter(|v| External::from(v.kind) == wanted) {
-		write!(w, "\t\t\t")?;
-		writeln!(w, r#"["{name}"] = {upper}[{index}],"#)?;
+fn write_flat_export_list(list: &[Export], type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
+	writeln!(w, "\t\texports = {{")?;
+
+	for export in list
+		.iter()
+		.filter(|v| External::from(v.kind) != External::Tag)
+	{
+		write_export_entry(export, type_info, w)?;
 	}
 
 	writeln!(w, "\t\t}},")
 }
 
-fn write_import_list(list: &[Import], w: &mut dyn Write) -> Result<()> {
-	write_import_of(list, External::Func, w)?;
-	write_import_of(list, External::Table, w)?;
-	write_import_of(list, External::Memory, w)?;
-	write_import_of(list, External::Global, w)
+fn write_import_list(
+	list: &[Import],
+	type_info: &TypeInfo,
+	import_name_list: &[String],
+	w: &mut dyn Write,
+) -> Result<()> {
+	write_import_of(list, External::Func, type_info, import_name_list, w)?;
+	write_import_of(list, External::Table, type_info, &[], w)?;
+	write_import_of(list, External::Memory, type_info, &[], w)?;
+	write_import_of(list, External::Global, type_info, &[], w)
 }
 
-fn write_export_list(list: &[Export], w: &mut dyn Write) -> Result<()> {
+fn write_export_list(
+	list: &[Export],
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<()> {
 	writeln!(w, "{}", crate::EXPORT_RUNTIME)?;
-	write_export_of(list, External::Func, w)?;
-	write_export_of(list, External::Table, w)?;
-	write_export_of(list, External::Memory, w)?;
-	write_export_of(list, External::Global, w)
+	write_export_of(list, External::Func, type_info, w)?;
+	write_export_of(list, External::Table, type_info, w)?;
+	write_export_of(list, External::Memory, type_info, w)?;
+	write_export_of(list, External::Global, type_info, w)?;
+
+	if config.flat_exports {
+		write_flat_export_list(list, type_info, w)?;
+	}
+
+	Ok(())
+}
+
+// Types are matched by structure, even across modules, so each
+// signature is spelled out instead of going by its type index
+fn write_func_type(ty: &FuncType, w: &mut dyn Write) -> Result<()> {
+	write!(w, "\"")?;
+
+	for param in ty.params() {
+		write!(w, "{param} ")?;
+	}
+
+	write!(w, "->")?;
+
+	for result in ty.results() {
+		write!(w, " {result}")?;
+	}
+
+	write!(w, "\"")
+}
+
+fn write_signature_list(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	func_list: &[(usize, FuncData)],
+	w: &mut dyn Write,
+) -> Result<()> {
+	for (i, ty) in wasm.type_section().iter().enumerate() {
+		let Type::Func(ty) = ty else {
+			continue;
+		};
+
+		write!(w, "\tTYPE_LIST[{i}] = ")?;
+		write_func_type(ty, w)?;
+		writeln!(w)?;
+	}
+
+	let import_list = 0..wasm.import_count(External::Func);
+	let defined_list = func_list.iter().map(|v| v.0);
+
+	for index in import_list.chain(defined_list) {
+		write!(w, "\tFUNC_LIST[{index}] = ")?;
+		write!(w, "rt_table.register(FUNC_LIST[{index}], ")?;
+		write_func_type(type_info.func_type(index), w)?;
+		writeln!(w, ")")?;
+	}
+
+	Ok(())
 }
 
 fn write_table_list(wasm: &Module, w: &mut dyn Write) -> Result<()> {
@@ -110,7 +283,7 @@ fn write_table_list(wasm: &Module, w: &mut dyn Write) -> Result<()> {
 	for (i, table) in table.iter().enumerate() {
 		let index = offset + i;
 		let min = table.ty.initial;
-		let max = table.ty.maximum.unwrap_or(0xFFFF);
+		let max = table.ty.maximum.unwrap_or(0xFFFF_FFFF);
 
 		writeln!(
 			w,
@@ -128,7 +301,14 @@ fn write_memory_list(wasm: &Module, w: &mut dyn Write) -> Result<()> {
 	for (i, ty) in memory.iter().enumerate() {
 		let index = offset + i;
 		let min = ty.initial;
-		let max = ty.maximum.unwrap_or(0xFFFF);
+		let max = ty.maximum.unwrap_or(0x1_0000);
+
+		if ty.shared {
+			writeln!(
+				w,
+				"\t-- shared memory, atomics are lowered to plain accesses (single-threaded approximation)"
+			)?;
+		}
 
 		writeln!(w, "\tMEMORY_LIST[{index}] = rt_allocator_new({min}, {max})")?;
 	}
@@ -136,7 +316,12 @@ fn write_memory_list(wasm: &Module, w: &mut dyn Write) -> Result<()> {
 	Ok(())
 }
 
-fn write_global_list(wasm: &Module, type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
+fn write_global_list(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<()> {
 	let offset = wasm.import_count(External::Global);
 	let global = wasm.global_section();
 
@@ -144,61 +329,107 @@ fn write_global_list(wasm: &Module, type_info: &TypeInfo, w: &mut dyn Write) ->
 		let index = offset + i;
 
 		write!(w, "\tGLOBAL_LIST[{index}] = {{ value = ")?;
-		write_constant(&global.init_expr, type_info, w)?;
+		write_constant(&global.init_expr, type_info, config, w)?;
 		writeln!(w, " }}")?;
 	}
 
 	Ok(())
 }
 
-fn write_element_list(list: &[Element], type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	for element in list {
-		let ElementKind::Active {
-			table_index: index,
-			offset_expr: init,
-		} = element.kind
-		else {
-			unimplemented!("passive elements not supported")
-		};
+fn write_element_items(
+	element: &Element,
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<u32> {
+	write!(w, "{{ ")?;
+
+	match element.items.clone() {
+		ElementItems::Functions(functions) => {
+			for index in functions {
+				let index = index.map_err(Error::from)?;
+				write!(w, "FUNC_LIST[{index}],")?;
+			}
+		}
+		ElementItems::Expressions(expressions) => {
+			for init in expressions {
+				let init = init.map_err(Error::from)?;
+				write_constant(&init, type_info, config, w)?;
+				write!(w, ",")?;
+			}
+		}
+	}
+
+	write!(w, " }}")?;
+
+	// Null references leave holes in the list, so the length has
+	// to come from the section instead of `#data`
+	let len = match &element.items {
+		ElementItems::Functions(list) => list.count(),
+		ElementItems::Expressions(list) => list.count(),
+	};
+
+	Ok(len)
+}
+
+// Passive segments are kept around for `table.init`, while active and
+// declared ones count as dropped once the module is instantiated
+fn write_element_list(
+	list: &[Element],
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<()> {
+	for (i, element) in list.iter().enumerate() {
+		let (index, init) = match element.kind {
+			ElementKind::Passive => {
+				write!(w, "\tELEM_LIST[{i}] = {{ data = ")?;
+
+				let len = write_element_items(element, type_info, config, w)?;
 
-		let index = index.unwrap_or(0);
+				writeln!(w, ", len = {len} }}")?;
+
+				continue;
+			}
+			ElementKind::Declared => continue,
+			ElementKind::Active {
+				table_index,
+				offset_expr,
+			} => (table_index.unwrap_or(0), offset_expr),
+		};
 
 		writeln!(w, "\tdo")?;
 		writeln!(w, "\t\tlocal target = TABLE_LIST[{index}].data")?;
 		write!(w, "\t\tlocal offset = ")?;
 
-		write_constant(&init, type_info, w)?;
+		write_constant(&init, type_info, config, w)?;
 
 		writeln!(w)?;
-		write!(w, "\t\tlocal data = {{ ")?;
+		write!(w, "\t\tlocal data = ")?;
 
-		match element.items.clone() {
-			ElementItems::Functions(functions) => {
-				for index in functions {
-					let index = index.unwrap();
-					write!(w, "FUNC_LIST[{index}],")?;
-				}
-			}
-			ElementItems::Expressions(expressions) => {
-				for init in expressions {
-					let init = init.unwrap();
-					write_constant(&init, type_info, w)?;
-				}
-			}
-		}
+		let len = write_element_items(element, type_info, config, w)?;
 
-		writeln!(w, " }}")?;
-		writeln!(w, "\t\ttable.move(data, 1, #data, offset, target)")?;
+		writeln!(w)?;
+		writeln!(w, "\t\ttable.move(data, 1, {len}, offset, target)")?;
 		writeln!(w, "\tend")?;
 	}
 
 	Ok(())
 }
 
-fn write_data_list(list: &[Data], type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	for data in list {
+fn write_data_list(
+	list: &[Data],
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<()> {
+	for (i, data) in list.iter().enumerate() {
 		let (index, init) = match data.kind {
-			DataKind::Passive => unimplemented!("passive data not supported"),
+			DataKind::Passive => {
+				writeln!(w, "\tDATA_LIST[{i}] = \"{}\"", data.data.escape_ascii())?;
+
+				continue;
+			}
 			DataKind::Active {
 				memory_index,
 				offset_expr,
@@ -206,27 +437,126 @@ fn write_data_list(list: &[Data], type_info: &TypeInfo, w: &mut dyn Write) -> Re
 		};
 
 		write!(w, "\trt_store_string(MEMORY_LIST[{index}], ")?;
-		write_constant(&init, type_info, w)?;
+		write_constant(&init, type_info, config, w)?;
 		writeln!(w, r#","{}")"#, data.data.escape_ascii())?;
 	}
 
 	Ok(())
 }
 
-fn build_func_list(wasm: &Module, type_info: &TypeInfo) -> Vec<FuncData> {
+fn build_func_list(
+	wasm: &Module,
+	type_info: &TypeInfo,
+) -> wasm_ast::error::Result<Vec<(usize, FuncData)>> {
 	let offset = wasm.import_count(External::Func);
 	let mut builder = Factory::from_type_info(type_info);
 
 	wasm.code_section()
 		.iter()
 		.enumerate()
-		.map(|f| builder.create_indexed(f.0 + offset, f.1).unwrap())
+		.map(|f| {
+			let index = f.0 + offset;
+
+			builder.create_indexed(index, f.1).map(|v| (index, v))
+		})
+		.collect()
+}
+
+fn add_const_references(init: &ConstExpr, list: &mut Vec<usize>) -> Result<()> {
+	for op in reader_to_code(init.get_operators_reader())? {
+		if let Operator::RefFunc { function_index } = op {
+			list.push(function_index.try_into().unwrap());
+		}
+	}
+
+	Ok(())
+}
+
+// Functions named by element segments and globals, which is everything
+// a table may end up holding apart from the `ref.func` uses in code
+fn find_indirect_list(wasm: &Module) -> Result<Vec<usize>> {
+	let mut list = Vec::new();
+
+	for element in wasm.element_section() {
+		match element.items.clone() {
+			ElementItems::Functions(functions) => {
+				for index in functions {
+					let index = index.map_err(Error::from)?;
+
+					list.push(index.try_into().unwrap());
+				}
+			}
+			ElementItems::Expressions(expressions) => {
+				for init in expressions {
+					let init = init.map_err(Error::from)?;
+
+					add_const_references(&init, &mut list)?;
+				}
+			}
+		}
+	}
+
+	for global in wasm.global_section() {
+		add_const_references(&global.init_expr, &mut list)?;
+	}
+
+	Ok(list)
+}
+
+// Every name asked for has to be there, so a typo does not quietly
+// drop an export
+fn find_export_list<'a>(wasm: &'a Module, config: &Config) -> Result<Vec<Export<'a>>> {
+	let list = wasm.export_section();
+	let Some(filter) = &config.export_filter else {
+		return Ok(list.to_vec());
+	};
+
+	if let Some(name) = filter.iter().find(|&v| list.iter().all(|e| e.name != v)) {
+		let message = format!("no export is named `{name}`");
+
+		return Err(std::io::Error::new(ErrorKind::InvalidInput, message));
+	}
+
+	Ok(list
+		.iter()
+		.filter(|v| filter.iter().any(|name| name == v.name))
+		.copied()
+		.collect())
+}
+
+// Anything a table may end up holding stays live, as `call_indirect`
+// can reach it without a direct call
+fn find_root_list(wasm: &Module, export_list: &[Export]) -> Result<Vec<usize>> {
+	let mut list: Vec<_> = export_list
+		.iter()
+		.filter(|v| External::from(v.kind) == External::Func)
+		.map(|v| v.index.try_into().unwrap())
+		.collect();
+
+	if let Some(start) = wasm.start_section() {
+		list.push(start.try_into().unwrap());
+	}
+
+	list.extend(find_indirect_list(wasm)?);
+
+	Ok(list)
+}
+
+fn find_import_name_list(wasm: &Module, config: &Config) -> Vec<String> {
+	let Some(import_name) = &config.import_name else {
+		return Vec::new();
+	};
+
+	wasm.import_section()
+		.iter()
+		.filter(|v| External::from(v.ty) == External::Func)
+		.map(|v| import_name(v.module, v.name))
 		.collect()
 }
 
 fn write_localize_used(
 	wasm: &Module,
-	func_list: &[FuncData],
+	func_list: &[(usize, FuncData)],
 	w: &mut dyn Write,
 ) -> Result<BTreeSet<usize>> {
 	let mut loc_set = BTreeSet::new();
@@ -243,7 +573,7 @@ fn write_localize_used(
 		loc_set.insert(("i64", "from_u32"));
 	}
 
-	for (loc, mem) in func_list.iter().map(localize::visit) {
+	for (loc, mem) in func_list.iter().map(|v| localize::visit(&v.1)) {
 		loc_set.extend(loc);
 		mem_set.extend(mem);
 	}
@@ -255,42 +585,96 @@ fn write_localize_used(
 	Ok(mem_set)
 }
 
-fn write_func_start(wasm: &Module, index: u32, w: &mut dyn Write) -> Result<()> {
-	write!(w, "FUNC_LIST[{index}] = ")?;
+fn build_definition<'a>(
+	wasm: &'a Module,
+	func: &FuncData,
+	index: usize,
+	config: &'a Config,
+	inline_map: &'a BTreeMap<usize, &'a FuncData>,
+	import_name_list: &'a [String],
+) -> Definition {
+	let mut mng = Manager::function(func, index, config);
+
+	if config.debug_names {
+		mng.set_debug_names(wasm);
+	}
+
+	mng.set_inline_map(inline_map);
+	mng.set_import_name_list(import_name_list);
+
+	let split_list = config.split_threshold.map_or_else(Vec::new, |threshold| {
+		split::visit(func, threshold, |block| can_split(block, &mng))
+	});
+
+	mng.set_split_list(&split_list);
 
-	wasm.name_section()
-		.get(&index)
-		.map_or_else(|| Ok(()), |name| write!(w, "--[[ {name} ]] "))
+	let split_list = split_list
+		.into_iter()
+		.map(|block| build_split(block, &mut mng))
+		.collect();
+
+	let name = wasm
+		.name_section()
+		.get(&index.try_into().unwrap())
+		.map(ToString::to_string);
+
+	Definition {
+		index,
+		name,
+		split_list,
+		function: build_func(func, &mut mng),
+	}
 }
 
-fn write_func_list(wasm: &Module, func_list: &[FuncData], w: &mut dyn Write) -> Result<()> {
-	let offset = wasm.import_count(External::Func);
+fn write_func_list(
+	wasm: &Module,
+	func_list: &[(usize, FuncData)],
+	config: &Config,
+	import_name_list: &[String],
+	rewrite: &mut dyn FnMut(&mut Definition),
+	w: &mut dyn Write,
+) -> Result<()> {
+	let inline_map = match config.inline_threshold {
+		Some(threshold) => inline::visit(func_list, find_indirect_list(wasm)?, threshold),
+		None => BTreeMap::new(),
+	};
 
-	func_list.iter().enumerate().try_for_each(|(i, v)| {
-		let index = (offset + i).try_into().unwrap();
+	for (index, func) in func_list {
+		let mut definition =
+			build_definition(wasm, func, *index, config, &inline_map, import_name_list);
 
-		write_func_start(wasm, index, w)?;
+		rewrite(&mut definition);
 
-		v.write(&mut Manager::function(v), w)
-	})
+		if config.minify {
+			minify::shorten(&mut definition);
+		}
+
+		definition.write(w)?;
+	}
+
+	Ok(())
 }
 
 fn write_module_start(
 	wasm: &Module,
 	type_info: &TypeInfo,
+	config: &Config,
+	func_list: &[(usize, FuncData)],
 	mem_set: &BTreeSet<usize>,
+	import_name_list: &[String],
 	w: &mut dyn Write,
 ) -> Result<()> {
 	writeln!(w, "local function run_init_code()")?;
+	write_signature_list(wasm, type_info, func_list, w)?;
 	write_table_list(wasm, w)?;
 	write_memory_list(wasm, w)?;
-	write_global_list(wasm, type_info, w)?;
-	write_element_list(wasm.element_section(), type_info, w)?;
-	write_data_list(wasm.data_section(), type_info, w)?;
+	write_global_list(wasm, type_info, config, w)?;
+	write_element_list(wasm.element_section(), type_info, config, w)?;
+	write_data_list(wasm.data_section(), type_info, config, w)?;
 	writeln!(w, "end")?;
 
-	writeln!(w, "return function(wasm)")?;
-	write_import_list(wasm.import_section(), w)?;
+	writeln!(w, "return function(imports)")?;
+	write_import_list(wasm.import_section(), type_info, import_name_list, w)?;
 	writeln!(w, "\trun_init_code()")?;
 
 	for mem in mem_set {
@@ -302,36 +686,289 @@ fn write_module_start(
 	}
 
 	writeln!(w, "\treturn {{")?;
-	write_export_list(wasm.export_section(), w)?;
+	write_export_list(&find_export_list(wasm, config)?, type_info, config, w)?;
 	writeln!(w, "\t}}")?;
 	writeln!(w, "end")
 }
 
 /// # Errors
-/// Returns `Err` if writing to `Write` failed.
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
 pub fn from_inst_list(code: &[Operator], type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	let ast = Factory::from_type_info(type_info).create_anonymous(code);
+	let ast = Factory::from_type_info(type_info).create_anonymous(code)?;
+	let function = build_func(&ast, &mut Manager::function(&ast, 0, &Config::default()));
+
+	function.write(0, w)?;
 
-	ast.write(&mut Manager::function(&ast), w)
+	writeln!(w)
 }
 
+fn write_runtime_require(path: &str, w: &mut dyn Write) -> Result<()> {
+	writeln!(w, "local rt_runtime = require({path})")?;
+
+	for name in crate::RUNTIME_NAME_LIST {
+		writeln!(w, "local {name} = rt_runtime.{name}")?;
+	}
+
+	writeln!(w)
+}
+
+/// Writes `RUNTIME` as a module returning everything it defines, to be
+/// loaded by output using [`Config::runtime_require`].
+///
 /// # Errors
 /// Returns `Err` if writing to `Write` failed.
-pub fn from_module_typed(wasm: &Module, type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
-	let func_list = build_func_list(wasm, type_info);
+pub fn write_runtime_module(w: &mut dyn Write) -> Result<()> {
+	writeln!(w, "{}", crate::RUNTIME)?;
+	writeln!(w, "return {{")?;
+
+	for name in crate::RUNTIME_NAME_LIST {
+		writeln!(w, "\t{name} = {name},")?;
+	}
+
+	writeln!(w, "}}")
+}
+
+fn write_module(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	config: &Config,
+	rewrite: &mut dyn FnMut(&mut Definition),
+	w: &mut dyn Write,
+) -> Result<()> {
+	if let Some(path) = &config.runtime_require {
+		write_runtime_require(path, w)?;
+	} else if config.inline_runtime {
+		writeln!(w, "{}", crate::RUNTIME)?;
+	}
+
+	let mut func_list = build_func_list(wasm, type_info)?;
+	let export_list = find_export_list(wasm, config)?;
+
+	if config.strip_unreachable || config.export_filter.is_some() {
+		let live_set = reachable::visit(&func_list, find_root_list(wasm, &export_list)?);
+
+		func_list.retain(|v| live_set.contains(&v.0));
+	}
+
 	let mem_set = write_localize_used(wasm, &func_list, w)?;
+	let import_name_list = find_import_name_list(wasm, config);
 
+	for name in &import_name_list {
+		writeln!(w, "local {name}")?;
+	}
+
+	write_named_array("TYPE_LIST", wasm.type_section().len(), w)?;
 	write_named_array("FUNC_LIST", wasm.function_space(), w)?;
 	write_named_array("TABLE_LIST", wasm.table_space(), w)?;
 	write_named_array("MEMORY_LIST", wasm.memory_space(), w)?;
 	write_named_array("GLOBAL_LIST", wasm.global_space(), w)?;
+	write_named_array("ELEM_LIST", wasm.element_space(), w)?;
+	write_named_array("DATA_LIST", wasm.data_space(), w)?;
+
+	write_func_list(wasm, &func_list, config, &import_name_list, rewrite, w)?;
+	write_module_start(
+		wasm,
+		type_info,
+		config,
+		&func_list,
+		&mem_set,
+		&import_name_list,
+		w,
+	)
+}
 
-	write_func_list(wasm, &func_list, w)?;
-	write_module_start(wasm, type_info, &mem_set, w)
+/// # Errors
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
+pub fn from_module_with_config(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<()> {
+	from_module_with_rewrite(wasm, type_info, config, &mut |_| {}, w)
 }
 
+/// Like [`from_module_with_config`], but hands the syntax tree of every
+/// function to `rewrite` before it is written, in the order they appear.
+///
 /// # Errors
-/// Returns `Err` if writing to `Write` failed.
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
+pub fn from_module_with_rewrite(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	config: &Config,
+	rewrite: &mut dyn FnMut(&mut Definition),
+	w: &mut dyn Write,
+) -> Result<()> {
+	if config.minify {
+		let mut w = Minify::new(w);
+
+		write_module(wasm, type_info, config, rewrite, &mut w)?;
+
+		return w.finish();
+	}
+
+	match config.indentation {
+		Indentation::Tab => write_module(wasm, type_info, config, rewrite, w),
+		Indentation::Space(width) => write_module(
+			wasm,
+			type_info,
+			config,
+			rewrite,
+			&mut Reindent::new(w, width),
+		),
+	}
+}
+
+/// Builds the syntax tree of the `FUNC_LIST[index] = function(...) end`
+/// assignment of one defined function, where `index` counts imported
+/// functions first.
+///
+/// # Errors
+/// Returns `Err` if `index` does not refer to a function with a body or
+/// the code contains an instruction that cannot be translated.
+pub fn build_function(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	index: usize,
+	config: &Config,
+) -> Result<Definition> {
+	let offset = wasm.import_count(External::Func);
+	let Some(body) = index
+		.checked_sub(offset)
+		.and_then(|i| wasm.code_section().get(i))
+	else {
+		let message = format!("function {index} has no body to translate");
+
+		return Err(std::io::Error::new(ErrorKind::InvalidInput, message));
+	};
+
+	let func = Factory::from_type_info(type_info).create_indexed(index, body)?;
+
+	// The other bodies are not built here, so nothing is inlined
+	let inline_map = BTreeMap::new();
+	let import_name_list = find_import_name_list(wasm, config);
+
+	Ok(build_definition(
+		wasm,
+		&func,
+		index,
+		config,
+		&inline_map,
+		&import_name_list,
+	))
+}
+
+/// Writes only the `FUNC_LIST[index] = function(...) end` assignment of one
+/// defined function, where `index` counts imported functions first. The
+/// caller is expected to provide `FUNC_LIST`, `GLOBAL_LIST`, `TABLE_LIST`,
+/// the `memory_at_*` locals, any locals named by [`Config::import_name`]
+/// and the runtime around it.
+///
+/// # Errors
+/// Returns `Err` if writing to `Write` failed, `index` does not refer to a
+/// function with a body or the code contains an instruction that cannot be
+/// translated.
+pub fn from_function(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	index: usize,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<()> {
+	let mut definition = build_function(wasm, type_info, index, config)?;
+
+	if config.minify {
+		let mut w = Minify::new(w);
+
+		minify::shorten(&mut definition);
+		definition.write(&mut w)?;
+
+		return w.finish();
+	}
+
+	match config.indentation {
+		Indentation::Tab => definition.write(w),
+		Indentation::Space(width) => definition.write(&mut Reindent::new(w, width)),
+	}
+}
+
+/// # Errors
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
+pub fn from_module_typed(wasm: &Module, type_info: &TypeInfo, w: &mut dyn Write) -> Result<()> {
+	from_module_with_config(wasm, type_info, &Config::default(), w)
+}
+
+/// Like [`from_module_with_config`], but also returns the [`Manifest`] of
+/// the module so a host can be built against it without parsing it again.
+///
+/// # Errors
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
+pub fn from_module_with_manifest(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<Manifest> {
+	from_module_with_config(wasm, type_info, config, w)?;
+
+	Ok(Manifest::from_module(wasm, type_info, config))
+}
+
+/// Like [`from_module_typed`], but also returns the [`Manifest`] of the
+/// module so a host can be built against it without parsing it again.
+///
+/// # Errors
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
+pub fn from_module_typed_with_manifest(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	w: &mut dyn Write,
+) -> Result<Manifest> {
+	from_module_with_manifest(wasm, type_info, &Config::default(), w)
+}
+
+/// Like [`from_module_with_config`], but also returns every place where
+/// the output only approximates WebAssembly, so a build can be audited.
+///
+/// # Errors
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
+pub fn from_module_with_diagnostics(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	config: &Config,
+	w: &mut dyn Write,
+) -> Result<Vec<Diagnostic>> {
+	from_module_with_config(wasm, type_info, config, w)?;
+
+	diagnostic::from_module(wasm, config)
+}
+
+/// Like [`from_module_typed`], but also returns every place where the
+/// output only approximates WebAssembly, so a build can be audited.
+///
+/// # Errors
+/// Returns `Err` if writing to `Write` failed or the code contains an
+/// instruction that cannot be translated.
+pub fn from_module_typed_diagnostics(
+	wasm: &Module,
+	type_info: &TypeInfo,
+	w: &mut dyn Write,
+) -> Result<Vec<Diagnostic>> {
+	from_module_with_diagnostics(wasm, type_info, &Config::default(), w)
+}
+
+/// # Errors
+/// Returns `Err` if writing to `Write` failed, the code contains an
+/// instruction that cannot be translated, or the code is structurally
+/// malformed in a way validation would have caught.
 pub fn from_module_untyped(wasm: &Module, w: &mut dyn Write) -> Result<()> {
 	let type_info = TypeInfo::from_module(wasm);
 
@@ -9,7 +9,9 @@ do
 		local old = assert(rt.load[name], "Missing load function " .. name)
 
 		rt.load[name] = function(memory, addr)
-			assert(is_valid_address(memory, addr, size), "Invalid memory read")
+			if not is_valid_address(memory, addr, size) then
+				error(rt.trap.out_of_bounds_memory_access)
+			end
 
 			return old(memory, addr)
 		end
@@ -19,7 +21,9 @@ do
 		local old = assert(rt.store[name], "Missing store function " .. name)
 
 		rt.store[name] = function(memory, addr, value)
-			assert(is_valid_address(memory, addr, size), "Invalid memory write")
+			if not is_valid_address(memory, addr, size) then
+				error(rt.trap.out_of_bounds_memory_access)
+			end
 
 			return old(memory, addr, value)
 		end
@@ -29,7 +33,9 @@ do
 		local old = rt.store.string
 
 		function rt.store.string(memory, addr, data, len)
-			assert(is_valid_address(memory, addr, #data), "Invalid memory write")
+			if not is_valid_address(memory, addr, #data) then
+				error(rt.trap.out_of_bounds_memory_access)
+			end
 
 			return old(memory, addr, data, len)
 		end
@@ -49,6 +55,7 @@ do
 	load_checked("i64", 8)
 	load_checked("f32", 4)
 	load_checked("f64", 8)
+	load_checked("v128", 16)
 
 	store_checked("i32_n8", 1)
 	store_checked("i32_n16", 2)
@@ -59,6 +66,7 @@ do
 	store_checked("i64", 8)
 	store_checked("f32", 4)
 	store_checked("f64", 8)
+	store_checked("v128", 16)
 end
 
 local loaded = {}
@@ -105,17 +113,36 @@ local function raw_invoke(func, ...)
 	return func(...)
 end
 
-local function assert_trap(func, ...)
-	if pcall(func, ...) then
+-- Like the reference interpreter, the expected message only has to be
+-- a prefix of the one the trap carries
+local function is_trap_of(err, message)
+	return type(err) == "table"
+		and rt.trap[err.kind] == err
+		and string.sub(err.message, 1, #message) == message
+end
+
+local function assert_trap(message, func, ...)
+	local ok, err = pcall(func, ...)
+
+	if ok then
 		local trace = debug.traceback("Failed to trap", 2)
 
+		if TRAP_IS_FATAL then
+			error(trace, 0)
+		end
+
 		io.stderr:write(trace, "\n")
+	elseif TRAP_IS_FATAL and not is_trap_of(err, message) then
+		local reason = string.format("Trapped with `%s` instead of `%s`", tostring(err), message)
+
+		error(debug.traceback(reason, 2), 0)
 	end
 end
 
+-- Expected null references leave holes, so the count is passed as `n`
 local function assert_return(data, wanted)
-	for i, v in ipairs(wanted) do
-		assert_eq(data[i], v, 2)
+	for i = 1, wanted.n do
+		assert_eq(data[i], wanted[i], 2)
 	end
 end
 
@@ -140,6 +167,6 @@ linked.spectest = {
 		global_i32 = { value = 666 },
 		global_i64 = { value = 666LL },
 	},
-	table_list = { table = { data = {} } },
+	table_list = { table = { min = 10, max = 20, data = {} } },
 	memory_list = { memory = rt.allocator.new(1, 2) },
 }
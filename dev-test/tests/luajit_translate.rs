@@ -24,6 +24,8 @@ impl LuaJIT {
 			WastArg::Core(WastArgCore::I64(v)) => write!(w, "{v}LL"),
 			WastArg::Core(WastArgCore::F32(v)) => target::write_f32(f32::from_bits(v.bits), w),
 			WastArg::Core(WastArgCore::F64(v)) => target::write_f64(f64::from_bits(v.bits), w),
+			WastArg::Core(WastArgCore::RefNull(_)) => write!(w, "nil"),
+			WastArg::Core(WastArgCore::RefExtern(v)) => target::write_extern(*v, w),
 			_ => panic!("Unsupported expression"),
 		}
 	}
@@ -34,22 +36,27 @@ impl LuaJIT {
 			WastRet::Core(WastRetCore::I64(v)) => write!(w, "{v}LL"),
 			WastRet::Core(WastRetCore::F32(v)) => target::write_f32_nan(v, w),
 			WastRet::Core(WastRetCore::F64(v)) => target::write_f64_nan(v, w),
+			WastRet::Core(WastRetCore::RefNull(_)) => write!(w, "nil"),
+			WastRet::Core(WastRetCore::RefExtern(v)) => target::write_extern(*v, w),
 			_ => panic!("Unsupported expression"),
 		}
 	}
 
-	fn write_call_of(handler: &str, data: &WastInvoke, w: &mut dyn Write) -> Result<()> {
+	fn write_callee(data: &WastInvoke, w: &mut dyn Write) -> Result<()> {
 		let name = get_name_from_id(data.module);
-		let func = data.name;
+		let func = data.name.as_bytes().escape_ascii();
 
-		write!(w, "{handler}(")?;
 		write!(w, r#"loaded["{name}"].func_list["{func}"]"#)?;
 
 		data.args.iter().try_for_each(|v| {
 			write!(w, ", ")?;
 			Self::write_arg(v, w)
-		})?;
+		})
+	}
 
+	fn write_call_of(handler: &str, data: &WastInvoke, w: &mut dyn Write) -> Result<()> {
+		write!(w, "{handler}(")?;
+		Self::write_callee(data, w)?;
 		write!(w, ")")
 	}
 }
@@ -60,6 +67,8 @@ impl Target for LuaJIT {
 	}
 
 	fn write_register(post: &str, pre: &str, w: &mut dyn Write) -> Result<()> {
+		let post = post.as_bytes().escape_ascii();
+
 		writeln!(w, r#"linked["{post}"] = loaded["{pre}"]"#)
 	}
 
@@ -68,11 +77,14 @@ impl Target for LuaJIT {
 		writeln!(w)
 	}
 
-	fn write_assert_trap(data: &mut WastExecute, w: &mut dyn Write) -> Result<()> {
+	fn write_assert_trap(data: &mut WastExecute, message: &str, w: &mut dyn Write) -> Result<()> {
+		let message = message.as_bytes().escape_ascii();
+
 		match data {
 			WastExecute::Invoke(data) => {
-				Self::write_call_of("assert_trap", data, w)?;
-				writeln!(w)
+				write!(w, r#"assert_trap("{message}", "#)?;
+				Self::write_callee(data, w)?;
+				writeln!(w, ")")
 			}
 			WastExecute::Get { module, global } => {
 				let name = get_name_from_id(*module);
@@ -88,7 +100,7 @@ impl Target for LuaJIT {
 				};
 				let data = Module::try_from_data(&bytes).unwrap();
 
-				writeln!(w, "assert_trap((function()")?;
+				writeln!(w, r#"assert_trap("{message}", (function()"#)?;
 				codegen_luajit::from_module_untyped(&data, w)?;
 				writeln!(w, "end)(), linked)")
 			}
@@ -112,7 +124,7 @@ impl Target for LuaJIT {
 					write!(w, ", ")?;
 				}
 
-				writeln!(w, "}})")
+				writeln!(w, "n = {}, }})", result.len())
 			}
 			WastExecute::Get { module, global } => {
 				let name = get_name_from_id(*module);
@@ -231,3 +243,12 @@ fn translate_file(path: PathBuf) {
 
 	LuaJIT::test(name, &source).unwrap();
 }
+
+#[test_generator::test_resources("dev-test/wast/*.wast")]
+fn translate_wast(path: PathBuf) {
+	let path = path.strip_prefix("dev-test/").unwrap();
+	let name = path.file_name().unwrap().to_str().unwrap();
+	let source = std::fs::read_to_string(path).unwrap();
+
+	LuaJIT::test_exact(&format!("wast.{name}"), &source).unwrap();
+}
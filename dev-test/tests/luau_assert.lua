@@ -1,66 +1,3 @@
-do
-	local WASM_PAGE_SIZE = 65536
-
-	local function is_valid_address(memory, addr, size)
-		return addr >= 0 and addr + size <= rt.allocator.size(memory) * WASM_PAGE_SIZE
-	end
-
-	local function load_checked(name, size)
-		local old = assert(rt.load[name], "Missing load function " .. name)
-
-		rt.load[name] = function(memory, addr)
-			assert(is_valid_address(memory, addr, size), "Invalid memory read")
-
-			return old(memory, addr)
-		end
-	end
-
-	local function store_checked(name, size)
-		local old = assert(rt.store[name], "Missing store function " .. name)
-
-		rt.store[name] = function(memory, addr, value)
-			assert(is_valid_address(memory, addr, size), "Invalid memory write")
-
-			return old(memory, addr, value)
-		end
-	end
-
-	do
-		local old = rt.store.string
-
-		function rt.store.string(memory, addr, data, len)
-			assert(is_valid_address(memory, addr, #data), "Invalid memory write")
-
-			return old(memory, addr, data, len)
-		end
-	end
-
-	load_checked("i32_i8", 1)
-	load_checked("i32_u8", 1)
-	load_checked("i32_i16", 2)
-	load_checked("i32_u16", 2)
-	load_checked("i32", 4)
-	load_checked("i64_i8", 1)
-	load_checked("i64_u8", 1)
-	load_checked("i64_i16", 2)
-	load_checked("i64_u16", 2)
-	load_checked("i64_i32", 4)
-	load_checked("i64_u32", 4)
-	load_checked("i64", 8)
-	load_checked("f32", 4)
-	load_checked("f64", 8)
-
-	store_checked("i32_n8", 1)
-	store_checked("i32_n16", 2)
-	store_checked("i32", 4)
-	store_checked("i64_n8", 1)
-	store_checked("i64_n16", 2)
-	store_checked("i64_n32", 4)
-	store_checked("i64", 8)
-	store_checked("f32", 4)
-	store_checked("f64", 8)
-end
-
 local loaded = {}
 local linked = {}
 
@@ -71,7 +8,7 @@ local LUA_INFINITY = math.huge
 
 local function is_number_equal(lhs, rhs)
 	if type(lhs) == "table" and type(rhs) == "table" then
-		return rt.eq.i64(lhs, rhs)
+		return rt_eq_i64(lhs, rhs)
 	elseif type(lhs) ~= "number" or type(rhs) ~= "number" then
 		return false
 	elseif lhs ~= lhs and rhs ~= rhs then
@@ -83,7 +20,7 @@ end
 
 local function to_string(data)
 	if type(data) == "table" then
-		data = rt.convert.f64_i64(data)
+		data = rt_convert_f64_i64(data)
 	end
 
 	return tostring(data)
@@ -117,17 +54,36 @@ local function raw_invoke(func, ...)
 	return func(...)
 end
 
-local function assert_trap(func, ...)
-	if pcall(func, ...) then
+-- Like the reference interpreter, the expected message only has to be
+-- a prefix of the one the trap carries
+local function is_trap_of(err, message)
+	return type(err) == "table"
+		and rt_trap[err.kind] == err
+		and string.sub(err.message, 1, #message) == message
+end
+
+local function assert_trap(message, func, ...)
+	local ok, err = pcall(func, ...)
+
+	if ok then
 		local trace = debug.traceback("Failed to trap", 2)
 
+		if TRAP_IS_FATAL then
+			error(trace, 0)
+		end
+
 		print(trace)
+	elseif TRAP_IS_FATAL and not is_trap_of(err, message) then
+		local reason = string.format("Trapped with `%s` instead of `%s`", tostring(err), message)
+
+		error(debug.traceback(reason, 2), 0)
 	end
 end
 
+-- Expected null references leave holes, so the count is passed as `n`
 local function assert_return(data, wanted)
-	for i, v in wanted do
-		assert_eq(data[i], v, 2)
+	for i = 1, wanted.n do
+		assert_eq(data[i], wanted[i], 2)
 	end
 end
 
@@ -150,8 +106,8 @@ linked.spectest = {
 		global_f32 = { value = 666 },
 		global_f64 = { value = 666 },
 		global_i32 = { value = 666 },
-		global_i64 = { value = rt.i64.from_u32(666, 0) },
+		global_i64 = { value = rt_i64_from_u32(666, 0) },
 	},
-	table_list = { table = { data = {} } },
-	memory_list = { memory = rt.allocator.new(1, 2) },
+	table_list = { table = { min = 10, max = 20, data = {} } },
+	memory_list = { memory = rt_allocator_new(1, 2) },
 }
@@ -1,11 +1,14 @@
 use std::{
 	io::{Result, Write},
+	marker::PhantomData,
 	path::PathBuf,
 };
 
+use codegen_luau::{diagnostic::Approximation, Config, Indentation};
 use wasm_ast::module::{Module, TypeInfo};
 use wast::{
 	core::{WastArgCore, WastRetCore},
+	parser::ParseBuffer,
 	WastArg, WastExecute, WastInvoke, WastRet, Wat,
 };
 
@@ -15,9 +18,57 @@ mod target;
 
 static ASSERTION: &str = include_str!("luau_assert.lua");
 
-struct Luau;
+// Each profile runs the whole suite again with the output of another
+// `Config`, which has to behave exactly like the default one
+trait Profile {
+	fn config() -> Config;
+}
+
+struct Plain;
+
+impl Profile for Plain {
+	fn config() -> Config {
+		Config::default()
+	}
+}
+
+struct Optimized;
+
+impl Profile for Optimized {
+	fn config() -> Config {
+		Config {
+			indentation: Indentation::Space(2),
+			native: true,
+			strip_unreachable: true,
+			fold_constants: true,
+			fold_spills: true,
+			coalesce_locals: true,
+			split_threshold: Some(40),
+			inline_threshold: Some(20),
+			fuel_callback: Some("rt_fuel.consume".into()),
+			..Config::default()
+		}
+	}
+}
+
+struct Minified;
+
+impl Profile for Minified {
+	fn config() -> Config {
+		Config {
+			minify: true,
+			source_map: true,
+			debug_names: true,
+			..Config::default()
+		}
+	}
+}
 
-impl Luau {
+struct Luau<P> {
+	_profile: PhantomData<P>,
+}
+
+impl<P: Profile> Luau<P> {
 	fn write_i32(data: i32, w: &mut dyn Write) -> Result<()> {
 		let data = u32::from_ne_bytes(data.to_ne_bytes());
 
@@ -29,7 +80,7 @@ impl Luau {
 		let data_1 = u32::from_ne_bytes(data[0..4].try_into().unwrap());
 		let data_2 = u32::from_ne_bytes(data[4..8].try_into().unwrap());
 
-		write!(w, "rt.i64.from_u32({data_1}, {data_2})")
+		write!(w, "rt_i64_from_u32({data_1}, {data_2})")
 	}
 
 	fn write_arg(data: &WastArg, w: &mut dyn Write) -> Result<()> {
@@ -38,6 +89,8 @@ impl Luau {
 			WastArg::Core(WastArgCore::I64(v)) => Self::write_i64(*v, w),
 			WastArg::Core(WastArgCore::F32(v)) => target::write_f32(f32::from_bits(v.bits), w),
 			WastArg::Core(WastArgCore::F64(v)) => target::write_f64(f64::from_bits(v.bits), w),
+			WastArg::Core(WastArgCore::RefNull(_)) => write!(w, "nil"),
+			WastArg::Core(WastArgCore::RefExtern(v)) => target::write_extern(*v, w),
 			_ => panic!("Unsupported expression"),
 		}
 	}
@@ -48,32 +101,39 @@ impl Luau {
 			WastRet::Core(WastRetCore::I64(v)) => Self::write_i64(*v, w),
 			WastRet::Core(WastRetCore::F32(v)) => target::write_f32_nan(v, w),
 			WastRet::Core(WastRetCore::F64(v)) => target::write_f64_nan(v, w),
+			WastRet::Core(WastRetCore::RefNull(_)) => write!(w, "nil"),
+			WastRet::Core(WastRetCore::RefExtern(v)) => target::write_extern(*v, w),
 			_ => panic!("Unsupported expression"),
 		}
 	}
 
-	fn write_call_of(handler: &str, data: &WastInvoke, w: &mut dyn Write) -> Result<()> {
+	fn write_callee(data: &WastInvoke, w: &mut dyn Write) -> Result<()> {
 		let name = get_name_from_id(data.module);
-		let func = data.name;
+		let func = data.name.as_bytes().escape_ascii();
 
-		write!(w, "{handler}(")?;
 		write!(w, r#"loaded["{name}"].func_list["{func}"]"#)?;
 
 		data.args.iter().try_for_each(|v| {
 			write!(w, ", ")?;
 			Self::write_arg(v, w)
-		})?;
+		})
+	}
 
+	fn write_call_of(handler: &str, data: &WastInvoke, w: &mut dyn Write) -> Result<()> {
+		write!(w, "{handler}(")?;
+		Self::write_callee(data, w)?;
 		write!(w, ")")
 	}
 }
 
-impl Target for Luau {
+impl<P: Profile> Target for Luau<P> {
 	fn executable() -> String {
 		std::env::var("LUAU_PATH").unwrap_or_else(|_| "luau".to_string())
 	}
 
 	fn write_register(post: &str, pre: &str, w: &mut dyn Write) -> Result<()> {
+		let post = post.as_bytes().escape_ascii();
+
 		writeln!(w, r#"linked["{post}"] = loaded["{pre}"]"#)
 	}
 
@@ -82,11 +142,14 @@ impl Target for Luau {
 		writeln!(w)
 	}
 
-	fn write_assert_trap(data: &mut WastExecute, w: &mut dyn Write) -> Result<()> {
+	fn write_assert_trap(data: &mut WastExecute, message: &str, w: &mut dyn Write) -> Result<()> {
+		let message = message.as_bytes().escape_ascii();
+
 		match data {
 			WastExecute::Invoke(data) => {
-				Self::write_call_of("assert_trap", data, w)?;
-				writeln!(w)
+				write!(w, r#"assert_trap("{message}", "#)?;
+				Self::write_callee(data, w)?;
+				writeln!(w, ")")
 			}
 			WastExecute::Get { module, global } => {
 				let name = get_name_from_id(*module);
@@ -101,9 +164,10 @@ impl Target for Luau {
 					Wat::Component(_) => unimplemented!(),
 				};
 				let data = Module::try_from_data(&bytes).unwrap();
+				let type_info = TypeInfo::from_module(&data);
 
-				writeln!(w, "assert_trap((function()")?;
-				codegen_luau::from_module_untyped(&data, w)?;
+				writeln!(w, r#"assert_trap("{message}", (function()"#)?;
+				codegen_luau::from_module_with_config(&data, &type_info, &P::config(), w)?;
 				writeln!(w, "end)(), linked)")
 			}
 		}
@@ -126,7 +190,7 @@ impl Target for Luau {
 					write!(w, ", ")?;
 				}
 
-				writeln!(w, "}})")
+				writeln!(w, "n = {}, }})", result.len())
 			}
 			WastExecute::Get { module, global } => {
 				let name = get_name_from_id(*module);
@@ -157,7 +221,7 @@ impl Target for Luau {
 		let type_info = TypeInfo::from_module(data);
 
 		writeln!(w, r#"loaded["temp"] = (function()"#)?;
-		codegen_luau::from_module_typed(data, &type_info, w)?;
+		codegen_luau::from_module_with_config(data, &type_info, &P::config(), w)?;
 		writeln!(w, "end)()(linked)")?;
 
 		if let Some(name) = name {
@@ -240,5 +304,445 @@ fn translate_file(path: PathBuf) {
 
 	let source = std::fs::read_to_string(path).unwrap();
 
-	Luau::test(name, &source).unwrap();
+	Luau::<Plain>::test(name, &source).unwrap();
+	Luau::<Optimized>::test(&format!("optimized.{name}"), &source).unwrap();
+	Luau::<Minified>::test(&format!("minified.{name}"), &source).unwrap();
+}
+
+#[test_generator::test_resources("dev-test/wast/*.wast")]
+fn translate_wast(path: PathBuf) {
+	let path = path.strip_prefix("dev-test/").unwrap();
+	let name = path.file_name().unwrap().to_str().unwrap();
+	let source = std::fs::read_to_string(path).unwrap();
+
+	let name = format!("wast.{name}");
+
+	Luau::<Plain>::test_exact(&name, &source).unwrap();
+	Luau::<Optimized>::test_exact(&format!("optimized.{name}"), &source).unwrap();
+	Luau::<Minified>::test_exact(&format!("minified.{name}"), &source).unwrap();
+}
+
+fn encode_module(source: &str) -> Vec<u8> {
+	let lexed = ParseBuffer::new(source).expect("Failed to tokenize");
+
+	match wast::parser::parse(&lexed).unwrap() {
+		Wat::Module(mut ast) => ast.encode().unwrap(),
+		Wat::Component(_) => unimplemented!(),
+	}
+}
+
+fn write_temp(name: &str, data: &[u8]) -> Result<PathBuf> {
+	let temp = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+		.join(name)
+		.with_extension("lua");
+
+	std::fs::write(&temp, data)?;
+
+	Ok(temp)
+}
+
+// Runs `setup`, instantiates `source` as `loaded["temp"]` and runs
+// `script` after it, for checks that need the host to step in between
+// calls
+fn run_script(name: &str, source: &str, config: &Config, setup: &str, script: &str) -> Result<()> {
+	let bytes = encode_module(source);
+	let wasm = Module::try_from_data(&bytes).unwrap();
+	let type_info = TypeInfo::from_module(&wasm);
+	let mut data = Vec::new();
+
+	Luau::<Plain>::write_runtime(&mut data)?;
+
+	writeln!(data, "{setup}")?;
+	writeln!(data, r#"loaded["temp"] = (function()"#)?;
+	codegen_luau::from_module_with_config(&wasm, &type_info, config, &mut data)?;
+	writeln!(data, "end)()(linked)")?;
+	writeln!(data, "{script}")?;
+
+	Luau::<Plain>::run_command(&write_temp(name, &data)?)
+}
+
+#[test]
+fn fuel_runs_out() {
+	let config = Config {
+		fuel_callback: Some("rt_fuel.consume".into()),
+		..Config::default()
+	};
+
+	run_script(
+		"fuel_runs_out",
+		r#"(module
+			(func (export "spin") (loop (br 0)))
+			(func (export "count") (param i32) (result i32) (local i32)
+				(block (loop
+					(br_if 1 (i32.eqz (local.get 0)))
+					(local.set 1 (i32.add (local.get 1) (i32.const 1)))
+					(local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+					(br 0)))
+				(local.get 1))
+		)"#,
+		&config,
+		"",
+		r#"
+			local func_list = loaded["temp"].func_list
+
+			rt_fuel.remaining = 1000
+
+			local ok, trap = pcall(func_list.spin)
+
+			assert(not ok and trap == rt_trap.out_of_fuel, "the loop never ran out")
+			assert(rt_fuel.remaining < 0)
+
+			rt_fuel.remaining = 10000
+
+			assert(func_list.count(100) == 100)
+			assert(rt_fuel.remaining > 0 and rt_fuel.remaining < 10000)
+
+			rt_fuel.remaining = 50
+			ok, trap = pcall(func_list.count, 100)
+
+			assert(not ok and trap == rt_trap.out_of_fuel, "the budget was not charged per iteration")
+		"#,
+	)
+	.unwrap();
+}
+
+// Sets 230 locals in one block of calls, which has to load whatever size
+// of block gets moved out of it
+#[test]
+fn split_many_locals() {
+	let mut source = String::from(
+		r#"(module
+			(func $leaf (param i32) (result i32) (i32.mul (local.get 0) (i32.const 3)))
+			(func (export "big") (param i32) (result i32)"#,
+	);
+
+	source.push_str(&" (local i32)".repeat(230));
+	source.push_str(" (block");
+
+	for i in 1..=230 {
+		source.push_str(&format!(
+			" (local.set {i} (call $leaf (i32.add (local.get 0) (i32.const {i}))))"
+		));
+	}
+
+	source.push(')');
+
+	for i in 2..=230 {
+		source.push_str(&format!(
+			" (local.set 1 (i32.add (local.get 1) (local.get {i})))"
+		));
+	}
+
+	source.push_str(" (local.get 1)))");
+
+	for threshold in [4, 40, 100] {
+		let config = Config {
+			split_threshold: Some(threshold),
+			..Config::default()
+		};
+
+		run_script(
+			&format!("split_many_locals.{threshold}"),
+			&source,
+			&config,
+			"",
+			r#"assert(loaded["temp"].func_list.big(1) == 80385)"#,
+		)
+		.unwrap();
+	}
+}
+
+// Output using `runtime_require` sees nothing but the names it binds from
+// the module, so every one of them has to be defined by the runtime
+#[test]
+fn runtime_module_names() {
+	let defined: Vec<_> = codegen_luau::RUNTIME
+		.lines()
+		.filter_map(|line| line.strip_prefix("local function rt_"))
+		.chain(
+			codegen_luau::RUNTIME
+				.lines()
+				.filter_map(|line| line.strip_prefix("local rt_")),
+		)
+		.map(|rest| {
+			let end = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_');
+
+			format!("rt_{}", &rest[..end.unwrap_or(rest.len())])
+		})
+		.collect();
+
+	for name in &defined {
+		assert!(
+			codegen_luau::RUNTIME_NAME_LIST.contains(&name.as_str()),
+			"`{name}` is missing from `RUNTIME_NAME_LIST`"
+		);
+	}
+
+	let bytes = encode_module(
+		r#"(module
+			(memory 1)
+			(func (export "add64") (param i64 i64) (result i64) (i64.add (local.get 0) (local.get 1)))
+			(func (export "store") (param i32 i32) (i32.store (local.get 0) (local.get 1)))
+			(func (export "load") (param i32) (result i32) (i32.load (local.get 0)))
+		)"#,
+	);
+
+	let wasm = Module::try_from_data(&bytes).unwrap();
+	let type_info = TypeInfo::from_module(&wasm);
+	let config = Config {
+		runtime_require: Some(r#""runtime""#.into()),
+		..Config::default()
+	};
+
+	let mut data = Vec::new();
+
+	writeln!(data, "local runtime = (function()").unwrap();
+	codegen_luau::write_runtime_module(&mut data).unwrap();
+	writeln!(data, "end)()").unwrap();
+
+	for name in codegen_luau::RUNTIME_NAME_LIST {
+		writeln!(
+			data,
+			r#"assert(runtime.{name} ~= nil, "`{name}` is not defined")"#
+		)
+		.unwrap();
+	}
+
+	writeln!(data, "local function require(path)").unwrap();
+	writeln!(data, r#"	assert(path == "runtime")"#).unwrap();
+	writeln!(data, "	return runtime").unwrap();
+	writeln!(data, "end").unwrap();
+	writeln!(data, "local instance = (function()").unwrap();
+	codegen_luau::from_module_with_config(&wasm, &type_info, &config, &mut data).unwrap();
+	writeln!(data, "end)()({{}})").unwrap();
+	writeln!(
+		data,
+		r#"
+		local func_list = instance.func_list
+		local sum = func_list.add64(runtime.rt_i64_from_u32(0xFFFFFFFF, 0), runtime.rt_i64_ONE)
+
+		assert(runtime.rt_eq_i64(sum, runtime.rt_i64_from_u32(0, 1)))
+
+		func_list.store(4, 0x12345678)
+
+		assert(func_list.load(4) == 0x12345678)
+		assert(not pcall(func_list.load, 65536))
+		"#
+	)
+	.unwrap();
+
+	let temp = write_temp("runtime_module_names", &data).unwrap();
+
+	Luau::<Plain>::run_command(&temp).unwrap();
+}
+
+// Globals stay shared with the host, whether exported by the module or
+// imported from a table holding plain values
+#[test]
+fn global_shared_with_host() {
+	run_script(
+		"global_shared_with_host",
+		r#"(module
+			(import "env" "g" (global $g (mut i32)))
+			(global $h (export "h") (mut i64) (i64.const 1))
+			(func (export "get_g") (result i32) (global.get $g))
+			(func (export "set_g") (param i32) (global.set $g (local.get 0)))
+			(func (export "get_h") (result i64) (global.get $h))
+			(func (export "set_h") (param i64) (global.set $h (local.get 0)))
+		)"#,
+		&Config::default(),
+		"linked.env = { g = 5 }",
+		r#"
+			local instance = loaded["temp"]
+			local func_list = instance.func_list
+
+			assert(func_list.get_g() == 5)
+
+			linked.env.g = 6
+
+			assert(func_list.get_g() == 6)
+
+			func_list.set_g(7)
+
+			assert(linked.env.g == 7)
+
+			local h = instance.global_list.h
+
+			assert(rt_eq_i64(func_list.get_h(), rt_i64_ONE))
+
+			h.value = rt_i64_from_u32(0xFFFFFFFF, 7)
+
+			assert(rt_eq_i64(func_list.get_h(), rt_i64_from_u32(0xFFFFFFFF, 7)))
+
+			func_list.set_h(rt_i64_from_u32(3, 0))
+
+			assert(rt_eq_i64(h.value, rt_i64_from_u32(3, 0)))
+		"#,
+	)
+	.unwrap();
+}
+
+// Hosts can pass i32 arguments as signed numbers, which the generated code
+// never produces itself
+#[test]
+fn mul_signed_host_values() {
+	run_script(
+		"mul_signed_host_values",
+		r#"(module
+			(func (export "mul32") (param i32 i32) (result i32) (i32.mul (local.get 0) (local.get 1)))
+		)"#,
+		&Config::default(),
+		"",
+		r#"
+			local mul32 = loaded["temp"].func_list.mul32
+
+			assert(mul32(-0x7FFFFFFF, 0x7FFFFFFF) == 0xFFFFFFFF)
+			assert(mul32(-0x80000000, 0x7FFFFFFF) == 0x80000000)
+			assert(mul32(-3, 0xFFFFFFFF) == 3)
+			assert(mul32(-1, -1) == 1)
+		"#,
+	)
+	.unwrap();
+}
+
+// The manifest describes the output as written under the `Config` it was
+// written with
+#[test]
+fn manifest_follows_config() {
+	let bytes = encode_module(
+		r#"(module
+			(func (export "a") (param f64) (result f64) (f64.add (local.get 0) (local.get 0)))
+			(func (export "b") (param f64) (result f64) (f64.mul (local.get 0) (local.get 0)))
+		)"#,
+	);
+
+	let wasm = Module::try_from_data(&bytes).unwrap();
+	let type_info = TypeInfo::from_module(&wasm);
+	let config = Config {
+		export_filter: Some(vec!["b".into()]),
+		..Config::default()
+	};
+
+	let mut data = Vec::new();
+	let manifest =
+		codegen_luau::from_module_with_manifest(&wasm, &type_info, &config, &mut data).unwrap();
+	let name_list: Vec<_> = manifest
+		.export_list
+		.iter()
+		.map(|v| v.name.as_str())
+		.collect();
+
+	assert_eq!(name_list, ["b"]);
+}
+
+// Hosts can provide a memory of their own made of just `max` and `data`
+#[test]
+fn host_memory_pages() {
+	run_script(
+		"host_memory_pages",
+		r#"(module
+			(import "env" "memory" (memory 1 4))
+			(func (export "size") (result i32) (memory.size))
+			(func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+			(func (export "load") (param i32) (result i32) (i32.load8_u (local.get 0)))
+		)"#,
+		&Config::default(),
+		"linked.env = { memory = { max = 4, data = buffer.create(65536) } }",
+		r#"
+			local func_list = loaded["temp"].func_list
+			local memory = linked.env.memory
+
+			buffer.writeu8(memory.data, 65535, 9)
+
+			assert(func_list.size() == 1)
+			assert(func_list.load(65535) == 9)
+			assert(func_list.grow(2) == 1)
+			assert(func_list.size() == 3)
+			assert(memory.min == 3)
+			assert(buffer.len(memory.data) == 3 * 65536)
+			assert(func_list.load(65535) == 9)
+			assert(func_list.grow(2) == 0xFFFFFFFF)
+			assert(func_list.grow(0) == 3)
+		"#,
+	)
+	.unwrap();
+}
+
+// Diagnostics only list the approximations made under the `Config` the
+// output was written with
+#[test]
+fn diagnostics_follow_config() {
+	let bytes = encode_module(
+		r#"(module
+			(func (export "add") (param f64) (result f64) (f64.add (local.get 0) (local.get 0)))
+		)"#,
+	);
+
+	let wasm = Module::try_from_data(&bytes).unwrap();
+	let type_info = TypeInfo::from_module(&wasm);
+	let config = Config {
+		canonicalize_nan: true,
+		..Config::default()
+	};
+
+	let mut data = Vec::new();
+	let diagnostic_list =
+		codegen_luau::from_module_typed_diagnostics(&wasm, &type_info, &mut data).unwrap();
+
+	assert_eq!(diagnostic_list.len(), 1);
+	assert_eq!(
+		diagnostic_list[0].approximation,
+		Approximation::NonCanonicalNan
+	);
+
+	data.clear();
+
+	let diagnostic_list =
+		codegen_luau::from_module_with_diagnostics(&wasm, &type_info, &config, &mut data).unwrap();
+
+	assert!(diagnostic_list.is_empty());
+}
+
+// Exports left out by the filter are gone from the instance, along with
+// the functions only they call
+#[test]
+fn export_filter_drops_exports() {
+	let source = r#"(module
+		(func $helper (result i32) (i32.const 41))
+		(func (export "keep") (result i32) (i32.const 1))
+		(func (export "drop") (result i32) (i32.add (call $helper) (i32.const 1)))
+	)"#;
+
+	let bytes = encode_module(source);
+	let wasm = Module::try_from_data(&bytes).unwrap();
+	let type_info = TypeInfo::from_module(&wasm);
+	let config = Config {
+		export_filter: Some(vec!["keep".into()]),
+		..Config::default()
+	};
+
+	let mut data = Vec::new();
+
+	codegen_luau::from_module_with_config(&wasm, &type_info, &config, &mut data).unwrap();
+
+	let output = String::from_utf8(data).unwrap();
+
+	assert!(output.contains(r#"["keep"]"#));
+	assert!(!output.contains(r#"["drop"]"#));
+	assert!(!output.contains("FUNC_LIST[0] ="));
+	assert!(!output.contains("FUNC_LIST[2] ="));
+
+	run_script(
+		"export_filter_drops_exports",
+		source,
+		&config,
+		"",
+		r#"
+			local func_list = loaded["temp"].func_list
+
+			assert(func_list.keep() == 1)
+			assert(func_list.drop == nil)
+		"#,
+	)
+	.unwrap();
 }
@@ -0,0 +1,100 @@
+use std::io::{Error, ErrorKind, Result, Write};
+
+use wasm_ast::module::Module;
+use wast::{parser::ParseBuffer, Wat};
+
+type Translate = fn(&Module, &mut dyn Write) -> Result<()>;
+
+static BACKEND_LIST: [(&str, Translate); 3] = [
+	("lua54", codegen_lua54::from_module_untyped),
+	("luajit", codegen_luajit::from_module_untyped),
+	("luau", codegen_luau::from_module_untyped),
+];
+
+fn encode_module(source: &str) -> Vec<u8> {
+	let lexed = ParseBuffer::new(source).expect("Failed to tokenize");
+
+	match wast::parser::parse(&lexed).unwrap() {
+		Wat::Module(mut ast) => ast.encode().unwrap(),
+		Wat::Component(_) => unimplemented!(),
+	}
+}
+
+// Every backend has to turn the code down with an error instead of
+// writing anything broken or panicking
+fn translate_all(bytes: &[u8]) -> Vec<(&'static str, Error)> {
+	let wasm = Module::try_from_data(bytes).unwrap();
+
+	BACKEND_LIST
+		.iter()
+		.map(|&(name, translate)| (name, translate(&wasm, &mut Vec::new()).expect_err(name)))
+		.collect()
+}
+
+#[test]
+fn unsupported_simd_op() {
+	let bytes = encode_module(
+		r#"(module
+			(func (export "shuffle") (param i32) (result i32)
+				(i8x16.extract_lane_u 0
+					(i8x16.shuffle 1 0 3 2 5 4 7 6 9 8 11 10 13 12 15 14
+						(i8x16.splat (local.get 0))
+						(i8x16.splat (i32.const 1)))))
+		)"#,
+	);
+
+	for (name, error) in translate_all(&bytes) {
+		assert_eq!(error.kind(), ErrorKind::Unsupported, "{name}");
+		assert_eq!(
+			error.to_string(),
+			"unsupported SIMD op `I8x16Shuffle`",
+			"{name}"
+		);
+	}
+}
+
+fn assert_malformed(bytes: &[u8], reason: &str) {
+	for (name, error) in translate_all(bytes) {
+		assert_eq!(error.kind(), ErrorKind::InvalidData, "{name}");
+		assert_eq!(
+			error.to_string(),
+			format!("malformed code: {reason}"),
+			"{name}"
+		);
+	}
+}
+
+#[test]
+fn malformed_empty_body() {
+	// The one function's body holds no locals and no code, not even `end`
+	let bytes = [
+		0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, // header
+		0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section
+		0x03, 0x02, 0x01, 0x00, // function section
+		0x0A, 0x03, 0x01, 0x01, 0x00, // code section
+	];
+
+	assert_malformed(&bytes, "function body is empty");
+}
+
+#[test]
+fn malformed_block_type() {
+	let bytes = encode_module(
+		r#"(module
+			(func (block (type 99)))
+		)"#,
+	);
+
+	assert_malformed(&bytes, "block type refers to missing type 99");
+}
+
+#[test]
+fn malformed_branch_depth() {
+	let bytes = encode_module(
+		r#"(module
+			(func (block (br 5)))
+		)"#,
+	);
+
+	assert_malformed(&bytes, "branch depth 5 is past the outermost label");
+}
@@ -0,0 +1,48 @@
+(module
+  (memory 1 1 shared)
+  (func (export "init") (param i64) (i64.store (i32.const 0) (local.get 0)))
+  (func (export "load") (result i64) (i64.atomic.load (i32.const 0)))
+  (func (export "load8") (result i32) (i32.atomic.load8_u (i32.const 0)))
+  (func (export "store16") (param i32) (i32.atomic.store16 (i32.const 2) (local.get 0)))
+  (func (export "add") (param i32) (result i32) (i32.atomic.rmw.add (i32.const 0) (local.get 0)))
+  (func (export "add8") (param i32) (result i32) (i32.atomic.rmw8.add_u (i32.const 0) (local.get 0)))
+  (func (export "sub64") (param i64) (result i64) (i64.atomic.rmw.sub (i32.const 0) (local.get 0)))
+  (func (export "xor32u") (param i64) (result i64) (i64.atomic.rmw32.xor_u (i32.const 4) (local.get 0)))
+  (func (export "xchg") (param i32) (result i32) (i32.atomic.rmw.xchg (i32.const 0) (local.get 0)))
+  (func (export "cas") (param i32 i32) (result i32) (i32.atomic.rmw.cmpxchg (i32.const 0) (local.get 0) (local.get 1)))
+  (func (export "cas8") (param i32 i32) (result i32) (i32.atomic.rmw8.cmpxchg_u (i32.const 0) (local.get 0) (local.get 1)))
+  (func (export "cas64") (param i64 i64) (result i64) (i64.atomic.rmw.cmpxchg (i32.const 0) (local.get 0) (local.get 1)))
+  (func (export "wait") (param i32) (result i32) (memory.atomic.wait32 (i32.const 0) (local.get 0) (i64.const 0)))
+  (func (export "wait64") (param i64) (result i32) (memory.atomic.wait64 (i32.const 0) (local.get 0) (i64.const -1)))
+  (func (export "notify") (result i32) (memory.atomic.notify (i32.const 0) (i32.const 5)))
+  (func (export "fence") (atomic.fence))
+  (func (export "sum") (param i32) (result i32)
+    (i32.add (local.get 0) (i32.atomic.rmw.add (i32.const 0) (i32.const 1))))
+)
+
+(invoke "init" (i64.const 0x1122334455667788))
+(assert_return (invoke "load") (i64.const 0x1122334455667788))
+(assert_return (invoke "load8") (i32.const 0x88))
+(assert_return (invoke "add8" (i32.const 0x80)) (i32.const 0x88))
+(assert_return (invoke "load") (i64.const 0x1122334455667708))
+(assert_return (invoke "add" (i32.const 1)) (i32.const 0x55667708))
+(assert_return (invoke "xchg" (i32.const 7)) (i32.const 0x55667709))
+(assert_return (invoke "cas" (i32.const 6) (i32.const 9)) (i32.const 7))
+(assert_return (invoke "cas" (i32.const 7) (i32.const 9)) (i32.const 7))
+(assert_return (invoke "load") (i64.const 0x1122334400000009))
+(assert_return (invoke "cas8" (i32.const 0x109) (i32.const 0x1ff)) (i32.const 9))
+(assert_return (invoke "load") (i64.const 0x11223344000000ff))
+(assert_return (invoke "sub64" (i64.const 0x100)) (i64.const 0x11223344000000ff))
+(assert_return (invoke "load") (i64.const 0x11223343ffffffff))
+(assert_return (invoke "xor32u" (i64.const 0xffffffff00000001)) (i64.const 0x11223343))
+(assert_return (invoke "load") (i64.const 0x11223342ffffffff))
+(assert_return (invoke "store16"(i32.const 0xabcd)))
+(assert_return (invoke "load") (i64.const 0x11223342abcdffff))
+(assert_return (invoke "cas64" (i64.const 0x11223342abcdffff) (i64.const 5)) (i64.const 0x11223342abcdffff))
+(assert_return (invoke "wait" (i32.const 4)) (i32.const 1))
+(assert_return (invoke "wait" (i32.const 5)) (i32.const 2))
+(assert_return (invoke "wait64" (i64.const 5)) (i32.const 2))
+(assert_return (invoke "notify") (i32.const 0))
+(assert_return (invoke "fence"))
+(assert_return (invoke "sum" (i32.const 10)) (i32.const 15))
+(assert_return (invoke "load") (i64.const 6))
@@ -0,0 +1,24 @@
+(module
+  (type $i (func (result i32)))
+  (type $j (func (result i64)))
+  (type $i2 (func (result i32)))
+  (type $p (func (param i32) (result i32)))
+  (table 4 funcref)
+  (elem (i32.const 0) $fi $fj $fp)
+  (func $fi (type $i2) (i32.const 1))
+  (func $fj (type $j) (i64.const 2))
+  (func $fp (type $p) (local.get 0))
+  (func (export "call_i") (param i32) (result i32) (call_indirect (type $i) (local.get 0)))
+  (func (export "call_p") (param i32) (result i32) (call_indirect (type $p) (i32.const 7) (local.get 0)))
+  (func (export "tail_i") (param i32) (result i32) (return_call_indirect (type $i) (local.get 0)))
+)
+(assert_return (invoke "call_i" (i32.const 0)) (i32.const 1))
+(assert_trap (invoke "call_i" (i32.const 1)) "indirect call type mismatch")
+(assert_trap (invoke "call_i" (i32.const 2)) "indirect call type mismatch")
+(assert_return (invoke "call_p" (i32.const 2)) (i32.const 7))
+(assert_trap (invoke "call_p" (i32.const 0)) "indirect call type mismatch")
+(assert_trap (invoke "call_i" (i32.const 3)) "uninitialized element")
+(assert_trap (invoke "call_i" (i32.const 4)) "undefined element")
+(assert_trap (invoke "call_i" (i32.const -1)) "undefined element")
+(assert_return (invoke "tail_i" (i32.const 0)) (i32.const 1))
+(assert_trap (invoke "tail_i" (i32.const 1)) "indirect call type mismatch")
@@ -0,0 +1,24 @@
+(module
+  (func (export "chain") (result i32)
+    (i32.mul (i32.add (i32.const 0x7FFFFFFF) (i32.const 2)) (i32.sub (i32.const 3) (i32.const 5))))
+  (func (export "chain64") (result i64)
+    (i64.shl (i64.xor (i64.const -1) (i64.const 0xFF)) (i64.const 60)))
+  (func (export "shift") (result i32) (i32.shr_u (i32.const -8) (i32.const 33)))
+  (func (export "rotate") (result i32) (i32.rotl (i32.const 0x80000001) (i32.const 1)))
+  (func (export "compare") (result i32) (i32.lt_s (i32.const -1) (i32.const 0)))
+  (func (export "wrap") (result i32) (i32.wrap_i64 (i64.const 0x1_0000_0005)))
+  (func (export "div_zero") (result i32) (i32.div_s (i32.const 1) (i32.const 0)))
+  (func (export "rem_zero") (result i64) (i64.rem_u (i64.const 1) (i64.const 0)))
+  (func (export "f64") (result f64) (f64.mul (f64.add (f64.const 0.5) (f64.const 0.25)) (f64.const 4)))
+  (func (export "nan") (result i64) (i64.reinterpret_f64 (f64.div (f64.const 0) (f64.const 0))))
+)
+
+(assert_return (invoke "chain") (i32.const -2))
+(assert_return (invoke "chain64") (i64.const 0))
+(assert_return (invoke "shift") (i32.const 0x7FFFFFFC))
+(assert_return (invoke "rotate") (i32.const 3))
+(assert_return (invoke "compare") (i32.const 1))
+(assert_return (invoke "wrap") (i32.const 5))
+(assert_trap (invoke "div_zero") "integer divide by zero")
+(assert_trap (invoke "rem_zero") "integer divide by zero")
+(assert_return (invoke "f64") (f64.const 3))
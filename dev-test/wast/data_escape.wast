@@ -0,0 +1,27 @@
+(module
+  (memory 1)
+  (data (i32.const 0) "\00\01" "1" "\ff\7f\"\\\n9\0a\00")
+  (data $p "\00" "42" "\80\009")
+  (func (export "byte") (param i32) (result i32) (i32.load8_u (local.get 0)))
+  (func (export "init") (param i32 i32 i32) (memory.init $p (local.get 0) (local.get 1) (local.get 2)))
+)
+(assert_return (invoke "byte" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "byte" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "byte" (i32.const 2)) (i32.const 49))
+(assert_return (invoke "byte" (i32.const 3)) (i32.const 255))
+(assert_return (invoke "byte" (i32.const 4)) (i32.const 127))
+(assert_return (invoke "byte" (i32.const 5)) (i32.const 34))
+(assert_return (invoke "byte" (i32.const 6)) (i32.const 92))
+(assert_return (invoke "byte" (i32.const 7)) (i32.const 10))
+(assert_return (invoke "byte" (i32.const 8)) (i32.const 57))
+(assert_return (invoke "byte" (i32.const 9)) (i32.const 10))
+(assert_return (invoke "byte" (i32.const 10)) (i32.const 0))
+(assert_return (invoke "init" (i32.const 100) (i32.const 0) (i32.const 6)))
+(assert_return (invoke "byte" (i32.const 100)) (i32.const 0))
+(assert_return (invoke "byte" (i32.const 101)) (i32.const 52))
+(assert_return (invoke "byte" (i32.const 102)) (i32.const 50))
+(assert_return (invoke "byte" (i32.const 103)) (i32.const 128))
+(assert_return (invoke "byte" (i32.const 104)) (i32.const 0))
+(assert_return (invoke "byte" (i32.const 105)) (i32.const 57))
+(assert_return (invoke "init" (i32.const 65536) (i32.const 6) (i32.const 0)))
+(assert_trap (invoke "init" (i32.const 65535) (i32.const 0) (i32.const 2)) "out of bounds")
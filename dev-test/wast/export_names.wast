@@ -0,0 +1,14 @@
+(module $a
+  (func (export "we\"ird.na\\me") (result i32) (i32.const 7))
+  (func (export "new\nline") (result i32) (i32.const 8))
+  (func (export "ünï") (result i32) (i32.const 9))
+)
+(register "mod.\"x\"" $a)
+(module
+  (import "mod.\"x\"" "we\"ird.na\\me" (func $f (result i32)))
+  (func (export "call") (result i32) (call $f))
+)
+(assert_return (invoke $a "we\"ird.na\\me") (i32.const 7))
+(assert_return (invoke $a "new\nline") (i32.const 8))
+(assert_return (invoke $a "ünï") (i32.const 9))
+(assert_return (invoke "call") (i32.const 7))
@@ -0,0 +1,61 @@
+(module
+  (func (export "min32") (param f32 f32) (result i32) (i32.reinterpret_f32 (f32.min (local.get 0) (local.get 1))))
+  (func (export "max32") (param f32 f32) (result i32) (i32.reinterpret_f32 (f32.max (local.get 0) (local.get 1))))
+  (func (export "cps32") (param f32 f32) (result i32) (i32.reinterpret_f32 (f32.copysign (local.get 0) (local.get 1))))
+  (func (export "min64") (param f64 f64) (result i64) (i64.reinterpret_f64 (f64.min (local.get 0) (local.get 1))))
+  (func (export "max64") (param f64 f64) (result i64) (i64.reinterpret_f64 (f64.max (local.get 0) (local.get 1))))
+  (func (export "cps64") (param f64 f64) (result i64) (i64.reinterpret_f64 (f64.copysign (local.get 0) (local.get 1))))
+  ;; NaN checks work on bit patterns so payloads survive the harness
+  (func (export "min32nan") (param i32 f32) (result i32)
+    (i32.eq (i32.and (i32.reinterpret_f32 (f32.min (f32.reinterpret_i32 (local.get 0)) (local.get 1))) (i32.const 0x7fc00000)) (i32.const 0x7fc00000)))
+  (func (export "max32nan") (param f32 i32) (result i32)
+    (i32.eq (i32.and (i32.reinterpret_f32 (f32.max (local.get 0) (f32.reinterpret_i32 (local.get 1)))) (i32.const 0x7fc00000)) (i32.const 0x7fc00000)))
+  (func (export "min64nan") (param i64 f64) (result i32)
+    (i64.eq (i64.and (i64.reinterpret_f64 (f64.min (f64.reinterpret_i64 (local.get 0)) (local.get 1))) (i64.const 0x7ff8000000000000)) (i64.const 0x7ff8000000000000)))
+  (func (export "max64nan") (param f64 i64) (result i32)
+    (i64.eq (i64.and (i64.reinterpret_f64 (f64.max (local.get 0) (f64.reinterpret_i64 (local.get 1)))) (i64.const 0x7ff8000000000000)) (i64.const 0x7ff8000000000000)))
+  (func (export "cps32nan") (param i32 f32) (result i32) (i32.reinterpret_f32 (f32.copysign (f32.reinterpret_i32 (local.get 0)) (local.get 1))))
+  (func (export "cps32sign") (param f32 i32) (result i32) (i32.reinterpret_f32 (f32.copysign (local.get 0) (f32.reinterpret_i32 (local.get 1)))))
+  (func (export "cps64nan") (param i64 f64) (result i64) (i64.reinterpret_f64 (f64.copysign (f64.reinterpret_i64 (local.get 0)) (local.get 1))))
+  (func (export "cps64sign") (param f64 i64) (result i64) (i64.reinterpret_f64 (f64.copysign (local.get 0) (f64.reinterpret_i64 (local.get 1)))))
+)
+
+(assert_return (invoke "min32" (f32.const -0) (f32.const 0)) (i32.const 0x80000000))
+(assert_return (invoke "min32" (f32.const 0) (f32.const -0)) (i32.const 0x80000000))
+(assert_return (invoke "max32" (f32.const -0) (f32.const 0)) (i32.const 0))
+(assert_return (invoke "max32" (f32.const 0) (f32.const -0)) (i32.const 0))
+(assert_return (invoke "min32" (f32.const -0) (f32.const -0)) (i32.const 0x80000000))
+(assert_return (invoke "max32" (f32.const 0) (f32.const 0)) (i32.const 0))
+(assert_return (invoke "min32" (f32.const 1.5) (f32.const -2)) (i32.const 0xc0000000))
+(assert_return (invoke "max32" (f32.const 1.5) (f32.const -2)) (i32.const 0x3fc00000))
+(assert_return (invoke "min32nan" (i32.const 0x7fc00000) (f32.const 1)) (i32.const 1))
+(assert_return (invoke "min32nan" (i32.const 0xffc00000) (f32.const -inf)) (i32.const 1))
+(assert_return (invoke "min32nan" (i32.const 0x7f800001) (f32.const 0)) (i32.const 1))
+(assert_return (invoke "max32nan" (f32.const 1) (i32.const 0x7fc00000)) (i32.const 1))
+(assert_return (invoke "max32nan" (f32.const inf) (i32.const 0xff800001)) (i32.const 1))
+(assert_return (invoke "cps32" (f32.const 0) (f32.const -0)) (i32.const 0x80000000))
+(assert_return (invoke "cps32" (f32.const -0) (f32.const 0)) (i32.const 0))
+(assert_return (invoke "cps32" (f32.const 3) (f32.const -0)) (i32.const 0xc0400000))
+(assert_return (invoke "cps32" (f32.const -3) (f32.const 0)) (i32.const 0x40400000))
+(assert_return (invoke "cps32nan" (i32.const 0x7fc00000) (f32.const -1)) (i32.const 0xffc00000))
+(assert_return (invoke "cps32nan" (i32.const 0xffc00000) (f32.const 1)) (i32.const 0x7fc00000))
+(assert_return (invoke "cps32sign" (f32.const 2) (i32.const 0xffc00000)) (i32.const 0xc0000000))
+(assert_return (invoke "cps32sign" (f32.const -2) (i32.const 0x7fc00000)) (i32.const 0x40000000))
+
+(assert_return (invoke "min64" (f64.const -0) (f64.const 0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "min64" (f64.const 0) (f64.const -0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "max64" (f64.const -0) (f64.const 0)) (i64.const 0))
+(assert_return (invoke "max64" (f64.const 0) (f64.const -0)) (i64.const 0))
+(assert_return (invoke "min64" (f64.const 1.5) (f64.const -2)) (i64.const 0xc000000000000000))
+(assert_return (invoke "max64" (f64.const 1.5) (f64.const -2)) (i64.const 0x3ff8000000000000))
+(assert_return (invoke "min64nan" (i64.const 0x7ff8000000000000) (f64.const 1)) (i32.const 1))
+(assert_return (invoke "min64nan" (i64.const 0x7ff0000000000001) (f64.const -inf)) (i32.const 1))
+(assert_return (invoke "max64nan" (f64.const 1) (i64.const 0xfff8000000000000)) (i32.const 1))
+(assert_return (invoke "max64nan" (f64.const inf) (i64.const 0x7ff0000000000001)) (i32.const 1))
+(assert_return (invoke "cps64" (f64.const 0) (f64.const -0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "cps64" (f64.const -0) (f64.const 0)) (i64.const 0))
+(assert_return (invoke "cps64" (f64.const 3) (f64.const -0)) (i64.const 0xc008000000000000))
+(assert_return (invoke "cps64nan" (i64.const 0x7ff8000000000000) (f64.const -1)) (i64.const 0xfff8000000000000))
+(assert_return (invoke "cps64nan" (i64.const 0xfff8000000000000) (f64.const 1)) (i64.const 0x7ff8000000000000))
+(assert_return (invoke "cps64sign" (f64.const 2) (i64.const 0xfff8000000000000)) (i64.const 0xc000000000000000))
+(assert_return (invoke "cps64sign" (f64.const -2) (i64.const 0x7ff8000000000000)) (i64.const 0x4000000000000000))
@@ -0,0 +1,72 @@
+(module
+  (memory 1)
+  (global $g (mut i32) (i32.const 0))
+  (func $inc (result i32)
+    (global.set $g (i32.add (global.get $g) (i32.const 1)))
+    (global.get $g))
+  (func $two (result i32 i32) (i32.const 3) (i32.const 4))
+  (func $pair (param i32 i32 i32) (result i32)
+    (i32.add (i32.mul (local.get 0) (i32.const 100)) (i32.add (i32.mul (local.get 1) (i32.const 10)) (local.get 2))))
+
+  (func (export "reset") (global.set $g (i32.const 0)))
+  (func (export "get") (result i32) (global.get $g))
+
+  (func (export "call_then_read") (result i32)
+    (i32.sub (call $inc) (global.get $g)))
+  (func (export "read_then_call") (result i32)
+    (i32.sub (global.get $g) (call $inc)))
+  (func (export "order") (result i32)
+    (call $pair (call $inc) (call $inc) (call $inc)))
+  (func (export "load_store") (param i32) (result i32)
+    (i32.store (i32.const 0) (i32.const 5))
+    (i32.load (i32.const 0))
+    (i32.store (i32.const 0) (local.get 0))
+    (i32.load (i32.const 0))
+    (i32.sub))
+  (func (export "trap_after") (param i32) (result i32)
+    (i32.add (call $inc) (i32.div_s (i32.const 10) (local.get 0))))
+  (func (export "trap_before") (param i32) (result i32)
+    (i32.add (i32.div_s (i32.const 10) (local.get 0)) (call $inc)))
+  (func (export "multi") (result i32)
+    (call $two)
+    (call $pair (i32.const 1)))
+  (func (export "chain") (param i32) (result i32)
+    (i32.mul (i32.add (i32.sub (local.get 0) (i32.const 1)) (i32.const 3)) (call $inc)))
+  (func (export "loop") (param i32) (result i32)
+    (local i32)
+    (block
+      (loop
+        (br_if 1 (i32.eqz (local.get 0)))
+        (local.set 1 (i32.add (local.get 1) (call $inc)))
+        (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+        (br 0)))
+    (local.get 1))
+  (func (export "select") (param i32) (result i32)
+    (select (call $inc) (i32.const 50) (local.get 0)))
+  (func (export "tee") (param i32) (result i32)
+    (local i32)
+    (i32.add (local.tee 1 (call $inc)) (i32.mul (local.get 1) (i32.const 10))))
+)
+
+(assert_return (invoke "call_then_read") (i32.const 0))
+(assert_return (invoke "read_then_call") (i32.const -1))
+(invoke "reset")
+(assert_return (invoke "order") (i32.const 123))
+(assert_return (invoke "load_store" (i32.const 2)) (i32.const 3))
+(invoke "reset")
+(assert_trap (invoke "trap_after" (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "get") (i32.const 1))
+(assert_trap (invoke "trap_before" (i32.const 0)) "integer divide by zero")
+(invoke "reset")
+(assert_return (invoke "trap_after" (i32.const 5)) (i32.const 3))
+(assert_return (invoke "multi") (i32.const 341))
+(invoke "reset")
+(assert_return (invoke "chain" (i32.const 4)) (i32.const 6))
+(invoke "reset")
+(assert_return (invoke "loop" (i32.const 4)) (i32.const 10))
+(invoke "reset")
+(assert_return (invoke "select" (i32.const 0)) (i32.const 50))
+(assert_return (invoke "get") (i32.const 1))
+(assert_return (invoke "select" (i32.const 1)) (i32.const 2))
+(invoke "reset")
+(assert_return (invoke "tee" (i32.const 0)) (i32.const 11))
@@ -0,0 +1,13 @@
+(module $A
+  (global (export "g") (mut i32) (i32.const 1))
+  (func (export "get") (result i32) (global.get 0)))
+(register "A" $A)
+(module $B
+  (import "A" "g" (global $g (mut i32)))
+  (func (export "set") (param i32) (global.set $g (local.get 0)))
+  (func (export "get") (result i32) (global.get $g)))
+(assert_return (invoke $A "get") (i32.const 1))
+(assert_return (invoke $B "set" (i32.const 9)))
+(assert_return (invoke $A "get") (i32.const 9))
+(assert_return (get $A "g") (i32.const 9))
+(assert_return (invoke $B "get") (i32.const 9))
@@ -0,0 +1,40 @@
+(module
+  (memory 1 3)
+  (table 2 4 funcref)
+  (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+  (func (export "size") (result i32) (memory.size))
+  (func (export "tgrow") (param i32) (result i32) (table.grow (ref.null func) (local.get 0)))
+  (func (export "tsize") (result i32) (table.size))
+  (func (export "store") (param i32) (i32.store8 (local.get 0) (i32.const 7)))
+  (func (export "load") (param i32) (result i32) (i32.load8_u (local.get 0)))
+)
+
+(assert_return (invoke "grow" (i32.const -1)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const 3)) (i32.const -1))
+(assert_return (invoke "size") (i32.const 1))
+(assert_return (invoke "grow" (i32.const 2)) (i32.const 1))
+(assert_return (invoke "size") (i32.const 3))
+(invoke "store" (i32.const 196607))
+(assert_return (invoke "load" (i32.const 196607)) (i32.const 7))
+(assert_trap (invoke "load" (i32.const 196608)) "out of bounds memory access")
+(assert_return (invoke "grow" (i32.const 1)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const 0)) (i32.const 3))
+(assert_return (invoke "size") (i32.const 3))
+(assert_return (invoke "tgrow" (i32.const -1)) (i32.const -1))
+(assert_return (invoke "tgrow" (i32.const 3)) (i32.const -1))
+(assert_return (invoke "tgrow" (i32.const 2)) (i32.const 2))
+(assert_return (invoke "tsize") (i32.const 4))
+(assert_return (invoke "tgrow" (i32.const 1)) (i32.const -1))
+(assert_return (invoke "tsize") (i32.const 4))
+
+(module
+  (memory 0)
+  (table 0 funcref)
+  (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+  (func (export "tgrow") (param i32) (result i32) (table.grow (ref.null func) (local.get 0)))
+)
+
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 0))
+(assert_return (invoke "grow" (i32.const 65536)) (i32.const -1))
+(assert_return (invoke "tgrow" (i32.const 70000)) (i32.const 0))
+(assert_return (invoke "tgrow" (i32.const 1)) (i32.const 70000))
@@ -0,0 +1,47 @@
+;; Shifts of values that fit in the low word and bit operations with a
+;; zero operand skip the packed limb arithmetic on Luau
+(module
+  (func (export "shl") (param i64 i64) (result i64) (i64.shl (local.get 0) (local.get 1)))
+  (func (export "shr_u") (param i64 i64) (result i64) (i64.shr_u (local.get 0) (local.get 1)))
+  (func (export "shr_s") (param i64 i64) (result i64) (i64.shr_s (local.get 0) (local.get 1)))
+  (func (export "rotl") (param i64 i64) (result i64) (i64.rotl (local.get 0) (local.get 1)))
+  (func (export "and") (param i64 i64) (result i64) (i64.and (local.get 0) (local.get 1)))
+  (func (export "or") (param i64 i64) (result i64) (i64.or (local.get 0) (local.get 1)))
+  (func (export "xor") (param i64 i64) (result i64) (i64.xor (local.get 0) (local.get 1)))
+)
+
+(assert_return (invoke "shl" (i64.const 0xffffffff) (i64.const 0x14)) (i64.const 0xffffffff00000))
+(assert_return (invoke "shl" (i64.const 0xffffffff) (i64.const 0x15)) (i64.const 0x1fffffffe00000))
+(assert_return (invoke "shl" (i64.const 0x1) (i64.const 0x14)) (i64.const 0x100000))
+(assert_return (invoke "shl" (i64.const 0x80000000) (i64.const 0x14)) (i64.const 0x8000000000000))
+(assert_return (invoke "shl" (i64.const 0x100000000) (i64.const 0x4)) (i64.const 0x1000000000))
+(assert_return (invoke "shl" (i64.const 0xffffffff) (i64.const 0x43)) (i64.const 0x7fffffff8))
+(assert_return (invoke "shl" (i64.const 0x123) (i64.const 0x54)) (i64.const 0x12300000))
+(assert_return (invoke "shr_u" (i64.const 0xffffffff) (i64.const 0x1f)) (i64.const 0x1))
+(assert_return (invoke "shr_u" (i64.const 0xffffffff) (i64.const 0x20)) (i64.const 0x0))
+(assert_return (invoke "shr_u" (i64.const 0xffffffff) (i64.const 0x21)) (i64.const 0x0))
+(assert_return (invoke "shr_u" (i64.const 0xffffffff) (i64.const 0x3f)) (i64.const 0x0))
+(assert_return (invoke "shr_u" (i64.const 0x100000000) (i64.const 0x1)) (i64.const 0x80000000))
+(assert_return (invoke "shr_u" (i64.const 0xffffffff) (i64.const 0x44)) (i64.const 0xfffffff))
+(assert_return (invoke "shr_u" (i64.const 0x1ffffffff) (i64.const 0x4)) (i64.const 0x1fffffff))
+(assert_return (invoke "shr_s" (i64.const 0xffffffff) (i64.const 0x4)) (i64.const 0xfffffff))
+(assert_return (invoke "shr_s" (i64.const 0xffffffff) (i64.const 0x1f)) (i64.const 0x1))
+(assert_return (invoke "shr_s" (i64.const -0x1) (i64.const 0x4)) (i64.const -0x1))
+(assert_return (invoke "shr_s" (i64.const -0x1) (i64.const 0x28)) (i64.const -0x1))
+(assert_return (invoke "shr_s" (i64.const 0x7fffffff) (i64.const 0x3f)) (i64.const 0x0))
+(assert_return (invoke "shr_s" (i64.const -0x100000000) (i64.const 0x4)) (i64.const -0x10000000))
+(assert_return (invoke "shr_s" (i64.const 0x80000000) (i64.const 0x20)) (i64.const 0x0))
+(assert_return (invoke "rotl" (i64.const 0xffffffff) (i64.const 0x4)) (i64.const 0xffffffff0))
+(assert_return (invoke "rotl" (i64.const -0x7fffffffffffffff) (i64.const 0x1)) (i64.const 0x3))
+(assert_return (invoke "rotl" (i64.const 0x1) (i64.const 0x3f)) (i64.const -0x8000000000000000))
+(assert_return (invoke "and" (i64.const 0x0) (i64.const -0x1)) (i64.const 0x0))
+(assert_return (invoke "and" (i64.const -0x1) (i64.const 0x0)) (i64.const 0x0))
+(assert_return (invoke "and" (i64.const 0xff00ff00ff) (i64.const 0xf0f0f0f0f0)) (i64.const 0xf000f000f0))
+(assert_return (invoke "or" (i64.const 0x0) (i64.const -0x1)) (i64.const -0x1))
+(assert_return (invoke "or" (i64.const -0x1) (i64.const 0x0)) (i64.const -0x1))
+(assert_return (invoke "or" (i64.const 0x0) (i64.const 0x0)) (i64.const 0x0))
+(assert_return (invoke "or" (i64.const 0xff00ff00ff) (i64.const 0xf0f0f0f0f0)) (i64.const 0xfff0fff0ff))
+(assert_return (invoke "xor" (i64.const 0x0) (i64.const 0x123456789)) (i64.const 0x123456789))
+(assert_return (invoke "xor" (i64.const 0x123456789) (i64.const 0x0)) (i64.const 0x123456789))
+(assert_return (invoke "xor" (i64.const 0xff00ff00ff) (i64.const 0xf0f0f0f0f0)) (i64.const 0xff00ff00f))
+(assert_return (invoke "xor" (i64.const -0x1) (i64.const -0x1)) (i64.const 0x0))
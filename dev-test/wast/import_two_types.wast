@@ -0,0 +1,52 @@
+;; The spectest host hands over the same `print` for every one of its
+;; print functions, so each import has to keep the type it was given
+(module
+  (type $i32 (func (param i32)))
+  (type $f64 (func (param f64)))
+
+  (import "spectest" "print_i32" (func $print_i32 (type $i32)))
+  (import "spectest" "print_f64" (func $print_f64 (type $f64)))
+
+  (table $tab 4 funcref)
+  (elem (table $tab) (i32.const 0) func $print_i32 $print_f64)
+
+  (func (export "call_i32") (param i32)
+    (call_indirect (type $i32) (i32.const 7) (local.get 0)))
+  (func (export "call_f64") (param i32)
+    (call_indirect (type $f64) (f64.const 1.5) (local.get 0)))
+
+  (func (export "set_f64") (param i32)
+    (table.set $tab (local.get 0) (ref.func $print_f64)))
+  (func (export "copy") (param i32 i32)
+    (table.copy $tab $tab (local.get 0) (local.get 1) (i32.const 1)))
+)
+
+(assert_return (invoke "call_i32" (i32.const 0)))
+(assert_return (invoke "call_f64" (i32.const 1)))
+(assert_trap (invoke "call_f64" (i32.const 0)) "indirect call type mismatch")
+(assert_trap (invoke "call_i32" (i32.const 1)) "indirect call type mismatch")
+
+(invoke "set_f64" (i32.const 2))
+(assert_return (invoke "call_f64" (i32.const 2)))
+(assert_trap (invoke "call_i32" (i32.const 2)) "indirect call type mismatch")
+
+(invoke "copy" (i32.const 3) (i32.const 0))
+(assert_return (invoke "call_i32" (i32.const 3)))
+(assert_trap (invoke "call_f64" (i32.const 3)) "indirect call type mismatch")
+
+;; A later module importing the same host function under the first type
+;; agrees with the first one
+(register "first")
+(module
+  (type $i32 (func (param i32)))
+  (import "spectest" "print_i32" (func $print_i32 (type $i32)))
+  (import "first" "call_i32" (func $call (param i32)))
+  (table 1 funcref)
+  (elem (i32.const 0) func $print_i32)
+
+  (func (export "call_here") (call_indirect (type $i32) (i32.const 3) (i32.const 0)))
+  (func (export "call_there") (call $call (i32.const 0)))
+)
+
+(assert_return (invoke "call_here"))
+(assert_return (invoke "call_there"))
@@ -0,0 +1,11 @@
+(module
+  (func (export "seven") (result i32) (i32.const 7))
+  (global (export "g") i32 (i32.const 5))
+)
+(register "lib")
+(module
+  (import "lib" "seven" (func $seven (result i32)))
+  (import "lib" "g" (global $g i32))
+  (func (export "sum") (result i32) (i32.add (call $seven) (global.get $g)))
+)
+(assert_return (invoke "sum") (i32.const 12))
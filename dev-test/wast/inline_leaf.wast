@@ -0,0 +1,118 @@
+(module
+  (memory 1)
+  (global $g (mut i32) (i32.const 5))
+  (table 1 funcref)
+  (elem (i32.const 0) $in_table)
+
+  (func $add (param i32 i32) (result i32)
+    (i32.add (local.get 0) (local.get 1)))
+
+  (func $mad (param i32 i32 i32) (result i32)
+    (local i32)
+    (local.set 3 (i32.mul (local.get 0) (local.get 1)))
+    (i32.add (local.get 3) (local.get 2)))
+
+  (func $pick (param i32) (result i32)
+    (if (result i32) (local.get 0)
+      (then (i32.const 10))
+      (else (i32.const 20))))
+
+  (func $swap (param i32 i32) (result i32 i32)
+    (local.get 1) (local.get 0))
+
+  (func $bump
+    (global.set $g (i32.add (global.get $g) (i32.const 1))))
+
+  (func $five (result i32) (i32.const 5))
+
+  (func $store (param i32 i64)
+    (i64.store (local.get 0) (local.get 1)))
+
+  (func $load (param i32) (result i64)
+    (i64.load (local.get 0)))
+
+  (func $wide (param i64 i64) (result i64)
+    (i64.mul (local.get 0) (local.get 1)))
+
+  (func $check (param i32)
+    (if (i32.eqz (local.get 0)) (then unreachable)))
+
+  (func $in_table (param i32) (result i32)
+    (i32.mul (local.get 0) (i32.const 3)))
+
+  (func $fac (param i32) (result i32)
+    (if (result i32) (i32.eqz (local.get 0))
+      (then (i32.const 1))
+      (else (i32.mul (local.get 0) (call $fac (i32.sub (local.get 0) (i32.const 1)))))))
+
+  (func (export "add") (param i32 i32) (result i32)
+    (call $add (local.get 1) (local.get 0)))
+
+  (func (export "nested") (param i32) (result i32)
+    (local i32)
+    (local.set 1 (i32.const 100))
+    (call $mad (local.get 0) (local.get 1) (call $add (local.get 1) (local.get 0)))
+    (local.get 1)
+    (i32.add))
+
+  (func (export "pick") (param i32) (result i32)
+    (call $pick (local.get 0)))
+
+  (func (export "swap") (param i32 i32) (result i32)
+    (call $swap (local.get 0) (local.get 1))
+    (i32.sub))
+
+  (func (export "bump") (result i32)
+    (call $bump)
+    (call $bump)
+    (global.get $g))
+
+  (func (export "five") (result i32)
+    (i32.add (call $five) (call $five)))
+
+  (func (export "memory") (param i32) (result i64)
+    (call $store (local.get 0) (i64.const 0x1122334455667788))
+    (call $load (local.get 0)))
+
+  (func (export "wide") (param i64) (result i64)
+    (call $wide (local.get 0) (call $wide (local.get 0) (i64.const 3))))
+
+  (func (export "check") (param i32) (result i32)
+    (call $check (local.get 0))
+    (local.get 0))
+
+  (func (export "indirect") (param i32) (result i32)
+    (i32.add
+      (call $in_table (local.get 0))
+      (call_indirect (param i32) (result i32) (local.get 0) (i32.const 0))))
+
+  (func (export "fac") (param i32) (result i32)
+    (call $fac (local.get 0)))
+
+  (func (export "loop") (param i32) (result i32)
+    (local i32)
+    (block
+      (loop
+        (br_if 1 (i32.eqz (local.get 0)))
+        (local.set 1 (call $add (local.get 1) (local.get 0)))
+        (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+        (br 0)))
+    (local.get 1))
+)
+
+(assert_return (invoke "add" (i32.const 2) (i32.const 3)) (i32.const 5))
+(assert_return (invoke "add" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "nested" (i32.const 3)) (i32.const 503))
+(assert_return (invoke "pick" (i32.const 1)) (i32.const 10))
+(assert_return (invoke "pick" (i32.const 0)) (i32.const 20))
+(assert_return (invoke "swap" (i32.const 10) (i32.const 3)) (i32.const -7))
+(assert_return (invoke "bump") (i32.const 7))
+(assert_return (invoke "bump") (i32.const 9))
+(assert_return (invoke "five") (i32.const 10))
+(assert_return (invoke "memory" (i32.const 8)) (i64.const 0x1122334455667788))
+(assert_return (invoke "wide" (i64.const 0x100000001)) (i64.const 0x600000003))
+(assert_return (invoke "check" (i32.const 4)) (i32.const 4))
+(assert_trap (invoke "check" (i32.const 0)) "unreachable")
+(assert_return (invoke "indirect" (i32.const 7)) (i32.const 42))
+(assert_return (invoke "fac" (i32.const 5)) (i32.const 120))
+(assert_return (invoke "loop" (i32.const 10)) (i32.const 55))
@@ -0,0 +1,58 @@
+(module
+  (memory 1 3)
+  (func (export "i32.store") (param i32 i32) (i32.store (local.get 0) (local.get 1)))
+  (func (export "i32.load") (param i32) (result i32) (i32.load (local.get 0)))
+  (func (export "i32.load8_s") (param i32) (result i32) (i32.load8_s (local.get 0)))
+  (func (export "i32.load8_u") (param i32) (result i32) (i32.load8_u (local.get 0)))
+  (func (export "i32.load16_s") (param i32) (result i32) (i32.load16_s (local.get 0)))
+  (func (export "i32.load16_u") (param i32) (result i32) (i32.load16_u (local.get 0)))
+  (func (export "i64.store") (param i32 i64) (i64.store (local.get 0) (local.get 1)))
+  (func (export "i64.load") (param i32) (result i64) (i64.load (local.get 0)))
+  (func (export "i64.load32_s") (param i32) (result i64) (i64.load32_s (local.get 0)))
+  (func (export "i64.load32_u") (param i32) (result i64) (i64.load32_u (local.get 0)))
+  (func (export "f32.store") (param i32 f32) (f32.store (local.get 0) (local.get 1)))
+  (func (export "f32.load") (param i32) (result f32) (f32.load (local.get 0)))
+  (func (export "f64.store") (param i32 f64) (f64.store (local.get 0) (local.get 1)))
+  (func (export "f64.load") (param i32) (result f64) (f64.load (local.get 0)))
+  (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+)
+
+(assert_return (invoke "i32.load" (i32.const 0)) (i32.const 0))
+(invoke "i32.store" (i32.const 0) (i32.const 0x80FF7F01))
+(assert_return (invoke "i32.load" (i32.const 0)) (i32.const 0x80FF7F01))
+(assert_return (invoke "i32.load8_s" (i32.const 1)) (i32.const 127))
+(assert_return (invoke "i32.load8_s" (i32.const 2)) (i32.const -1))
+(assert_return (invoke "i32.load8_u" (i32.const 2)) (i32.const 255))
+(assert_return (invoke "i32.load16_s" (i32.const 2)) (i32.const -32513))
+(assert_return (invoke "i32.load16_u" (i32.const 2)) (i32.const 33023))
+
+(invoke "i64.store" (i32.const 8) (i64.const 0xFEDCBA9876543210))
+(assert_return (invoke "i64.load" (i32.const 8)) (i64.const 0xFEDCBA9876543210))
+(assert_return (invoke "i64.load32_s" (i32.const 12)) (i64.const -19088744))
+(assert_return (invoke "i64.load32_u" (i32.const 12)) (i64.const 0xFEDCBA98))
+
+(invoke "f32.store" (i32.const 16) (f32.const -1.5))
+(assert_return (invoke "f32.load" (i32.const 16)) (f32.const -1.5))
+(assert_return (invoke "i32.load" (i32.const 16)) (i32.const 0xBFC00000))
+(invoke "f64.store" (i32.const 24) (f64.const 0x1.23456789abcdfp+100))
+(assert_return (invoke "f64.load" (i32.const 24)) (f64.const 0x1.23456789abcdfp+100))
+
+;; The last bytes of a page are usable, the first one past it traps
+(invoke "i32.store" (i32.const 65532) (i32.const 0x11223344))
+(assert_return (invoke "i32.load" (i32.const 65532)) (i32.const 0x11223344))
+(assert_trap (invoke "i32.load" (i32.const 65533)) "out of bounds memory access")
+(assert_trap (invoke "i64.store" (i32.const 65529) (i64.const 1)) "out of bounds memory access")
+;; A store that goes past the end leaves the bytes it overlaps alone
+(assert_return (invoke "i32.load" (i32.const 65532)) (i32.const 0x11223344))
+(assert_trap (invoke "f64.load" (i32.const 65536)) "out of bounds memory access")
+
+;; Growing reallocates, keeping the old contents and zeroing the new page
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "i32.load" (i32.const 0)) (i32.const 0x80FF7F01))
+(assert_return (invoke "i64.load" (i32.const 8)) (i64.const 0xFEDCBA9876543210))
+(assert_return (invoke "i32.load" (i32.const 65532)) (i32.const 0x11223344))
+(assert_return (invoke "i64.load" (i32.const 65536)) (i64.const 0))
+(invoke "i32.store" (i32.const 131068) (i32.const 7))
+(assert_return (invoke "i32.load" (i32.const 131068)) (i32.const 7))
+(assert_trap (invoke "i32.load" (i32.const 131069)) "out of bounds memory access")
+(assert_trap (invoke "i32.load" (i32.const -1)) "out of bounds memory access")
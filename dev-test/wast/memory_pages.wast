@@ -0,0 +1,33 @@
+(module
+  (memory 1 8)
+  (func (export "size") (result i32) (memory.size))
+  (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+  (func (export "last") (result i32)
+    (i32.store8 (i32.sub (i32.mul (memory.size) (i32.const 65536)) (i32.const 1)) (i32.const 7))
+    (i32.load8_u (i32.sub (i32.mul (memory.size) (i32.const 65536)) (i32.const 1))))
+  (func (export "past") (result i32)
+    (i32.load8_u (i32.mul (memory.size) (i32.const 65536))))
+)
+
+(assert_return (invoke "size") (i32.const 1))
+(assert_return (invoke "grow" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "size") (i32.const 1))
+(assert_return (invoke "grow" (i32.const 2)) (i32.const 1))
+(assert_return (invoke "size") (i32.const 3))
+(assert_return (invoke "last") (i32.const 7))
+(assert_trap (invoke "past") "out of bounds")
+(assert_return (invoke "grow" (i32.const 6)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const -1)) (i32.const -1))
+(assert_return (invoke "size") (i32.const 3))
+(assert_return (invoke "grow" (i32.const 5)) (i32.const 3))
+(assert_return (invoke "size") (i32.const 8))
+
+(module
+  (memory 0)
+  (func (export "size") (result i32) (memory.size))
+  (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+)
+
+(assert_return (invoke "size") (i32.const 0))
+(assert_return (invoke "grow" (i32.const 2)) (i32.const 0))
+(assert_return (invoke "size") (i32.const 2))
@@ -0,0 +1,27 @@
+(module
+  (func (export "mul32") (param i32 i32) (result i32) (i32.mul (local.get 0) (local.get 1)))
+  (func (export "mul64") (param i64 i64) (result i64) (i64.mul (local.get 0) (local.get 1)))
+)
+
+;; Products past 2^53, which a plain float multiply gets wrong in the low bits
+(assert_return (invoke "mul32" (i32.const 0xffffffff) (i32.const 0xffffffff)) (i32.const 1))
+(assert_return (invoke "mul32" (i32.const 0x7fffffff) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "mul32" (i32.const 0x80000000) (i32.const 0xffffffff)) (i32.const -2147483648))
+(assert_return (invoke "mul32" (i32.const 0x80000000) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "mul32" (i32.const 0xb505) (i32.const 0xb505)) (i32.const -2147479015))
+(assert_return (invoke "mul32" (i32.const 0x10001) (i32.const 0xffff)) (i32.const -1))
+(assert_return (invoke "mul32" (i32.const 0x12345678) (i32.const 0x9abcdef1)) (i32.const 912357112))
+(assert_return (invoke "mul32" (i32.const 0xdeadbeef) (i32.const 0xcafebabe)) (i32.const -1999676574))
+(assert_return (invoke "mul32" (i32.const 0xfffffffd) (i32.const 0x7)) (i32.const -21))
+(assert_return (invoke "mul32" (i32.const 0x1000001) (i32.const 0x1000001)) (i32.const 33554433))
+
+(assert_return (invoke "mul64" (i64.const 0xffffffffffffffff) (i64.const 0xffffffffffffffff)) (i64.const 1))
+(assert_return (invoke "mul64" (i64.const 0xffffffff) (i64.const 0xffffffff)) (i64.const -8589934591))
+(assert_return (invoke "mul64" (i64.const 0x7fffffffffffffff) (i64.const 0x7fffffffffffffff)) (i64.const 1))
+(assert_return (invoke "mul64" (i64.const 0x7fffffffffffffff) (i64.const 0x2)) (i64.const -2))
+(assert_return (invoke "mul64" (i64.const 0x100000001) (i64.const 0xffffffff)) (i64.const -1))
+(assert_return (invoke "mul64" (i64.const 0x8000000000000000) (i64.const 0xffffffffffffffff)) (i64.const -9223372036854775808))
+(assert_return (invoke "mul64" (i64.const 0x80000000) (i64.const 0x80000000)) (i64.const 4611686018427387904))
+(assert_return (invoke "mul64" (i64.const 0xfffffffffffffffe) (i64.const 0x7fffffffffffffff)) (i64.const 2))
+(assert_return (invoke "mul64" (i64.const 0x123456789abcdef0) (i64.const 0xfedcba987654321)) (i64.const 2465395958572223728))
+(assert_return (invoke "mul64" (i64.const 0xfffffffedcba9877) (i64.const 0xffffffff00000001)) (i64.const 2541551397941975159))
@@ -0,0 +1,85 @@
+(module
+  (type $pp (func (param i32 i32) (result i32 i32)))
+  (type $p1 (func (param i32) (result i32)))
+
+  (func (export "swap") (param i32 i32) (result i32 i32)
+    (local.get 0) (local.get 1)
+    (block (param i32 i32) (result i32 i32)
+      (local.set 0) (local.set 1)
+      (local.get 0) (local.get 1)))
+
+  (func (export "typed") (param i32 i32) (result i32)
+    (local.get 0) (local.get 1)
+    (block (type $pp)
+      (i32.add) (i32.const 7))
+    (i32.sub))
+
+  (func (export "br_out") (param i32 i32 i32) (result i32 i32)
+    (local.get 0) (local.get 1)
+    (block (param i32 i32) (result i32 i32)
+      (br_if 0 (local.get 2))
+      (drop) (drop)
+      (i32.const 100) (i32.const 200)))
+
+  (func (export "br_table") (param i32) (result i32 i32)
+    (i32.const 1) (i32.const 2)
+    (block (param i32 i32) (result i32 i32)
+      (block (param i32 i32) (result i32 i32)
+        (br_table 0 1 (local.get 0)))
+      (i32.add) (i32.const 10)))
+
+  (func (export "loop_sum") (param i32) (result i32)
+    (i32.const 0) (local.get 0)
+    (loop $l (param i32 i32) (result i32)
+      (local.set 0)
+      (local.get 0)
+      (i32.add)
+      (local.get 0) (i32.const 1) (i32.sub)
+      (local.tee 0)
+      (br_if $l (i32.ne (local.get 0) (i32.const 0)))
+      (drop)))
+
+  (func (export "if_params") (param i32 i32 i32) (result i32 i32)
+    (local.get 0) (local.get 1)
+    (if (type $pp) (local.get 2)
+      (then (i32.add) (i32.const 1))
+      (else (i32.sub) (i32.const 2))))
+
+  (func (export "if_one") (param i32 i32) (result i32)
+    (local.get 0)
+    (if (type $p1) (local.get 1)
+      (then (i32.const 2) (i32.mul))
+      (else (i32.const 3) (i32.mul))))
+
+  (func (export "nested") (param i32) (result i32 i32 i32)
+    (local.get 0) (i32.const 1) (i32.const 2)
+    (block (param i32 i32 i32) (result i32 i32 i32)
+      (block (param i32 i32) (result i32 i32)
+        (i32.const 3) (i32.add)
+        (i32.const 4) (br 0))
+      (i32.add) (i32.const 9)))
+
+  (func (export "deep") (param i32) (result i32)
+    (i32.const 10) (i32.const 20)
+    (block $outer (param i32 i32) (result i32)
+      (block $inner (param i32 i32) (result i32 i32)
+        (br_if $inner (local.get 0))
+        (i32.add) (br $outer))
+      (i32.sub)))
+)
+
+(assert_return (invoke "swap" (i32.const 1) (i32.const 2)) (i32.const 2) (i32.const 1))
+(assert_return (invoke "typed" (i32.const 5) (i32.const 6)) (i32.const 4))
+(assert_return (invoke "br_out" (i32.const 1) (i32.const 2) (i32.const 1)) (i32.const 1) (i32.const 2))
+(assert_return (invoke "br_out" (i32.const 1) (i32.const 2) (i32.const 0)) (i32.const 100) (i32.const 200))
+(assert_return (invoke "br_table" (i32.const 0)) (i32.const 3) (i32.const 10))
+(assert_return (invoke "br_table" (i32.const 1)) (i32.const 1) (i32.const 2))
+(assert_return (invoke "br_table" (i32.const 5)) (i32.const 1) (i32.const 2))
+(assert_return (invoke "loop_sum" (i32.const 4)) (i32.const 10))
+(assert_return (invoke "if_params" (i32.const 5) (i32.const 3) (i32.const 1)) (i32.const 8) (i32.const 1))
+(assert_return (invoke "if_params" (i32.const 5) (i32.const 3) (i32.const 0)) (i32.const 2) (i32.const 2))
+(assert_return (invoke "if_one" (i32.const 5) (i32.const 1)) (i32.const 10))
+(assert_return (invoke "if_one" (i32.const 5) (i32.const 0)) (i32.const 15))
+(assert_return (invoke "nested" (i32.const 5)) (i32.const 5) (i32.const 9) (i32.const 9))
+(assert_return (invoke "deep" (i32.const 0)) (i32.const 30))
+(assert_return (invoke "deep" (i32.const 1)) (i32.const -10))
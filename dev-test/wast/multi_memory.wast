@@ -0,0 +1,37 @@
+(module
+  (memory $a 1)
+  (memory $b 2 4)
+  (data (memory $a) (i32.const 0) "\01\02")
+  (data (memory $b) (i32.const 0) "\0a\0b")
+  (func (export "load_a") (param i32) (result i32) (i32.load8_u $a (local.get 0)))
+  (func (export "load_b") (param i32) (result i32) (i32.load8_u $b (local.get 0)))
+  (func (export "store_a") (param i32 i32) (i32.store8 $a (local.get 0) (local.get 1)))
+  (func (export "store_b") (param i32 i32) (i32.store8 $b (local.get 0) (local.get 1)))
+  (func (export "size_a") (result i32) (memory.size $a))
+  (func (export "size_b") (result i32) (memory.size $b))
+  (func (export "grow_a") (param i32) (result i32) (memory.grow $a (local.get 0)))
+  (func (export "grow_b") (param i32) (result i32) (memory.grow $b (local.get 0)))
+)
+
+(assert_return (invoke "load_a" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "load_b" (i32.const 0)) (i32.const 10))
+(assert_return (invoke "load_b" (i32.const 1)) (i32.const 11))
+
+(invoke "store_a" (i32.const 1) (i32.const 42))
+(assert_return (invoke "load_a" (i32.const 1)) (i32.const 42))
+(assert_return (invoke "load_b" (i32.const 1)) (i32.const 11))
+
+(assert_return (invoke "size_a") (i32.const 1))
+(assert_return (invoke "size_b") (i32.const 2))
+(assert_return (invoke "load_b" (i32.const 131071)) (i32.const 0))
+(assert_trap (invoke "load_a" (i32.const 65536)) "out of bounds memory access")
+
+(assert_return (invoke "grow_a" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "size_a") (i32.const 2))
+(assert_return (invoke "size_b") (i32.const 2))
+(assert_return (invoke "grow_b" (i32.const 3)) (i32.const -1))
+(assert_return (invoke "grow_b" (i32.const 2)) (i32.const 2))
+(assert_return (invoke "size_b") (i32.const 4))
+(invoke "store_b" (i32.const 262143) (i32.const 9))
+(assert_return (invoke "load_b" (i32.const 262143)) (i32.const 9))
+(assert_return (invoke "load_a" (i32.const 0)) (i32.const 1))
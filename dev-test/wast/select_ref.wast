@@ -0,0 +1,23 @@
+(module
+  (func (export "select-externref") (param externref externref i32) (result externref)
+    (select (result externref) (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "select-funcref") (param i32) (result i32)
+    (ref.is_null (select (result funcref) (ref.func 0) (ref.null func) (local.get 0))))
+  (func (export "select-i32") (param i32 i32 i32) (result i32)
+    (select (result i32) (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "select-f64") (param f64 f64 i32) (result f64)
+    (select (result f64) (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "select-untyped") (param i32 i32 i32) (result i32)
+    (select (local.get 0) (local.get 1) (local.get 2)))
+)
+(assert_return (invoke "select-externref" (ref.extern 1) (ref.extern 2) (i32.const 1)) (ref.extern 1))
+(assert_return (invoke "select-externref" (ref.extern 1) (ref.extern 2) (i32.const 0)) (ref.extern 2))
+(assert_return (invoke "select-externref" (ref.null extern) (ref.extern 2) (i32.const 1)) (ref.null extern))
+(assert_return (invoke "select-externref" (ref.extern 1) (ref.null extern) (i32.const 0)) (ref.null extern))
+(assert_return (invoke "select-externref" (ref.null extern) (ref.extern 2) (i32.const 0)) (ref.extern 2))
+(assert_return (invoke "select-funcref" (i32.const 1)) (i32.const 0))
+(assert_return (invoke "select-funcref" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "select-i32" (i32.const 1) (i32.const 2) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "select-i32" (i32.const 1) (i32.const 2) (i32.const 0)) (i32.const 2))
+(assert_return (invoke "select-f64" (f64.const 1.5) (f64.const 2.5) (i32.const 0)) (f64.const 2.5))
+(assert_return (invoke "select-untyped" (i32.const 7) (i32.const 8) (i32.const 1)) (i32.const 7))
@@ -0,0 +1,52 @@
+(module
+  (func (export "i32.extend8_s") (param i32) (result i32) (i32.extend8_s (local.get 0)))
+  (func (export "i32.extend16_s") (param i32) (result i32) (i32.extend16_s (local.get 0)))
+  (func (export "i64.extend8_s") (param i64) (result i64) (i64.extend8_s (local.get 0)))
+  (func (export "i64.extend16_s") (param i64) (result i64) (i64.extend16_s (local.get 0)))
+  (func (export "i64.extend32_s") (param i64) (result i64) (i64.extend32_s (local.get 0)))
+)
+(assert_return (invoke "i32.extend8_s" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "i32.extend8_s" (i32.const 0x7F)) (i32.const 127))
+(assert_return (invoke "i32.extend8_s" (i32.const 0x80)) (i32.const -128))
+(assert_return (invoke "i32.extend8_s" (i32.const 0xFF)) (i32.const -1))
+(assert_return (invoke "i32.extend8_s" (i32.const 0x012345_00)) (i32.const 0))
+(assert_return (invoke "i32.extend8_s" (i32.const 0xFEDCBA_80)) (i32.const -0x80))
+(assert_return (invoke "i32.extend8_s" (i32.const -1)) (i32.const -1))
+
+(assert_return (invoke "i32.extend16_s" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "i32.extend16_s" (i32.const 0x7F)) (i32.const 0x7F))
+(assert_return (invoke "i32.extend16_s" (i32.const 0xFF)) (i32.const 0xFF))
+(assert_return (invoke "i32.extend16_s" (i32.const 0x7FFF)) (i32.const 32767))
+(assert_return (invoke "i32.extend16_s" (i32.const 0x8000)) (i32.const -32768))
+(assert_return (invoke "i32.extend16_s" (i32.const 0xFFFF)) (i32.const -1))
+(assert_return (invoke "i32.extend16_s" (i32.const 0x0123_0000)) (i32.const 0))
+(assert_return (invoke "i32.extend16_s" (i32.const 0xFEDC_8000)) (i32.const -0x8000))
+(assert_return (invoke "i32.extend16_s" (i32.const -1)) (i32.const -1))
+
+(assert_return (invoke "i64.extend8_s" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "i64.extend8_s" (i64.const 0x7F)) (i64.const 127))
+(assert_return (invoke "i64.extend8_s" (i64.const 0x80)) (i64.const -128))
+(assert_return (invoke "i64.extend8_s" (i64.const 0xFF)) (i64.const -1))
+(assert_return (invoke "i64.extend8_s" (i64.const 0x01234567_89ABCD_00)) (i64.const 0))
+(assert_return (invoke "i64.extend8_s" (i64.const 0xFEDCBA98_765432_80)) (i64.const -0x80))
+(assert_return (invoke "i64.extend8_s" (i64.const -1)) (i64.const -1))
+
+(assert_return (invoke "i64.extend16_s" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "i64.extend16_s" (i64.const 0xFF)) (i64.const 0xFF))
+(assert_return (invoke "i64.extend16_s" (i64.const 0x7FFF)) (i64.const 32767))
+(assert_return (invoke "i64.extend16_s" (i64.const 0x8000)) (i64.const -32768))
+(assert_return (invoke "i64.extend16_s" (i64.const 0xFFFF)) (i64.const -1))
+(assert_return (invoke "i64.extend16_s" (i64.const 0x12345678_9ABC_0000)) (i64.const 0))
+(assert_return (invoke "i64.extend16_s" (i64.const 0xFEDCBA98_7654_8000)) (i64.const -0x8000))
+(assert_return (invoke "i64.extend16_s" (i64.const -1)) (i64.const -1))
+
+(assert_return (invoke "i64.extend32_s" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "i64.extend32_s" (i64.const 0xFF)) (i64.const 0xFF))
+(assert_return (invoke "i64.extend32_s" (i64.const 0x7FFF)) (i64.const 0x7FFF))
+(assert_return (invoke "i64.extend32_s" (i64.const 0x8000)) (i64.const 0x8000))
+(assert_return (invoke "i64.extend32_s" (i64.const 0x7FFFFFFF)) (i64.const 0x7FFFFFFF))
+(assert_return (invoke "i64.extend32_s" (i64.const 0x80000000)) (i64.const -0x80000000))
+(assert_return (invoke "i64.extend32_s" (i64.const 0xFFFFFFFF)) (i64.const -1))
+(assert_return (invoke "i64.extend32_s" (i64.const 0x01234567_00000000)) (i64.const 0))
+(assert_return (invoke "i64.extend32_s" (i64.const 0xFEDCBA98_80000000)) (i64.const -0x80000000))
+(assert_return (invoke "i64.extend32_s" (i64.const -1)) (i64.const -1))
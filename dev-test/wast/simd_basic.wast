@@ -0,0 +1,173 @@
+;; Vectors never cross into the harness, so every case builds them inside
+;; the function and hands back one scalar lane
+(module
+  (memory 1)
+  (data (i32.const 0) "\01\02\03\04\05\06\07\08\09\0a\0b\0c\0d\0e\0f\10\11\12\13\14")
+
+  (func (export "const_i32") (param i32) (result i32)
+    (local v128)
+    (local.set 1 (v128.const i32x4 10 -20 0x7fffffff 0x80000000))
+    (block
+      (block
+        (block
+          (block (br_table 0 1 2 3 (local.get 0)))
+          (return (i32x4.extract_lane 0 (local.get 1))))
+        (return (i32x4.extract_lane 1 (local.get 1))))
+      (return (i32x4.extract_lane 2 (local.get 1))))
+    (i32x4.extract_lane 3 (local.get 1)))
+  (func (export "const_i8_u") (result i32)
+    (i8x16.extract_lane_u 15 (v128.const i8x16 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 -1)))
+  (func (export "const_i8_s") (result i32)
+    (i8x16.extract_lane_s 15 (v128.const i8x16 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 -1)))
+  (func (export "const_i16_u") (result i32)
+    (i16x8.extract_lane_u 1 (v128.const i16x8 1 -2 3 4 5 6 7 8)))
+  (func (export "const_i16_s") (result i32)
+    (i16x8.extract_lane_s 1 (v128.const i16x8 1 -2 3 4 5 6 7 8)))
+  (func (export "const_i64") (result i64)
+    (i64x2.extract_lane 1 (v128.const i64x2 1 0x8000000000000001)))
+  (func (export "const_f64") (result f64)
+    (f64x2.extract_lane 0 (v128.const f64x2 -1.5 2.5)))
+
+  ;; Lanes are read and written little-endian at any address
+  (func (export "load_lane0") (param i32) (result i32) (i32x4.extract_lane 0 (v128.load (local.get 0))))
+  (func (export "load_lane3") (param i32) (result i32) (i32x4.extract_lane 3 (v128.load (local.get 0))))
+  (func (export "load_offset") (result i32) (i32x4.extract_lane 1 (v128.load offset=3 (i32.const 0))))
+  (func (export "load_i64") (param i32) (result i64) (i64x2.extract_lane 1 (v128.load (local.get 0))))
+  (func (export "copy_replace") (param i32 i32)
+    (v128.store (local.get 0) (i32x4.replace_lane 2 (v128.load (i32.const 0)) (local.get 1))))
+  (func (export "store_const") (param i32)
+    (v128.store (local.get 0) (v128.const i8x16 -1 -2 -3 -4 -5 -6 -7 -8 -9 -10 -11 -12 -13 -14 -15 -16)))
+  (func (export "byte") (param i32) (result i32) (i32.load8_u (local.get 0)))
+  (func (export "word") (param i32) (result i32) (i32.load (local.get 0)))
+
+  (func (export "splat_i8") (param i32) (result i32) (i8x16.extract_lane_u 9 (i8x16.splat (local.get 0))))
+  (func (export "splat_i16") (param i32) (result i32) (i16x8.extract_lane_s 7 (i16x8.splat (local.get 0))))
+  (func (export "splat_i32") (param i32) (result i32) (i32x4.extract_lane 3 (i32x4.splat (local.get 0))))
+  (func (export "splat_i64") (param i64) (result i64) (i64x2.extract_lane 1 (i64x2.splat (local.get 0))))
+  (func (export "splat_f32") (param f32) (result f32) (f32x4.extract_lane 2 (f32x4.splat (local.get 0))))
+
+  ;; Replacing one lane leaves the others alone
+  (func (export "replace_i32_other") (param i32 i32) (result i32)
+    (i32x4.extract_lane 1 (i32x4.replace_lane 2 (i32x4.splat (local.get 0)) (local.get 1))))
+  (func (export "replace_i32_own") (param i32 i32) (result i32)
+    (i32x4.extract_lane 2 (i32x4.replace_lane 2 (i32x4.splat (local.get 0)) (local.get 1))))
+  (func (export "replace_i16") (param i32) (result i32)
+    (i16x8.extract_lane_u 4 (i16x8.replace_lane 4 (i16x8.splat (i32.const 9)) (local.get 0))))
+  (func (export "replace_i8") (param i32) (result i32)
+    (i8x16.extract_lane_s 0 (i8x16.replace_lane 0 (i8x16.splat (i32.const 9)) (local.get 0))))
+  (func (export "replace_i64") (param i64) (result i64)
+    (i64x2.extract_lane 0 (i64x2.replace_lane 0 (i64x2.splat (i64.const 7)) (local.get 0))))
+
+  (func (export "i32x4.add") (param i32 i32) (result i32)
+    (i32x4.extract_lane 3 (i32x4.add (i32x4.splat (local.get 0)) (i32x4.splat (local.get 1)))))
+  (func (export "i32x4.sub") (param i32 i32) (result i32)
+    (i32x4.extract_lane 3 (i32x4.sub (i32x4.splat (local.get 0)) (i32x4.splat (local.get 1)))))
+  (func (export "i32x4.mul") (param i32 i32) (result i32)
+    (i32x4.extract_lane 3 (i32x4.mul (i32x4.splat (local.get 0)) (i32x4.splat (local.get 1)))))
+  (func (export "i16x8.add") (param i32 i32) (result i32)
+    (i16x8.extract_lane_s 5 (i16x8.add (i16x8.splat (local.get 0)) (i16x8.splat (local.get 1)))))
+  (func (export "i16x8.sub") (param i32 i32) (result i32)
+    (i16x8.extract_lane_s 5 (i16x8.sub (i16x8.splat (local.get 0)) (i16x8.splat (local.get 1)))))
+  (func (export "i16x8.mul") (param i32 i32) (result i32)
+    (i16x8.extract_lane_u 5 (i16x8.mul (i16x8.splat (local.get 0)) (i16x8.splat (local.get 1)))))
+
+  ;; A lane that wraps around must not carry into its neighbour
+  (func (export "i32x4.add_lane") (param i32) (result i32)
+    (local v128)
+    (local.set 1 (i32x4.add (v128.const i32x4 0xffffffff 0x7fffffff 3 -4) (v128.const i32x4 1 1 -5 4)))
+    (block
+      (block
+        (block
+          (block (br_table 0 1 2 3 (local.get 0)))
+          (return (i32x4.extract_lane 0 (local.get 1))))
+        (return (i32x4.extract_lane 1 (local.get 1))))
+      (return (i32x4.extract_lane 2 (local.get 1))))
+    (i32x4.extract_lane 3 (local.get 1)))
+  (func (export "i32x4.sub_lane1") (result i32)
+    (i32x4.extract_lane 1 (i32x4.sub (v128.const i32x4 0 0 0 0) (v128.const i32x4 1 1 1 1))))
+  (func (export "i32x4.sub_lane2") (result i32)
+    (i32x4.extract_lane 2 (i32x4.sub (v128.const i32x4 5 0 7 0) (v128.const i32x4 0 1 0 0))))
+  (func (export "i32x4.mul_lane0") (result i32)
+    (i32x4.extract_lane 0 (i32x4.mul (v128.const i32x4 0x10000 3 0 0) (v128.const i32x4 0x10000 5 0 0))))
+  (func (export "i32x4.mul_lane1") (result i32)
+    (i32x4.extract_lane 1 (i32x4.mul (v128.const i32x4 0x10000 3 0 0) (v128.const i32x4 0x10000 5 0 0))))
+  (func (export "i16x8.add_lane0") (result i32)
+    (i16x8.extract_lane_u 0 (i16x8.add (v128.const i16x8 0xffff 1 0 0 0 0 0 0) (v128.const i16x8 1 0 0 0 0 0 0 0))))
+  (func (export "i16x8.add_lane1") (result i32)
+    (i16x8.extract_lane_u 1 (i16x8.add (v128.const i16x8 0xffff 1 0 0 0 0 0 0) (v128.const i16x8 1 0 0 0 0 0 0 0))))
+  (func (export "i16x8.sub_lane7") (result i32)
+    (i16x8.extract_lane_s 7 (i16x8.sub (v128.const i16x8 0 0 0 0 0 0 0 -32768) (v128.const i16x8 0 0 0 0 0 0 1 1))))
+  (func (export "i16x8.sub_lane6") (result i32)
+    (i16x8.extract_lane_s 6 (i16x8.sub (v128.const i16x8 0 0 0 0 0 0 0 -32768) (v128.const i16x8 0 0 0 0 0 0 1 1))))
+  (func (export "i16x8.mul_lane2") (result i32)
+    (i16x8.extract_lane_u 2 (i16x8.mul (v128.const i16x8 0 0 0x100 2 0 0 0 0) (v128.const i16x8 0 0 0x100 3 0 0 0 0))))
+  (func (export "i16x8.mul_lane3") (result i32)
+    (i16x8.extract_lane_u 3 (i16x8.mul (v128.const i16x8 0 0 0x100 2 0 0 0 0) (v128.const i16x8 0 0 0x100 3 0 0 0 0))))
+)
+
+(assert_return (invoke "const_i32" (i32.const 0)) (i32.const 10))
+(assert_return (invoke "const_i32" (i32.const 1)) (i32.const -20))
+(assert_return (invoke "const_i32" (i32.const 2)) (i32.const 0x7fffffff))
+(assert_return (invoke "const_i32" (i32.const 3)) (i32.const 0x80000000))
+(assert_return (invoke "const_i8_u") (i32.const 255))
+(assert_return (invoke "const_i8_s") (i32.const -1))
+(assert_return (invoke "const_i16_u") (i32.const 0xfffe))
+(assert_return (invoke "const_i16_s") (i32.const -2))
+(assert_return (invoke "const_i64") (i64.const 0x8000000000000001))
+(assert_return (invoke "const_f64") (f64.const -1.5))
+
+(assert_return (invoke "load_lane0" (i32.const 0)) (i32.const 0x04030201))
+(assert_return (invoke "load_lane3" (i32.const 0)) (i32.const 0x100f0e0d))
+(assert_return (invoke "load_lane0" (i32.const 1)) (i32.const 0x05040302))
+(assert_return (invoke "load_lane3" (i32.const 3)) (i32.const 0x13121110))
+(assert_return (invoke "load_offset") (i32.const 0x0b0a0908))
+(assert_return (invoke "load_i64" (i32.const 0)) (i64.const 0x100f0e0d0c0b0a09))
+(invoke "copy_replace" (i32.const 33) (i32.const 0xa1b2c3d4))
+(assert_return (invoke "word" (i32.const 33)) (i32.const 0x04030201))
+(assert_return (invoke "word" (i32.const 41)) (i32.const 0xa1b2c3d4))
+(assert_return (invoke "byte" (i32.const 41)) (i32.const 0xd4))
+(assert_return (invoke "word" (i32.const 45)) (i32.const 0x100f0e0d))
+(assert_return (invoke "byte" (i32.const 49)) (i32.const 0))
+(invoke "store_const" (i32.const 65520))
+(assert_return (invoke "byte" (i32.const 65520)) (i32.const 0xff))
+(assert_return (invoke "byte" (i32.const 65535)) (i32.const 0xf0))
+(assert_trap (invoke "load_lane0" (i32.const 65521)) "out of bounds memory access")
+(assert_trap (invoke "store_const" (i32.const 65521)) "out of bounds memory access")
+
+(assert_return (invoke "splat_i8" (i32.const 0x1ff)) (i32.const 0xff))
+(assert_return (invoke "splat_i16" (i32.const 0x18000)) (i32.const -32768))
+(assert_return (invoke "splat_i32" (i32.const -7)) (i32.const -7))
+(assert_return (invoke "splat_i64" (i64.const -0x123456789)) (i64.const -0x123456789))
+(assert_return (invoke "splat_f32" (f32.const -0.25)) (f32.const -0.25))
+
+(assert_return (invoke "replace_i32_other" (i32.const 3) (i32.const 9)) (i32.const 3))
+(assert_return (invoke "replace_i32_own" (i32.const 3) (i32.const 9)) (i32.const 9))
+(assert_return (invoke "replace_i16" (i32.const 0x12345)) (i32.const 0x2345))
+(assert_return (invoke "replace_i8" (i32.const 0x80)) (i32.const -128))
+(assert_return (invoke "replace_i64" (i64.const -1)) (i64.const -1))
+
+(assert_return (invoke "i32x4.add" (i32.const 0x7fffffff) (i32.const 1)) (i32.const 0x80000000))
+(assert_return (invoke "i32x4.add" (i32.const -1) (i32.const -1)) (i32.const -2))
+(assert_return (invoke "i32x4.sub" (i32.const 0) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "i32x4.sub" (i32.const 0x80000000) (i32.const 1)) (i32.const 0x7fffffff))
+(assert_return (invoke "i32x4.mul" (i32.const 0xffffffff) (i32.const 0xffffffff)) (i32.const 1))
+(assert_return (invoke "i32x4.mul" (i32.const 0x12345678) (i32.const 0x9abcdef1)) (i32.const 912357112))
+(assert_return (invoke "i16x8.add" (i32.const 0x7fff) (i32.const 1)) (i32.const -32768))
+(assert_return (invoke "i16x8.sub" (i32.const -32768) (i32.const 1)) (i32.const 32767))
+(assert_return (invoke "i16x8.mul" (i32.const 0xffff) (i32.const 0xffff)) (i32.const 1))
+(assert_return (invoke "i16x8.mul" (i32.const 300) (i32.const 300)) (i32.const 24464))
+
+(assert_return (invoke "i32x4.add_lane" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "i32x4.add_lane" (i32.const 1)) (i32.const 0x80000000))
+(assert_return (invoke "i32x4.add_lane" (i32.const 2)) (i32.const -2))
+(assert_return (invoke "i32x4.add_lane" (i32.const 3)) (i32.const 0))
+(assert_return (invoke "i32x4.sub_lane1") (i32.const -1))
+(assert_return (invoke "i32x4.sub_lane2") (i32.const 7))
+(assert_return (invoke "i32x4.mul_lane0") (i32.const 0))
+(assert_return (invoke "i32x4.mul_lane1") (i32.const 15))
+(assert_return (invoke "i16x8.add_lane0") (i32.const 0))
+(assert_return (invoke "i16x8.add_lane1") (i32.const 1))
+(assert_return (invoke "i16x8.sub_lane7") (i32.const 32767))
+(assert_return (invoke "i16x8.sub_lane6") (i32.const -1))
+(assert_return (invoke "i16x8.mul_lane2") (i32.const 0))
+(assert_return (invoke "i16x8.mul_lane3") (i32.const 6))
@@ -0,0 +1,475 @@
+;; One function with more locals than Luau has registers, which splitting
+;; must not pass to a moved out block one by one
+(module
+  (func $leaf (param i32) (result i32) (i32.mul (local.get 0) (i32.const 3)))
+  (func (export "big") (param i32) (result i32)
+    (local i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32)
+    (block
+      (local.set 1 (call $leaf (i32.add (local.get 0) (i32.const 1))))
+      (local.set 2 (call $leaf (i32.add (local.get 0) (i32.const 2))))
+      (local.set 3 (call $leaf (i32.add (local.get 0) (i32.const 3))))
+      (local.set 4 (call $leaf (i32.add (local.get 0) (i32.const 4))))
+      (local.set 5 (call $leaf (i32.add (local.get 0) (i32.const 5))))
+      (local.set 6 (call $leaf (i32.add (local.get 0) (i32.const 6))))
+      (local.set 7 (call $leaf (i32.add (local.get 0) (i32.const 7))))
+      (local.set 8 (call $leaf (i32.add (local.get 0) (i32.const 8))))
+      (local.set 9 (call $leaf (i32.add (local.get 0) (i32.const 9))))
+      (local.set 10 (call $leaf (i32.add (local.get 0) (i32.const 10))))
+      (local.set 11 (call $leaf (i32.add (local.get 0) (i32.const 11))))
+      (local.set 12 (call $leaf (i32.add (local.get 0) (i32.const 12))))
+      (local.set 13 (call $leaf (i32.add (local.get 0) (i32.const 13))))
+      (local.set 14 (call $leaf (i32.add (local.get 0) (i32.const 14))))
+      (local.set 15 (call $leaf (i32.add (local.get 0) (i32.const 15))))
+      (local.set 16 (call $leaf (i32.add (local.get 0) (i32.const 16))))
+      (local.set 17 (call $leaf (i32.add (local.get 0) (i32.const 17))))
+      (local.set 18 (call $leaf (i32.add (local.get 0) (i32.const 18))))
+      (local.set 19 (call $leaf (i32.add (local.get 0) (i32.const 19))))
+      (local.set 20 (call $leaf (i32.add (local.get 0) (i32.const 20))))
+      (local.set 21 (call $leaf (i32.add (local.get 0) (i32.const 21))))
+      (local.set 22 (call $leaf (i32.add (local.get 0) (i32.const 22))))
+      (local.set 23 (call $leaf (i32.add (local.get 0) (i32.const 23))))
+      (local.set 24 (call $leaf (i32.add (local.get 0) (i32.const 24))))
+      (local.set 25 (call $leaf (i32.add (local.get 0) (i32.const 25))))
+      (local.set 26 (call $leaf (i32.add (local.get 0) (i32.const 26))))
+      (local.set 27 (call $leaf (i32.add (local.get 0) (i32.const 27))))
+      (local.set 28 (call $leaf (i32.add (local.get 0) (i32.const 28))))
+      (local.set 29 (call $leaf (i32.add (local.get 0) (i32.const 29))))
+      (local.set 30 (call $leaf (i32.add (local.get 0) (i32.const 30))))
+      (local.set 31 (call $leaf (i32.add (local.get 0) (i32.const 31))))
+      (local.set 32 (call $leaf (i32.add (local.get 0) (i32.const 32))))
+      (local.set 33 (call $leaf (i32.add (local.get 0) (i32.const 33))))
+      (local.set 34 (call $leaf (i32.add (local.get 0) (i32.const 34))))
+      (local.set 35 (call $leaf (i32.add (local.get 0) (i32.const 35))))
+      (local.set 36 (call $leaf (i32.add (local.get 0) (i32.const 36))))
+      (local.set 37 (call $leaf (i32.add (local.get 0) (i32.const 37))))
+      (local.set 38 (call $leaf (i32.add (local.get 0) (i32.const 38))))
+      (local.set 39 (call $leaf (i32.add (local.get 0) (i32.const 39))))
+      (local.set 40 (call $leaf (i32.add (local.get 0) (i32.const 40))))
+      (local.set 41 (call $leaf (i32.add (local.get 0) (i32.const 41))))
+      (local.set 42 (call $leaf (i32.add (local.get 0) (i32.const 42))))
+      (local.set 43 (call $leaf (i32.add (local.get 0) (i32.const 43))))
+      (local.set 44 (call $leaf (i32.add (local.get 0) (i32.const 44))))
+      (local.set 45 (call $leaf (i32.add (local.get 0) (i32.const 45))))
+      (local.set 46 (call $leaf (i32.add (local.get 0) (i32.const 46))))
+      (local.set 47 (call $leaf (i32.add (local.get 0) (i32.const 47))))
+      (local.set 48 (call $leaf (i32.add (local.get 0) (i32.const 48))))
+      (local.set 49 (call $leaf (i32.add (local.get 0) (i32.const 49))))
+      (local.set 50 (call $leaf (i32.add (local.get 0) (i32.const 50))))
+      (local.set 51 (call $leaf (i32.add (local.get 0) (i32.const 51))))
+      (local.set 52 (call $leaf (i32.add (local.get 0) (i32.const 52))))
+      (local.set 53 (call $leaf (i32.add (local.get 0) (i32.const 53))))
+      (local.set 54 (call $leaf (i32.add (local.get 0) (i32.const 54))))
+      (local.set 55 (call $leaf (i32.add (local.get 0) (i32.const 55))))
+      (local.set 56 (call $leaf (i32.add (local.get 0) (i32.const 56))))
+      (local.set 57 (call $leaf (i32.add (local.get 0) (i32.const 57))))
+      (local.set 58 (call $leaf (i32.add (local.get 0) (i32.const 58))))
+      (local.set 59 (call $leaf (i32.add (local.get 0) (i32.const 59))))
+      (local.set 60 (call $leaf (i32.add (local.get 0) (i32.const 60))))
+      (local.set 61 (call $leaf (i32.add (local.get 0) (i32.const 61))))
+      (local.set 62 (call $leaf (i32.add (local.get 0) (i32.const 62))))
+      (local.set 63 (call $leaf (i32.add (local.get 0) (i32.const 63))))
+      (local.set 64 (call $leaf (i32.add (local.get 0) (i32.const 64))))
+      (local.set 65 (call $leaf (i32.add (local.get 0) (i32.const 65))))
+      (local.set 66 (call $leaf (i32.add (local.get 0) (i32.const 66))))
+      (local.set 67 (call $leaf (i32.add (local.get 0) (i32.const 67))))
+      (local.set 68 (call $leaf (i32.add (local.get 0) (i32.const 68))))
+      (local.set 69 (call $leaf (i32.add (local.get 0) (i32.const 69))))
+      (local.set 70 (call $leaf (i32.add (local.get 0) (i32.const 70))))
+      (local.set 71 (call $leaf (i32.add (local.get 0) (i32.const 71))))
+      (local.set 72 (call $leaf (i32.add (local.get 0) (i32.const 72))))
+      (local.set 73 (call $leaf (i32.add (local.get 0) (i32.const 73))))
+      (local.set 74 (call $leaf (i32.add (local.get 0) (i32.const 74))))
+      (local.set 75 (call $leaf (i32.add (local.get 0) (i32.const 75))))
+      (local.set 76 (call $leaf (i32.add (local.get 0) (i32.const 76))))
+      (local.set 77 (call $leaf (i32.add (local.get 0) (i32.const 77))))
+      (local.set 78 (call $leaf (i32.add (local.get 0) (i32.const 78))))
+      (local.set 79 (call $leaf (i32.add (local.get 0) (i32.const 79))))
+      (local.set 80 (call $leaf (i32.add (local.get 0) (i32.const 80))))
+      (local.set 81 (call $leaf (i32.add (local.get 0) (i32.const 81))))
+      (local.set 82 (call $leaf (i32.add (local.get 0) (i32.const 82))))
+      (local.set 83 (call $leaf (i32.add (local.get 0) (i32.const 83))))
+      (local.set 84 (call $leaf (i32.add (local.get 0) (i32.const 84))))
+      (local.set 85 (call $leaf (i32.add (local.get 0) (i32.const 85))))
+      (local.set 86 (call $leaf (i32.add (local.get 0) (i32.const 86))))
+      (local.set 87 (call $leaf (i32.add (local.get 0) (i32.const 87))))
+      (local.set 88 (call $leaf (i32.add (local.get 0) (i32.const 88))))
+      (local.set 89 (call $leaf (i32.add (local.get 0) (i32.const 89))))
+      (local.set 90 (call $leaf (i32.add (local.get 0) (i32.const 90))))
+      (local.set 91 (call $leaf (i32.add (local.get 0) (i32.const 91))))
+      (local.set 92 (call $leaf (i32.add (local.get 0) (i32.const 92))))
+      (local.set 93 (call $leaf (i32.add (local.get 0) (i32.const 93))))
+      (local.set 94 (call $leaf (i32.add (local.get 0) (i32.const 94))))
+      (local.set 95 (call $leaf (i32.add (local.get 0) (i32.const 95))))
+      (local.set 96 (call $leaf (i32.add (local.get 0) (i32.const 96))))
+      (local.set 97 (call $leaf (i32.add (local.get 0) (i32.const 97))))
+      (local.set 98 (call $leaf (i32.add (local.get 0) (i32.const 98))))
+      (local.set 99 (call $leaf (i32.add (local.get 0) (i32.const 99))))
+      (local.set 100 (call $leaf (i32.add (local.get 0) (i32.const 100))))
+      (local.set 101 (call $leaf (i32.add (local.get 0) (i32.const 101))))
+      (local.set 102 (call $leaf (i32.add (local.get 0) (i32.const 102))))
+      (local.set 103 (call $leaf (i32.add (local.get 0) (i32.const 103))))
+      (local.set 104 (call $leaf (i32.add (local.get 0) (i32.const 104))))
+      (local.set 105 (call $leaf (i32.add (local.get 0) (i32.const 105))))
+      (local.set 106 (call $leaf (i32.add (local.get 0) (i32.const 106))))
+      (local.set 107 (call $leaf (i32.add (local.get 0) (i32.const 107))))
+      (local.set 108 (call $leaf (i32.add (local.get 0) (i32.const 108))))
+      (local.set 109 (call $leaf (i32.add (local.get 0) (i32.const 109))))
+      (local.set 110 (call $leaf (i32.add (local.get 0) (i32.const 110))))
+      (local.set 111 (call $leaf (i32.add (local.get 0) (i32.const 111))))
+      (local.set 112 (call $leaf (i32.add (local.get 0) (i32.const 112))))
+      (local.set 113 (call $leaf (i32.add (local.get 0) (i32.const 113))))
+      (local.set 114 (call $leaf (i32.add (local.get 0) (i32.const 114))))
+      (local.set 115 (call $leaf (i32.add (local.get 0) (i32.const 115))))
+      (local.set 116 (call $leaf (i32.add (local.get 0) (i32.const 116))))
+      (local.set 117 (call $leaf (i32.add (local.get 0) (i32.const 117))))
+      (local.set 118 (call $leaf (i32.add (local.get 0) (i32.const 118))))
+      (local.set 119 (call $leaf (i32.add (local.get 0) (i32.const 119))))
+      (local.set 120 (call $leaf (i32.add (local.get 0) (i32.const 120))))
+      (local.set 121 (call $leaf (i32.add (local.get 0) (i32.const 121))))
+      (local.set 122 (call $leaf (i32.add (local.get 0) (i32.const 122))))
+      (local.set 123 (call $leaf (i32.add (local.get 0) (i32.const 123))))
+      (local.set 124 (call $leaf (i32.add (local.get 0) (i32.const 124))))
+      (local.set 125 (call $leaf (i32.add (local.get 0) (i32.const 125))))
+      (local.set 126 (call $leaf (i32.add (local.get 0) (i32.const 126))))
+      (local.set 127 (call $leaf (i32.add (local.get 0) (i32.const 127))))
+      (local.set 128 (call $leaf (i32.add (local.get 0) (i32.const 128))))
+      (local.set 129 (call $leaf (i32.add (local.get 0) (i32.const 129))))
+      (local.set 130 (call $leaf (i32.add (local.get 0) (i32.const 130))))
+      (local.set 131 (call $leaf (i32.add (local.get 0) (i32.const 131))))
+      (local.set 132 (call $leaf (i32.add (local.get 0) (i32.const 132))))
+      (local.set 133 (call $leaf (i32.add (local.get 0) (i32.const 133))))
+      (local.set 134 (call $leaf (i32.add (local.get 0) (i32.const 134))))
+      (local.set 135 (call $leaf (i32.add (local.get 0) (i32.const 135))))
+      (local.set 136 (call $leaf (i32.add (local.get 0) (i32.const 136))))
+      (local.set 137 (call $leaf (i32.add (local.get 0) (i32.const 137))))
+      (local.set 138 (call $leaf (i32.add (local.get 0) (i32.const 138))))
+      (local.set 139 (call $leaf (i32.add (local.get 0) (i32.const 139))))
+      (local.set 140 (call $leaf (i32.add (local.get 0) (i32.const 140))))
+      (local.set 141 (call $leaf (i32.add (local.get 0) (i32.const 141))))
+      (local.set 142 (call $leaf (i32.add (local.get 0) (i32.const 142))))
+      (local.set 143 (call $leaf (i32.add (local.get 0) (i32.const 143))))
+      (local.set 144 (call $leaf (i32.add (local.get 0) (i32.const 144))))
+      (local.set 145 (call $leaf (i32.add (local.get 0) (i32.const 145))))
+      (local.set 146 (call $leaf (i32.add (local.get 0) (i32.const 146))))
+      (local.set 147 (call $leaf (i32.add (local.get 0) (i32.const 147))))
+      (local.set 148 (call $leaf (i32.add (local.get 0) (i32.const 148))))
+      (local.set 149 (call $leaf (i32.add (local.get 0) (i32.const 149))))
+      (local.set 150 (call $leaf (i32.add (local.get 0) (i32.const 150))))
+      (local.set 151 (call $leaf (i32.add (local.get 0) (i32.const 151))))
+      (local.set 152 (call $leaf (i32.add (local.get 0) (i32.const 152))))
+      (local.set 153 (call $leaf (i32.add (local.get 0) (i32.const 153))))
+      (local.set 154 (call $leaf (i32.add (local.get 0) (i32.const 154))))
+      (local.set 155 (call $leaf (i32.add (local.get 0) (i32.const 155))))
+      (local.set 156 (call $leaf (i32.add (local.get 0) (i32.const 156))))
+      (local.set 157 (call $leaf (i32.add (local.get 0) (i32.const 157))))
+      (local.set 158 (call $leaf (i32.add (local.get 0) (i32.const 158))))
+      (local.set 159 (call $leaf (i32.add (local.get 0) (i32.const 159))))
+      (local.set 160 (call $leaf (i32.add (local.get 0) (i32.const 160))))
+      (local.set 161 (call $leaf (i32.add (local.get 0) (i32.const 161))))
+      (local.set 162 (call $leaf (i32.add (local.get 0) (i32.const 162))))
+      (local.set 163 (call $leaf (i32.add (local.get 0) (i32.const 163))))
+      (local.set 164 (call $leaf (i32.add (local.get 0) (i32.const 164))))
+      (local.set 165 (call $leaf (i32.add (local.get 0) (i32.const 165))))
+      (local.set 166 (call $leaf (i32.add (local.get 0) (i32.const 166))))
+      (local.set 167 (call $leaf (i32.add (local.get 0) (i32.const 167))))
+      (local.set 168 (call $leaf (i32.add (local.get 0) (i32.const 168))))
+      (local.set 169 (call $leaf (i32.add (local.get 0) (i32.const 169))))
+      (local.set 170 (call $leaf (i32.add (local.get 0) (i32.const 170))))
+      (local.set 171 (call $leaf (i32.add (local.get 0) (i32.const 171))))
+      (local.set 172 (call $leaf (i32.add (local.get 0) (i32.const 172))))
+      (local.set 173 (call $leaf (i32.add (local.get 0) (i32.const 173))))
+      (local.set 174 (call $leaf (i32.add (local.get 0) (i32.const 174))))
+      (local.set 175 (call $leaf (i32.add (local.get 0) (i32.const 175))))
+      (local.set 176 (call $leaf (i32.add (local.get 0) (i32.const 176))))
+      (local.set 177 (call $leaf (i32.add (local.get 0) (i32.const 177))))
+      (local.set 178 (call $leaf (i32.add (local.get 0) (i32.const 178))))
+      (local.set 179 (call $leaf (i32.add (local.get 0) (i32.const 179))))
+      (local.set 180 (call $leaf (i32.add (local.get 0) (i32.const 180))))
+      (local.set 181 (call $leaf (i32.add (local.get 0) (i32.const 181))))
+      (local.set 182 (call $leaf (i32.add (local.get 0) (i32.const 182))))
+      (local.set 183 (call $leaf (i32.add (local.get 0) (i32.const 183))))
+      (local.set 184 (call $leaf (i32.add (local.get 0) (i32.const 184))))
+      (local.set 185 (call $leaf (i32.add (local.get 0) (i32.const 185))))
+      (local.set 186 (call $leaf (i32.add (local.get 0) (i32.const 186))))
+      (local.set 187 (call $leaf (i32.add (local.get 0) (i32.const 187))))
+      (local.set 188 (call $leaf (i32.add (local.get 0) (i32.const 188))))
+      (local.set 189 (call $leaf (i32.add (local.get 0) (i32.const 189))))
+      (local.set 190 (call $leaf (i32.add (local.get 0) (i32.const 190))))
+      (local.set 191 (call $leaf (i32.add (local.get 0) (i32.const 191))))
+      (local.set 192 (call $leaf (i32.add (local.get 0) (i32.const 192))))
+      (local.set 193 (call $leaf (i32.add (local.get 0) (i32.const 193))))
+      (local.set 194 (call $leaf (i32.add (local.get 0) (i32.const 194))))
+      (local.set 195 (call $leaf (i32.add (local.get 0) (i32.const 195))))
+      (local.set 196 (call $leaf (i32.add (local.get 0) (i32.const 196))))
+      (local.set 197 (call $leaf (i32.add (local.get 0) (i32.const 197))))
+      (local.set 198 (call $leaf (i32.add (local.get 0) (i32.const 198))))
+      (local.set 199 (call $leaf (i32.add (local.get 0) (i32.const 199))))
+      (local.set 200 (call $leaf (i32.add (local.get 0) (i32.const 200))))
+      (local.set 201 (call $leaf (i32.add (local.get 0) (i32.const 201))))
+      (local.set 202 (call $leaf (i32.add (local.get 0) (i32.const 202))))
+      (local.set 203 (call $leaf (i32.add (local.get 0) (i32.const 203))))
+      (local.set 204 (call $leaf (i32.add (local.get 0) (i32.const 204))))
+      (local.set 205 (call $leaf (i32.add (local.get 0) (i32.const 205))))
+      (local.set 206 (call $leaf (i32.add (local.get 0) (i32.const 206))))
+      (local.set 207 (call $leaf (i32.add (local.get 0) (i32.const 207))))
+      (local.set 208 (call $leaf (i32.add (local.get 0) (i32.const 208))))
+      (local.set 209 (call $leaf (i32.add (local.get 0) (i32.const 209))))
+      (local.set 210 (call $leaf (i32.add (local.get 0) (i32.const 210))))
+      (local.set 211 (call $leaf (i32.add (local.get 0) (i32.const 211))))
+      (local.set 212 (call $leaf (i32.add (local.get 0) (i32.const 212))))
+      (local.set 213 (call $leaf (i32.add (local.get 0) (i32.const 213))))
+      (local.set 214 (call $leaf (i32.add (local.get 0) (i32.const 214))))
+      (local.set 215 (call $leaf (i32.add (local.get 0) (i32.const 215))))
+      (local.set 216 (call $leaf (i32.add (local.get 0) (i32.const 216))))
+      (local.set 217 (call $leaf (i32.add (local.get 0) (i32.const 217))))
+      (local.set 218 (call $leaf (i32.add (local.get 0) (i32.const 218))))
+      (local.set 219 (call $leaf (i32.add (local.get 0) (i32.const 219))))
+      (local.set 220 (call $leaf (i32.add (local.get 0) (i32.const 220))))
+      (local.set 221 (call $leaf (i32.add (local.get 0) (i32.const 221))))
+      (local.set 222 (call $leaf (i32.add (local.get 0) (i32.const 222))))
+      (local.set 223 (call $leaf (i32.add (local.get 0) (i32.const 223))))
+      (local.set 224 (call $leaf (i32.add (local.get 0) (i32.const 224))))
+      (local.set 225 (call $leaf (i32.add (local.get 0) (i32.const 225))))
+      (local.set 226 (call $leaf (i32.add (local.get 0) (i32.const 226))))
+      (local.set 227 (call $leaf (i32.add (local.get 0) (i32.const 227))))
+      (local.set 228 (call $leaf (i32.add (local.get 0) (i32.const 228))))
+      (local.set 229 (call $leaf (i32.add (local.get 0) (i32.const 229))))
+      (local.set 230 (call $leaf (i32.add (local.get 0) (i32.const 230))))
+    )
+    (local.set 1 (i32.add (local.get 1) (local.get 2)))
+    (local.set 1 (i32.add (local.get 1) (local.get 3)))
+    (local.set 1 (i32.add (local.get 1) (local.get 4)))
+    (local.set 1 (i32.add (local.get 1) (local.get 5)))
+    (local.set 1 (i32.add (local.get 1) (local.get 6)))
+    (local.set 1 (i32.add (local.get 1) (local.get 7)))
+    (local.set 1 (i32.add (local.get 1) (local.get 8)))
+    (local.set 1 (i32.add (local.get 1) (local.get 9)))
+    (local.set 1 (i32.add (local.get 1) (local.get 10)))
+    (local.set 1 (i32.add (local.get 1) (local.get 11)))
+    (local.set 1 (i32.add (local.get 1) (local.get 12)))
+    (local.set 1 (i32.add (local.get 1) (local.get 13)))
+    (local.set 1 (i32.add (local.get 1) (local.get 14)))
+    (local.set 1 (i32.add (local.get 1) (local.get 15)))
+    (local.set 1 (i32.add (local.get 1) (local.get 16)))
+    (local.set 1 (i32.add (local.get 1) (local.get 17)))
+    (local.set 1 (i32.add (local.get 1) (local.get 18)))
+    (local.set 1 (i32.add (local.get 1) (local.get 19)))
+    (local.set 1 (i32.add (local.get 1) (local.get 20)))
+    (local.set 1 (i32.add (local.get 1) (local.get 21)))
+    (local.set 1 (i32.add (local.get 1) (local.get 22)))
+    (local.set 1 (i32.add (local.get 1) (local.get 23)))
+    (local.set 1 (i32.add (local.get 1) (local.get 24)))
+    (local.set 1 (i32.add (local.get 1) (local.get 25)))
+    (local.set 1 (i32.add (local.get 1) (local.get 26)))
+    (local.set 1 (i32.add (local.get 1) (local.get 27)))
+    (local.set 1 (i32.add (local.get 1) (local.get 28)))
+    (local.set 1 (i32.add (local.get 1) (local.get 29)))
+    (local.set 1 (i32.add (local.get 1) (local.get 30)))
+    (local.set 1 (i32.add (local.get 1) (local.get 31)))
+    (local.set 1 (i32.add (local.get 1) (local.get 32)))
+    (local.set 1 (i32.add (local.get 1) (local.get 33)))
+    (local.set 1 (i32.add (local.get 1) (local.get 34)))
+    (local.set 1 (i32.add (local.get 1) (local.get 35)))
+    (local.set 1 (i32.add (local.get 1) (local.get 36)))
+    (local.set 1 (i32.add (local.get 1) (local.get 37)))
+    (local.set 1 (i32.add (local.get 1) (local.get 38)))
+    (local.set 1 (i32.add (local.get 1) (local.get 39)))
+    (local.set 1 (i32.add (local.get 1) (local.get 40)))
+    (local.set 1 (i32.add (local.get 1) (local.get 41)))
+    (local.set 1 (i32.add (local.get 1) (local.get 42)))
+    (local.set 1 (i32.add (local.get 1) (local.get 43)))
+    (local.set 1 (i32.add (local.get 1) (local.get 44)))
+    (local.set 1 (i32.add (local.get 1) (local.get 45)))
+    (local.set 1 (i32.add (local.get 1) (local.get 46)))
+    (local.set 1 (i32.add (local.get 1) (local.get 47)))
+    (local.set 1 (i32.add (local.get 1) (local.get 48)))
+    (local.set 1 (i32.add (local.get 1) (local.get 49)))
+    (local.set 1 (i32.add (local.get 1) (local.get 50)))
+    (local.set 1 (i32.add (local.get 1) (local.get 51)))
+    (local.set 1 (i32.add (local.get 1) (local.get 52)))
+    (local.set 1 (i32.add (local.get 1) (local.get 53)))
+    (local.set 1 (i32.add (local.get 1) (local.get 54)))
+    (local.set 1 (i32.add (local.get 1) (local.get 55)))
+    (local.set 1 (i32.add (local.get 1) (local.get 56)))
+    (local.set 1 (i32.add (local.get 1) (local.get 57)))
+    (local.set 1 (i32.add (local.get 1) (local.get 58)))
+    (local.set 1 (i32.add (local.get 1) (local.get 59)))
+    (local.set 1 (i32.add (local.get 1) (local.get 60)))
+    (local.set 1 (i32.add (local.get 1) (local.get 61)))
+    (local.set 1 (i32.add (local.get 1) (local.get 62)))
+    (local.set 1 (i32.add (local.get 1) (local.get 63)))
+    (local.set 1 (i32.add (local.get 1) (local.get 64)))
+    (local.set 1 (i32.add (local.get 1) (local.get 65)))
+    (local.set 1 (i32.add (local.get 1) (local.get 66)))
+    (local.set 1 (i32.add (local.get 1) (local.get 67)))
+    (local.set 1 (i32.add (local.get 1) (local.get 68)))
+    (local.set 1 (i32.add (local.get 1) (local.get 69)))
+    (local.set 1 (i32.add (local.get 1) (local.get 70)))
+    (local.set 1 (i32.add (local.get 1) (local.get 71)))
+    (local.set 1 (i32.add (local.get 1) (local.get 72)))
+    (local.set 1 (i32.add (local.get 1) (local.get 73)))
+    (local.set 1 (i32.add (local.get 1) (local.get 74)))
+    (local.set 1 (i32.add (local.get 1) (local.get 75)))
+    (local.set 1 (i32.add (local.get 1) (local.get 76)))
+    (local.set 1 (i32.add (local.get 1) (local.get 77)))
+    (local.set 1 (i32.add (local.get 1) (local.get 78)))
+    (local.set 1 (i32.add (local.get 1) (local.get 79)))
+    (local.set 1 (i32.add (local.get 1) (local.get 80)))
+    (local.set 1 (i32.add (local.get 1) (local.get 81)))
+    (local.set 1 (i32.add (local.get 1) (local.get 82)))
+    (local.set 1 (i32.add (local.get 1) (local.get 83)))
+    (local.set 1 (i32.add (local.get 1) (local.get 84)))
+    (local.set 1 (i32.add (local.get 1) (local.get 85)))
+    (local.set 1 (i32.add (local.get 1) (local.get 86)))
+    (local.set 1 (i32.add (local.get 1) (local.get 87)))
+    (local.set 1 (i32.add (local.get 1) (local.get 88)))
+    (local.set 1 (i32.add (local.get 1) (local.get 89)))
+    (local.set 1 (i32.add (local.get 1) (local.get 90)))
+    (local.set 1 (i32.add (local.get 1) (local.get 91)))
+    (local.set 1 (i32.add (local.get 1) (local.get 92)))
+    (local.set 1 (i32.add (local.get 1) (local.get 93)))
+    (local.set 1 (i32.add (local.get 1) (local.get 94)))
+    (local.set 1 (i32.add (local.get 1) (local.get 95)))
+    (local.set 1 (i32.add (local.get 1) (local.get 96)))
+    (local.set 1 (i32.add (local.get 1) (local.get 97)))
+    (local.set 1 (i32.add (local.get 1) (local.get 98)))
+    (local.set 1 (i32.add (local.get 1) (local.get 99)))
+    (local.set 1 (i32.add (local.get 1) (local.get 100)))
+    (local.set 1 (i32.add (local.get 1) (local.get 101)))
+    (local.set 1 (i32.add (local.get 1) (local.get 102)))
+    (local.set 1 (i32.add (local.get 1) (local.get 103)))
+    (local.set 1 (i32.add (local.get 1) (local.get 104)))
+    (local.set 1 (i32.add (local.get 1) (local.get 105)))
+    (local.set 1 (i32.add (local.get 1) (local.get 106)))
+    (local.set 1 (i32.add (local.get 1) (local.get 107)))
+    (local.set 1 (i32.add (local.get 1) (local.get 108)))
+    (local.set 1 (i32.add (local.get 1) (local.get 109)))
+    (local.set 1 (i32.add (local.get 1) (local.get 110)))
+    (local.set 1 (i32.add (local.get 1) (local.get 111)))
+    (local.set 1 (i32.add (local.get 1) (local.get 112)))
+    (local.set 1 (i32.add (local.get 1) (local.get 113)))
+    (local.set 1 (i32.add (local.get 1) (local.get 114)))
+    (local.set 1 (i32.add (local.get 1) (local.get 115)))
+    (local.set 1 (i32.add (local.get 1) (local.get 116)))
+    (local.set 1 (i32.add (local.get 1) (local.get 117)))
+    (local.set 1 (i32.add (local.get 1) (local.get 118)))
+    (local.set 1 (i32.add (local.get 1) (local.get 119)))
+    (local.set 1 (i32.add (local.get 1) (local.get 120)))
+    (local.set 1 (i32.add (local.get 1) (local.get 121)))
+    (local.set 1 (i32.add (local.get 1) (local.get 122)))
+    (local.set 1 (i32.add (local.get 1) (local.get 123)))
+    (local.set 1 (i32.add (local.get 1) (local.get 124)))
+    (local.set 1 (i32.add (local.get 1) (local.get 125)))
+    (local.set 1 (i32.add (local.get 1) (local.get 126)))
+    (local.set 1 (i32.add (local.get 1) (local.get 127)))
+    (local.set 1 (i32.add (local.get 1) (local.get 128)))
+    (local.set 1 (i32.add (local.get 1) (local.get 129)))
+    (local.set 1 (i32.add (local.get 1) (local.get 130)))
+    (local.set 1 (i32.add (local.get 1) (local.get 131)))
+    (local.set 1 (i32.add (local.get 1) (local.get 132)))
+    (local.set 1 (i32.add (local.get 1) (local.get 133)))
+    (local.set 1 (i32.add (local.get 1) (local.get 134)))
+    (local.set 1 (i32.add (local.get 1) (local.get 135)))
+    (local.set 1 (i32.add (local.get 1) (local.get 136)))
+    (local.set 1 (i32.add (local.get 1) (local.get 137)))
+    (local.set 1 (i32.add (local.get 1) (local.get 138)))
+    (local.set 1 (i32.add (local.get 1) (local.get 139)))
+    (local.set 1 (i32.add (local.get 1) (local.get 140)))
+    (local.set 1 (i32.add (local.get 1) (local.get 141)))
+    (local.set 1 (i32.add (local.get 1) (local.get 142)))
+    (local.set 1 (i32.add (local.get 1) (local.get 143)))
+    (local.set 1 (i32.add (local.get 1) (local.get 144)))
+    (local.set 1 (i32.add (local.get 1) (local.get 145)))
+    (local.set 1 (i32.add (local.get 1) (local.get 146)))
+    (local.set 1 (i32.add (local.get 1) (local.get 147)))
+    (local.set 1 (i32.add (local.get 1) (local.get 148)))
+    (local.set 1 (i32.add (local.get 1) (local.get 149)))
+    (local.set 1 (i32.add (local.get 1) (local.get 150)))
+    (local.set 1 (i32.add (local.get 1) (local.get 151)))
+    (local.set 1 (i32.add (local.get 1) (local.get 152)))
+    (local.set 1 (i32.add (local.get 1) (local.get 153)))
+    (local.set 1 (i32.add (local.get 1) (local.get 154)))
+    (local.set 1 (i32.add (local.get 1) (local.get 155)))
+    (local.set 1 (i32.add (local.get 1) (local.get 156)))
+    (local.set 1 (i32.add (local.get 1) (local.get 157)))
+    (local.set 1 (i32.add (local.get 1) (local.get 158)))
+    (local.set 1 (i32.add (local.get 1) (local.get 159)))
+    (local.set 1 (i32.add (local.get 1) (local.get 160)))
+    (local.set 1 (i32.add (local.get 1) (local.get 161)))
+    (local.set 1 (i32.add (local.get 1) (local.get 162)))
+    (local.set 1 (i32.add (local.get 1) (local.get 163)))
+    (local.set 1 (i32.add (local.get 1) (local.get 164)))
+    (local.set 1 (i32.add (local.get 1) (local.get 165)))
+    (local.set 1 (i32.add (local.get 1) (local.get 166)))
+    (local.set 1 (i32.add (local.get 1) (local.get 167)))
+    (local.set 1 (i32.add (local.get 1) (local.get 168)))
+    (local.set 1 (i32.add (local.get 1) (local.get 169)))
+    (local.set 1 (i32.add (local.get 1) (local.get 170)))
+    (local.set 1 (i32.add (local.get 1) (local.get 171)))
+    (local.set 1 (i32.add (local.get 1) (local.get 172)))
+    (local.set 1 (i32.add (local.get 1) (local.get 173)))
+    (local.set 1 (i32.add (local.get 1) (local.get 174)))
+    (local.set 1 (i32.add (local.get 1) (local.get 175)))
+    (local.set 1 (i32.add (local.get 1) (local.get 176)))
+    (local.set 1 (i32.add (local.get 1) (local.get 177)))
+    (local.set 1 (i32.add (local.get 1) (local.get 178)))
+    (local.set 1 (i32.add (local.get 1) (local.get 179)))
+    (local.set 1 (i32.add (local.get 1) (local.get 180)))
+    (local.set 1 (i32.add (local.get 1) (local.get 181)))
+    (local.set 1 (i32.add (local.get 1) (local.get 182)))
+    (local.set 1 (i32.add (local.get 1) (local.get 183)))
+    (local.set 1 (i32.add (local.get 1) (local.get 184)))
+    (local.set 1 (i32.add (local.get 1) (local.get 185)))
+    (local.set 1 (i32.add (local.get 1) (local.get 186)))
+    (local.set 1 (i32.add (local.get 1) (local.get 187)))
+    (local.set 1 (i32.add (local.get 1) (local.get 188)))
+    (local.set 1 (i32.add (local.get 1) (local.get 189)))
+    (local.set 1 (i32.add (local.get 1) (local.get 190)))
+    (local.set 1 (i32.add (local.get 1) (local.get 191)))
+    (local.set 1 (i32.add (local.get 1) (local.get 192)))
+    (local.set 1 (i32.add (local.get 1) (local.get 193)))
+    (local.set 1 (i32.add (local.get 1) (local.get 194)))
+    (local.set 1 (i32.add (local.get 1) (local.get 195)))
+    (local.set 1 (i32.add (local.get 1) (local.get 196)))
+    (local.set 1 (i32.add (local.get 1) (local.get 197)))
+    (local.set 1 (i32.add (local.get 1) (local.get 198)))
+    (local.set 1 (i32.add (local.get 1) (local.get 199)))
+    (local.set 1 (i32.add (local.get 1) (local.get 200)))
+    (local.set 1 (i32.add (local.get 1) (local.get 201)))
+    (local.set 1 (i32.add (local.get 1) (local.get 202)))
+    (local.set 1 (i32.add (local.get 1) (local.get 203)))
+    (local.set 1 (i32.add (local.get 1) (local.get 204)))
+    (local.set 1 (i32.add (local.get 1) (local.get 205)))
+    (local.set 1 (i32.add (local.get 1) (local.get 206)))
+    (local.set 1 (i32.add (local.get 1) (local.get 207)))
+    (local.set 1 (i32.add (local.get 1) (local.get 208)))
+    (local.set 1 (i32.add (local.get 1) (local.get 209)))
+    (local.set 1 (i32.add (local.get 1) (local.get 210)))
+    (local.set 1 (i32.add (local.get 1) (local.get 211)))
+    (local.set 1 (i32.add (local.get 1) (local.get 212)))
+    (local.set 1 (i32.add (local.get 1) (local.get 213)))
+    (local.set 1 (i32.add (local.get 1) (local.get 214)))
+    (local.set 1 (i32.add (local.get 1) (local.get 215)))
+    (local.set 1 (i32.add (local.get 1) (local.get 216)))
+    (local.set 1 (i32.add (local.get 1) (local.get 217)))
+    (local.set 1 (i32.add (local.get 1) (local.get 218)))
+    (local.set 1 (i32.add (local.get 1) (local.get 219)))
+    (local.set 1 (i32.add (local.get 1) (local.get 220)))
+    (local.set 1 (i32.add (local.get 1) (local.get 221)))
+    (local.set 1 (i32.add (local.get 1) (local.get 222)))
+    (local.set 1 (i32.add (local.get 1) (local.get 223)))
+    (local.set 1 (i32.add (local.get 1) (local.get 224)))
+    (local.set 1 (i32.add (local.get 1) (local.get 225)))
+    (local.set 1 (i32.add (local.get 1) (local.get 226)))
+    (local.set 1 (i32.add (local.get 1) (local.get 227)))
+    (local.set 1 (i32.add (local.get 1) (local.get 228)))
+    (local.set 1 (i32.add (local.get 1) (local.get 229)))
+    (local.set 1 (i32.add (local.get 1) (local.get 230)))
+    (local.get 1)
+  )
+)
+
+(assert_return (invoke "big" (i32.const 0)) (i32.const 79695))
+(assert_return (invoke "big" (i32.const 1)) (i32.const 80385))
+(assert_return (invoke "big" (i32.const -1)) (i32.const 79005))
+(assert_return (invoke "big" (i32.const 2147483647)) (i32.const 79005))
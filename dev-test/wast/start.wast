@@ -0,0 +1,15 @@
+(module
+  (memory 1)
+  (global $g (mut i32) (i32.const 0))
+  (data (i32.const 0) "\01")
+  (func $init
+    (i32.store8 (i32.const 1) (i32.add (i32.load8_u (i32.const 0)) (i32.const 41)))
+    (global.set $g (i32.const 7))
+  )
+  (func (export "sentinel") (result i32) (i32.load8_u (i32.const 1)))
+  (func (export "global") (result i32) (global.get $g))
+  (start $init)
+)
+(assert_return (invoke "sentinel") (i32.const 42))
+(assert_return (invoke "global") (i32.const 7))
+(assert_trap (module (func $trap (unreachable)) (start $trap)) "unreachable")
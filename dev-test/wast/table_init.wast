@@ -0,0 +1,24 @@
+(module
+  (type $t (func (result i32)))
+  (table 4 funcref)
+  (elem $p func $f1 $f2 $f3)
+  (elem declare func $f1)
+  (func $f1 (result i32) (i32.const 11))
+  (func $f2 (result i32) (i32.const 22))
+  (func $f3 (result i32) (i32.const 33))
+  (func (export "init") (param i32 i32 i32) (table.init $p (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "drop") (elem.drop $p))
+  (func (export "call") (param i32) (result i32) (call_indirect (type $t) (local.get 0)))
+)
+(assert_trap (invoke "call" (i32.const 0)) "uninitialized element")
+(invoke "init" (i32.const 1) (i32.const 0) (i32.const 3))
+(assert_return (invoke "call" (i32.const 1)) (i32.const 11))
+(assert_return (invoke "call" (i32.const 3)) (i32.const 33))
+(invoke "init" (i32.const 0) (i32.const 2) (i32.const 1))
+(assert_return (invoke "call" (i32.const 0)) (i32.const 33))
+(assert_trap (invoke "init" (i32.const 2) (i32.const 0) (i32.const 3)) "out of bounds table access")
+(assert_trap (invoke "init" (i32.const 0) (i32.const 0) (i32.const -1)) "out of bounds table access")
+(invoke "init" (i32.const 4) (i32.const 3) (i32.const 0))
+(invoke "drop")
+(invoke "init" (i32.const 0) (i32.const 0) (i32.const 0))
+(assert_trap (invoke "init" (i32.const 0) (i32.const 0) (i32.const 1)) "out of bounds table access")
@@ -0,0 +1,48 @@
+(module
+  (type $t (func (result i32)))
+  (table $a 2 4 funcref)
+  (table $b 3 externref)
+  (elem (table $a) (i32.const 0) func $f1 $f2)
+  (func $f1 (result i32) (i32.const 11))
+  (func $f2 (result i32) (i32.const 22))
+  (func (export "size") (result i32) (table.size $a))
+  (func (export "grow") (param i32) (result i32) (table.grow $a (ref.func $f2) (local.get 0)))
+  (func (export "grow_null") (param i32) (result i32) (table.grow $a (ref.null func) (local.get 0)))
+  (func (export "grow_b") (param i32) (result i32) (table.grow $b (ref.null extern) (local.get 0)))
+  (func (export "size_b") (result i32) (table.size $b))
+  (func (export "fill") (param i32 i32) (table.fill $a (local.get 0) (ref.func $f1) (local.get 1)))
+  (func (export "clear") (param i32 i32) (table.fill $a (local.get 0) (ref.null func) (local.get 1)))
+  (func (export "copy") (param i32 i32 i32) (table.copy $a $a (local.get 0) (local.get 1) (local.get 2)))
+  (func (export "call") (param i32) (result i32) (call_indirect $a (type $t) (local.get 0)))
+  (func (export "size_then_grow") (result i32)
+    (table.size $a)
+    (drop (table.grow $a (ref.null func) (i32.const 1)))
+  )
+)
+(assert_return (invoke "size") (i32.const 2))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 2))
+(assert_return (invoke "size") (i32.const 3))
+(assert_return (invoke "call" (i32.const 2)) (i32.const 22))
+(assert_return (invoke "grow" (i32.const 2)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const -1)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const 0)) (i32.const 3))
+(assert_return (invoke "size_b") (i32.const 3))
+(assert_return (invoke "grow_b" (i32.const 10)) (i32.const 3))
+(assert_return (invoke "size_b") (i32.const 13))
+(invoke "copy" (i32.const 1) (i32.const 0) (i32.const 2))
+(assert_return (invoke "call" (i32.const 1)) (i32.const 11))
+(assert_return (invoke "call" (i32.const 2)) (i32.const 22))
+(invoke "copy" (i32.const 0) (i32.const 1) (i32.const 2))
+(assert_return (invoke "call" (i32.const 0)) (i32.const 11))
+(assert_return (invoke "call" (i32.const 1)) (i32.const 22))
+(assert_trap (invoke "copy" (i32.const 2) (i32.const 0) (i32.const 2)) "out of bounds table access")
+(assert_trap (invoke "copy" (i32.const 0) (i32.const -1) (i32.const 1)) "out of bounds table access")
+(invoke "copy" (i32.const 3) (i32.const 0) (i32.const 0))
+(invoke "fill" (i32.const 1) (i32.const 2))
+(assert_return (invoke "call" (i32.const 2)) (i32.const 11))
+(invoke "clear" (i32.const 2) (i32.const 1))
+(assert_trap (invoke "call" (i32.const 2)) "uninitialized element")
+(assert_trap (invoke "fill" (i32.const 2) (i32.const 2)) "out of bounds table access")
+(assert_return (invoke "grow_null" (i32.const 1)) (i32.const 3))
+(assert_trap (invoke "call" (i32.const 3)) "uninitialized element")
+(assert_return (invoke "size") (i32.const 4))
@@ -0,0 +1,9 @@
+(module
+  (table $a 1 funcref)
+  (func (export "size_then_grow") (result i32)
+    (table.size $a)
+    (drop (table.grow $a (ref.null func) (i32.const 1)))
+  )
+)
+(assert_return (invoke "size_then_grow") (i32.const 1))
+(assert_return (invoke "size_then_grow") (i32.const 2))
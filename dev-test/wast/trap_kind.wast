@@ -0,0 +1,16 @@
+(module
+  (memory 1)
+  (table 1 funcref)
+  (func (export "unreachable") (unreachable))
+  (func (export "div_s") (param i32 i32) (result i32) (i32.div_s (local.get 0) (local.get 1)))
+  (func (export "div_u64") (param i64 i64) (result i64) (i64.div_u (local.get 0) (local.get 1)))
+  (func (export "fill") (memory.fill (i32.const 65535) (i32.const 0) (i32.const 2)))
+  (func (export "table") (result funcref) (table.get (i32.const 1)))
+  (func (export "call") (call_indirect (i32.const 0)))
+)
+(assert_trap (invoke "unreachable") "unreachable")
+(assert_trap (invoke "div_s" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_trap (invoke "div_u64" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_trap (invoke "fill") "out of bounds memory access")
+(assert_trap (invoke "table") "out of bounds table access")
+(assert_trap (invoke "call") "uninitialized element")
@@ -0,0 +1,60 @@
+(module
+  (memory 1)
+  (data (i32.const 0) "\00\01\02\03\04\05\06\07\08\09\0a\0b\0c\0d\0e\0f\10\11\12\13")
+  (func (export "i32_load") (param i32) (result i32) (i32.load (local.get 0)))
+  (func (export "i32_load_off") (param i32) (result i32) (i32.load offset=1 (local.get 0)))
+  (func (export "i32_load16_u") (param i32) (result i32) (i32.load16_u (local.get 0)))
+  (func (export "i32_load16_s") (param i32) (result i32) (i32.load16_s (local.get 0)))
+  (func (export "i64_load") (param i32) (result i64) (i64.load (local.get 0)))
+  (func (export "i64_load32_u") (param i32) (result i64) (i64.load32_u (local.get 0)))
+  (func (export "f32_load_bits") (param i32) (result i32) (i32.reinterpret_f32 (f32.load (local.get 0))))
+  (func (export "f64_load_bits") (param i32) (result i64) (i64.reinterpret_f64 (f64.load (local.get 0))))
+  (func (export "byte") (param i32) (result i32) (i32.load8_u (local.get 0)))
+  (func (export "i32_store") (param i32 i32) (i32.store (local.get 0) (local.get 1)))
+  (func (export "i32_store16") (param i32 i32) (i32.store16 (local.get 0) (local.get 1)))
+  (func (export "i64_store") (param i32 i64) (i64.store (local.get 0) (local.get 1)))
+  (func (export "i64_store32") (param i32 i64) (i64.store32 (local.get 0) (local.get 1)))
+  (func (export "f32_store_bits") (param i32 i32) (f32.store (local.get 0) (f32.reinterpret_i32 (local.get 1))))
+  (func (export "f64_store_bits") (param i32 i64) (f64.store (local.get 0) (f64.reinterpret_i64 (local.get 1))))
+)
+(assert_return (invoke "i32_load" (i32.const 1)) (i32.const 0x04030201))
+(assert_return (invoke "i32_load" (i32.const 2)) (i32.const 0x05040302))
+(assert_return (invoke "i32_load" (i32.const 3)) (i32.const 0x06050403))
+(assert_return (invoke "i32_load_off" (i32.const 2)) (i32.const 0x06050403))
+(assert_return (invoke "i32_load16_u" (i32.const 3)) (i32.const 0x0403))
+(assert_return (invoke "i32_load16_s" (i32.const 7)) (i32.const 0x0807))
+(assert_return (invoke "i64_load" (i32.const 1)) (i64.const 0x0807060504030201))
+(assert_return (invoke "i64_load" (i32.const 3)) (i64.const 0x0a09080706050403))
+(assert_return (invoke "i64_load" (i32.const 6)) (i64.const 0x0d0c0b0a09080706))
+(assert_return (invoke "i64_load32_u" (i32.const 3)) (i64.const 0x06050403))
+(assert_return (invoke "f32_load_bits" (i32.const 2)) (i32.const 0x05040302))
+(assert_return (invoke "f64_load_bits" (i32.const 3)) (i64.const 0x0a09080706050403))
+(invoke "i32_store" (i32.const 33) (i32.const 0xa1b2c3d4))
+(assert_return (invoke "byte" (i32.const 33)) (i32.const 0xd4))
+(assert_return (invoke "byte" (i32.const 34)) (i32.const 0xc3))
+(assert_return (invoke "byte" (i32.const 35)) (i32.const 0xb2))
+(assert_return (invoke "byte" (i32.const 36)) (i32.const 0xa1))
+(assert_return (invoke "i32_load" (i32.const 34)) (i32.const 0x00a1b2c3))
+(invoke "i32_store16" (i32.const 39) (i32.const 0xbeef))
+(assert_return (invoke "byte" (i32.const 39)) (i32.const 0xef))
+(assert_return (invoke "byte" (i32.const 40)) (i32.const 0xbe))
+(invoke "i64_store" (i32.const 50) (i64.const 0x0102030405060708))
+(assert_return (invoke "byte" (i32.const 50)) (i32.const 0x08))
+(assert_return (invoke "byte" (i32.const 53)) (i32.const 0x05))
+(assert_return (invoke "byte" (i32.const 57)) (i32.const 0x01))
+(assert_return (invoke "i32_load" (i32.const 51)) (i32.const 0x04050607))
+(invoke "i64_store32" (i32.const 67) (i64.const 0x1122334455667788))
+(assert_return (invoke "byte" (i32.const 67)) (i32.const 0x88))
+(assert_return (invoke "byte" (i32.const 70)) (i32.const 0x55))
+(assert_return (invoke "byte" (i32.const 71)) (i32.const 0x00))
+(invoke "f32_store_bits" (i32.const 81) (i32.const 0x3f800001))
+(assert_return (invoke "byte" (i32.const 81)) (i32.const 0x01))
+(assert_return (invoke "byte" (i32.const 84)) (i32.const 0x3f))
+(assert_return (invoke "i32_load" (i32.const 81)) (i32.const 0x3f800001))
+(invoke "f64_store_bits" (i32.const 90) (i64.const 0x3ff0000000000001))
+(assert_return (invoke "byte" (i32.const 90)) (i32.const 0x01))
+(assert_return (invoke "byte" (i32.const 97)) (i32.const 0x3f))
+(assert_return (invoke "i64_load" (i32.const 90)) (i64.const 0x3ff0000000000001))
+(assert_trap (invoke "i32_load" (i32.const 65533)) "out of bounds memory access")
+(assert_trap (invoke "i64_load" (i32.const 65529)) "out of bounds memory access")
+(assert_trap (invoke "i32_store" (i32.const 65534) (i32.const 0)) "out of bounds memory access")
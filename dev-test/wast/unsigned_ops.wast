@@ -0,0 +1,122 @@
+(module
+  (func (export "i32.lt_u") (param i32 i32) (result i32) (i32.lt_u (local.get 0) (local.get 1)))
+  (func (export "i32.gt_u") (param i32 i32) (result i32) (i32.gt_u (local.get 0) (local.get 1)))
+  (func (export "i32.le_u") (param i32 i32) (result i32) (i32.le_u (local.get 0) (local.get 1)))
+  (func (export "i32.ge_u") (param i32 i32) (result i32) (i32.ge_u (local.get 0) (local.get 1)))
+  (func (export "i32.div_u") (param i32 i32) (result i32) (i32.div_u (local.get 0) (local.get 1)))
+  (func (export "i32.rem_u") (param i32 i32) (result i32) (i32.rem_u (local.get 0) (local.get 1)))
+  (func (export "i32.div_u_const") (param i32) (result i32) (i32.div_u (local.get 0) (i32.const 2)))
+  (func (export "i32.lt_u_const") (param i32) (result i32) (i32.lt_u (local.get 0) (i32.const 1)))
+  (func (export "i64.lt_u") (param i64 i64) (result i32) (i64.lt_u (local.get 0) (local.get 1)))
+  (func (export "i64.gt_u") (param i64 i64) (result i32) (i64.gt_u (local.get 0) (local.get 1)))
+  (func (export "i64.le_u") (param i64 i64) (result i32) (i64.le_u (local.get 0) (local.get 1)))
+  (func (export "i64.ge_u") (param i64 i64) (result i32) (i64.ge_u (local.get 0) (local.get 1)))
+  (func (export "i64.div_u") (param i64 i64) (result i64) (i64.div_u (local.get 0) (local.get 1)))
+  (func (export "i64.rem_u") (param i64 i64) (result i64) (i64.rem_u (local.get 0) (local.get 1)))
+  (func (export "i64.div_u_const") (param i64) (result i64) (i64.div_u (local.get 0) (i64.const 2)))
+  (func (export "i64.lt_u_const") (param i64) (result i32) (i64.lt_u (local.get 0) (i64.const 1)))
+  (func (export "fold_lt") (result i32) (i32.lt_u (i32.const 0xFFFFFFFF) (i32.const 1)))
+  (func (export "fold_div") (result i32) (i32.div_u (i32.const 0x80000000) (i32.const 2)))
+  (func (export "fold_div64") (result i64) (i64.div_u (i64.const 0x8000000000000000) (i64.const 2)))
+  (func (export "fold_rem64") (result i64) (i64.rem_u (i64.const -1) (i64.const 10)))
+)
+
+;; i32 operands with the sign bit set are above every positive one
+(assert_return (invoke "i32.lt_u" (i32.const 0xffffffff) (i32.const 0x1)) (i32.const 0))
+(assert_return (invoke "i32.lt_u" (i32.const 0x1) (i32.const 0xffffffff)) (i32.const 1))
+(assert_return (invoke "i32.lt_u" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "i32.lt_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "i32.lt_u" (i32.const 0x5) (i32.const 0x5)) (i32.const 0))
+(assert_return (invoke "i32.lt_u" (i32.const 0x0) (i32.const 0xffffffff)) (i32.const 1))
+(assert_return (invoke "i32.gt_u" (i32.const 0xffffffff) (i32.const 0x1)) (i32.const 1))
+(assert_return (invoke "i32.gt_u" (i32.const 0x1) (i32.const 0xffffffff)) (i32.const 0))
+(assert_return (invoke "i32.gt_u" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "i32.gt_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "i32.gt_u" (i32.const 0x5) (i32.const 0x5)) (i32.const 0))
+(assert_return (invoke "i32.gt_u" (i32.const 0x0) (i32.const 0xffffffff)) (i32.const 0))
+(assert_return (invoke "i32.le_u" (i32.const 0xffffffff) (i32.const 0x1)) (i32.const 0))
+(assert_return (invoke "i32.le_u" (i32.const 0x1) (i32.const 0xffffffff)) (i32.const 1))
+(assert_return (invoke "i32.le_u" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 0))
+(assert_return (invoke "i32.le_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "i32.le_u" (i32.const 0x5) (i32.const 0x5)) (i32.const 1))
+(assert_return (invoke "i32.le_u" (i32.const 0x0) (i32.const 0xffffffff)) (i32.const 1))
+(assert_return (invoke "i32.ge_u" (i32.const 0xffffffff) (i32.const 0x1)) (i32.const 1))
+(assert_return (invoke "i32.ge_u" (i32.const 0x1) (i32.const 0xffffffff)) (i32.const 0))
+(assert_return (invoke "i32.ge_u" (i32.const 0x80000000) (i32.const 0x7fffffff)) (i32.const 1))
+(assert_return (invoke "i32.ge_u" (i32.const 0x7fffffff) (i32.const 0x80000000)) (i32.const 0))
+(assert_return (invoke "i32.ge_u" (i32.const 0x5) (i32.const 0x5)) (i32.const 1))
+(assert_return (invoke "i32.ge_u" (i32.const 0x0) (i32.const 0xffffffff)) (i32.const 0))
+
+(assert_return (invoke "i32.div_u" (i32.const 0x80000000) (i32.const 0x2)) (i32.const 1073741824))
+(assert_return (invoke "i32.div_u" (i32.const 0xffffffff) (i32.const 0x1)) (i32.const -1))
+(assert_return (invoke "i32.div_u" (i32.const 0xffffffff) (i32.const 0xffffffff)) (i32.const 1))
+(assert_return (invoke "i32.div_u" (i32.const 0xffffffff) (i32.const 0x80000000)) (i32.const 1))
+(assert_return (invoke "i32.div_u" (i32.const 0x7) (i32.const 0xffffffff)) (i32.const 0))
+(assert_return (invoke "i32.div_u" (i32.const 0xffffffff) (i32.const 0xa)) (i32.const 429496729))
+(assert_return (invoke "i32.div_u" (i32.const 0x80000001) (i32.const 0x3)) (i32.const 715827883))
+(assert_trap (invoke "i32.div_u" (i32.const 0xffffffff) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "i32.rem_u" (i32.const 0x80000000) (i32.const 0x2)) (i32.const 0))
+(assert_return (invoke "i32.rem_u" (i32.const 0xffffffff) (i32.const 0x1)) (i32.const 0))
+(assert_return (invoke "i32.rem_u" (i32.const 0xffffffff) (i32.const 0xffffffff)) (i32.const 0))
+(assert_return (invoke "i32.rem_u" (i32.const 0xffffffff) (i32.const 0x80000000)) (i32.const 2147483647))
+(assert_return (invoke "i32.rem_u" (i32.const 0x7) (i32.const 0xffffffff)) (i32.const 7))
+(assert_return (invoke "i32.rem_u" (i32.const 0xffffffff) (i32.const 0xa)) (i32.const 5))
+(assert_return (invoke "i32.rem_u" (i32.const 0x80000001) (i32.const 0x3)) (i32.const 0))
+(assert_trap (invoke "i32.rem_u" (i32.const 0xffffffff) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "i32.div_u_const" (i32.const 0x80000000)) (i32.const 1073741824))
+(assert_return (invoke "i32.div_u_const" (i32.const 0xffffffff)) (i32.const 2147483647))
+(assert_return (invoke "i32.lt_u_const" (i32.const 0xffffffff)) (i32.const 0))
+(assert_return (invoke "i32.lt_u_const" (i32.const 0)) (i32.const 1))
+
+;; i64 operands with the sign bit set are above every positive one
+(assert_return (invoke "i64.lt_u" (i64.const 0xffffffffffffffff) (i64.const 0x1)) (i32.const 0))
+(assert_return (invoke "i64.lt_u" (i64.const 0x1) (i64.const 0xffffffffffffffff)) (i32.const 1))
+(assert_return (invoke "i64.lt_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "i64.lt_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "i64.lt_u" (i64.const 0x5) (i64.const 0x5)) (i32.const 0))
+(assert_return (invoke "i64.lt_u" (i64.const 0x0) (i64.const 0xffffffffffffffff)) (i32.const 1))
+(assert_return (invoke "i64.gt_u" (i64.const 0xffffffffffffffff) (i64.const 0x1)) (i32.const 1))
+(assert_return (invoke "i64.gt_u" (i64.const 0x1) (i64.const 0xffffffffffffffff)) (i32.const 0))
+(assert_return (invoke "i64.gt_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "i64.gt_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "i64.gt_u" (i64.const 0x5) (i64.const 0x5)) (i32.const 0))
+(assert_return (invoke "i64.gt_u" (i64.const 0x0) (i64.const 0xffffffffffffffff)) (i32.const 0))
+(assert_return (invoke "i64.le_u" (i64.const 0xffffffffffffffff) (i64.const 0x1)) (i32.const 0))
+(assert_return (invoke "i64.le_u" (i64.const 0x1) (i64.const 0xffffffffffffffff)) (i32.const 1))
+(assert_return (invoke "i64.le_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 0))
+(assert_return (invoke "i64.le_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 1))
+(assert_return (invoke "i64.le_u" (i64.const 0x5) (i64.const 0x5)) (i32.const 1))
+(assert_return (invoke "i64.le_u" (i64.const 0x0) (i64.const 0xffffffffffffffff)) (i32.const 1))
+(assert_return (invoke "i64.ge_u" (i64.const 0xffffffffffffffff) (i64.const 0x1)) (i32.const 1))
+(assert_return (invoke "i64.ge_u" (i64.const 0x1) (i64.const 0xffffffffffffffff)) (i32.const 0))
+(assert_return (invoke "i64.ge_u" (i64.const 0x8000000000000000) (i64.const 0x7fffffffffffffff)) (i32.const 1))
+(assert_return (invoke "i64.ge_u" (i64.const 0x7fffffffffffffff) (i64.const 0x8000000000000000)) (i32.const 0))
+(assert_return (invoke "i64.ge_u" (i64.const 0x5) (i64.const 0x5)) (i32.const 1))
+(assert_return (invoke "i64.ge_u" (i64.const 0x0) (i64.const 0xffffffffffffffff)) (i32.const 0))
+
+(assert_return (invoke "i64.div_u" (i64.const 0x8000000000000000) (i64.const 0x2)) (i64.const 4611686018427387904))
+(assert_return (invoke "i64.div_u" (i64.const 0xffffffffffffffff) (i64.const 0x1)) (i64.const -1))
+(assert_return (invoke "i64.div_u" (i64.const 0xffffffffffffffff) (i64.const 0xffffffffffffffff)) (i64.const 1))
+(assert_return (invoke "i64.div_u" (i64.const 0xffffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 1))
+(assert_return (invoke "i64.div_u" (i64.const 0x7) (i64.const 0xffffffffffffffff)) (i64.const 0))
+(assert_return (invoke "i64.div_u" (i64.const 0xffffffffffffffff) (i64.const 0xa)) (i64.const 1844674407370955161))
+(assert_return (invoke "i64.div_u" (i64.const 0x8000000000000001) (i64.const 0x3)) (i64.const 3074457345618258603))
+(assert_trap (invoke "i64.div_u" (i64.const 0xffffffffffffffff) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "i64.rem_u" (i64.const 0x8000000000000000) (i64.const 0x2)) (i64.const 0))
+(assert_return (invoke "i64.rem_u" (i64.const 0xffffffffffffffff) (i64.const 0x1)) (i64.const 0))
+(assert_return (invoke "i64.rem_u" (i64.const 0xffffffffffffffff) (i64.const 0xffffffffffffffff)) (i64.const 0))
+(assert_return (invoke "i64.rem_u" (i64.const 0xffffffffffffffff) (i64.const 0x8000000000000000)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.rem_u" (i64.const 0x7) (i64.const 0xffffffffffffffff)) (i64.const 7))
+(assert_return (invoke "i64.rem_u" (i64.const 0xffffffffffffffff) (i64.const 0xa)) (i64.const 5))
+(assert_return (invoke "i64.rem_u" (i64.const 0x8000000000000001) (i64.const 0x3)) (i64.const 0))
+(assert_trap (invoke "i64.rem_u" (i64.const 0xffffffffffffffff) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "i64.div_u_const" (i64.const 0x8000000000000000)) (i64.const 4611686018427387904))
+(assert_return (invoke "i64.div_u_const" (i64.const 0xffffffffffffffff)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.lt_u_const" (i64.const 0xffffffffffffffff)) (i32.const 0))
+(assert_return (invoke "i64.lt_u_const" (i64.const 0)) (i32.const 1))
+
+;; The same with constant operands, which may be folded before running
+(assert_return (invoke "fold_lt") (i32.const 0))
+(assert_return (invoke "fold_div") (i32.const 1073741824))
+(assert_return (invoke "fold_div64") (i64.const 4611686018427387904))
+(assert_return (invoke "fold_rem64") (i64.const 5))
@@ -0,0 +1,73 @@
+use std::fmt::{Display, Formatter};
+
+use wasmparser::{BinaryReaderError, Operator};
+
+#[derive(Debug)]
+pub enum Error {
+	Parse(BinaryReaderError),
+	UnsupportedSimd(String),
+	Unsupported(String),
+	Malformed(String),
+}
+
+impl Error {
+	// Only the variant name is kept, the immediates are rarely
+	// helpful and can be very long
+	pub(crate) fn unsupported(op: &Operator) -> Self {
+		let debug = format!("{op:?}");
+		let name = debug
+			.split(|v: char| !v.is_ascii_alphanumeric())
+			.next()
+			.unwrap_or_default()
+			.to_string();
+
+		let is_simd = ["V128", "I8x16", "I16x8", "I32x4", "I64x2", "F32x4", "F64x2"]
+			.iter()
+			.any(|v| name.starts_with(v));
+
+		if is_simd {
+			Self::UnsupportedSimd(name)
+		} else {
+			Self::Unsupported(name)
+		}
+	}
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Parse(error) => error.fmt(f),
+			Self::UnsupportedSimd(name) => write!(f, "unsupported SIMD op `{name}`"),
+			Self::Unsupported(name) => write!(f, "unsupported instruction `{name}`"),
+			Self::Malformed(reason) => write!(f, "malformed code: {reason}"),
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Parse(error) => Some(error),
+			Self::UnsupportedSimd(_) | Self::Unsupported(_) | Self::Malformed(_) => None,
+		}
+	}
+}
+
+impl From<BinaryReaderError> for Error {
+	fn from(error: BinaryReaderError) -> Self {
+		Self::Parse(error)
+	}
+}
+
+impl From<Error> for std::io::Error {
+	fn from(error: Error) -> Self {
+		let kind = match error {
+			Error::Parse(_) | Error::Malformed(_) => std::io::ErrorKind::InvalidData,
+			Error::UnsupportedSimd(_) | Error::Unsupported(_) => std::io::ErrorKind::Unsupported,
+		};
+
+		Self::new(kind, error)
+	}
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
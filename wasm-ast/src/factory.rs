@@ -1,12 +1,15 @@
-use wasmparser::{BlockType, FunctionBody, MemArg, Operator, Result};
+use wasmparser::{BlockType, FunctionBody, MemArg, Operator};
 
 use crate::{
+	error::{Error, Result},
 	module::{read_checked, read_checked_locals, TypeInfo},
 	node::{
-		BinOp, BinOpType, Block, Br, BrIf, BrTable, Call, CallIndirect, CmpOp, CmpOpType,
-		Expression, FuncData, GetGlobal, If, LabelType, LoadAt, LoadType, Local, MemoryArgument,
-		MemoryCopy, MemoryFill, MemoryGrow, MemorySize, Select, SetGlobal, SetLocal, Statement,
-		StoreAt, StoreType, Terminator, UnOp, UnOpType, Value,
+		BinOp, BinOpType, Block, Br, BrIf, BrTable, Call, CallIndirect, CmpOp, CmpOpType, DataDrop,
+		ElemDrop, Expression, ExtractLane, ExtractLaneType, FuncData, GetGlobal, If, LabelType,
+		LoadAt, LoadType, Local, MemoryArgument, MemoryCopy, MemoryFill, MemoryGrow, MemoryInit,
+		MemorySize, RefIsNull, ReplaceLane, ReplaceLaneType, ResultList, Select, SetGlobal,
+		SetLocal, SetTemporary, Statement, StoreAt, StoreType, TableCopy, TableFill, TableGet,
+		TableGrow, TableInit, TableSet, TableSize, Temporary, Terminator, UnOp, UnOpType, Value,
 	},
 	stack::{ReadGet, Stack},
 };
@@ -47,8 +50,10 @@ impl From<BlockData> for LabelType {
 struct StatList {
 	stack: Stack,
 	code: Vec<Statement>,
+	offsets: Vec<usize>,
 	last: Option<Box<Terminator>>,
 
+	position: usize,
 	block_data: BlockData,
 	has_reference: bool,
 }
@@ -58,31 +63,51 @@ impl StatList {
 		Self::default()
 	}
 
+	// Statements are pushed from many places, including the stack, so their
+	// offsets are filled in lazily before the position moves on
+	fn sync_offsets(&mut self) {
+		let position = self.position;
+
+		self.offsets.resize(self.code.len(), position);
+	}
+
 	fn leak_all(&mut self) {
 		self.stack.leak_into(&mut self.code, |_| true);
 	}
 
 	fn leak_pre_call(&mut self) {
 		self.stack.leak_into(&mut self.code, |node| {
-			ReadGet::run(node, |_| false, |_| true, |_| true)
+			ReadGet::run(node, |_| false, |_| true, |_| true, |_| true)
 		});
 	}
 
 	fn leak_local_write(&mut self, id: usize) {
 		self.stack.leak_into(&mut self.code, |node| {
-			ReadGet::run(node, |var| var.var() == id, |_| false, |_| false)
+			ReadGet::run(node, |var| var.var() == id, |_| false, |_| false, |_| false)
 		});
 	}
 
 	fn leak_global_write(&mut self, id: usize) {
 		self.stack.leak_into(&mut self.code, |node| {
-			ReadGet::run(node, |_| false, |var| var.var() == id, |_| false)
+			ReadGet::run(node, |_| false, |var| var.var() == id, |_| false, |_| false)
 		});
 	}
 
 	fn leak_memory_write(&mut self, id: usize) {
 		self.stack.leak_into(&mut self.code, |node| {
-			ReadGet::run(node, |_| false, |_| false, |var| var.memory() == id)
+			ReadGet::run(
+				node,
+				|_| false,
+				|_| false,
+				|var| var.memory() == id,
+				|_| false,
+			)
+		});
+	}
+
+	fn leak_table_write(&mut self, id: usize) {
+		self.stack.leak_into(&mut self.code, |node| {
+			ReadGet::run(node, |_| false, |_| false, |_| false, |table| table == id)
 		});
 	}
 
@@ -116,6 +141,195 @@ impl StatList {
 		self.code.push(data);
 	}
 
+	// Atomic operations read some operands more than once, so these are
+	// leaked beforehand and only ever refer to their own temporaries
+	fn pop_temporary(&mut self) -> Temporary {
+		match self.stack.pop() {
+			Expression::GetTemporary(temporary) => temporary,
+			_ => unreachable!("atomic operand should be leaked"),
+		}
+	}
+
+	// There is only a single thread of execution, so the read-modify-write
+	// is lowered to a plain load and a store with the old value as result
+	fn add_atomic_rmw(
+		&mut self,
+		load_type: LoadType,
+		store_type: StoreType,
+		op_type: Option<BinOpType>,
+		memarg: MemArg,
+	) {
+		let memory = memarg.memory.try_into().unwrap();
+		let offset = memarg.offset.try_into().unwrap();
+
+		self.leak_all();
+
+		let old = self.stack.push_temporary();
+
+		self.stack.pop();
+
+		let value = self.pop_temporary();
+		let pointer = self.pop_temporary();
+		let result = self.stack.push_temporary();
+
+		let load = Statement::SetTemporary(SetTemporary {
+			var: old,
+			value: Expression::LoadAt(LoadAt {
+				load_type,
+				memory,
+				offset,
+				pointer: Expression::GetTemporary(pointer).into(),
+			})
+			.into(),
+		});
+
+		let value = match op_type {
+			Some(op_type) => Expression::BinOp(BinOp {
+				op_type,
+				lhs: Expression::GetTemporary(old).into(),
+				rhs: Expression::GetTemporary(value).into(),
+			}),
+			None => Expression::GetTemporary(value),
+		};
+
+		let store = Statement::StoreAt(StoreAt {
+			store_type,
+			memory,
+			offset,
+			pointer: Expression::GetTemporary(pointer).into(),
+			value: value.into(),
+		});
+
+		let set = Statement::SetTemporary(SetTemporary {
+			var: result,
+			value: Expression::GetTemporary(old).into(),
+		});
+
+		self.code.extend([load, store, set]);
+	}
+
+	// The expected operand is compared at the width of the access, so narrow
+	// forms mask it first as the loaded value is already zero extended
+	fn add_atomic_cmpxchg(&mut self, load_type: LoadType, store_type: StoreType, memarg: MemArg) {
+		let memory = memarg.memory.try_into().unwrap();
+		let offset = memarg.offset.try_into().unwrap();
+
+		self.leak_all();
+
+		let old = self.stack.push_temporary();
+
+		self.stack.pop();
+
+		let replacement = self.pop_temporary();
+		let expected = self.pop_temporary();
+		let pointer = self.pop_temporary();
+		let result = self.stack.push_temporary();
+
+		let load = Statement::SetTemporary(SetTemporary {
+			var: old,
+			value: Expression::LoadAt(LoadAt {
+				load_type,
+				memory,
+				offset,
+				pointer: Expression::GetTemporary(pointer).into(),
+			})
+			.into(),
+		});
+
+		let mask = match store_type {
+			StoreType::I32_N8 => Some((BinOpType::And_I32, Value::I32(0xFF))),
+			StoreType::I32_N16 => Some((BinOpType::And_I32, Value::I32(0xFFFF))),
+			StoreType::I64_N8 => Some((BinOpType::And_I64, Value::I64(0xFF))),
+			StoreType::I64_N16 => Some((BinOpType::And_I64, Value::I64(0xFFFF))),
+			StoreType::I64_N32 => Some((BinOpType::And_I64, Value::I64(0xFFFF_FFFF))),
+			_ => None,
+		};
+
+		let expected = match mask {
+			Some((op_type, value)) => Expression::BinOp(BinOp {
+				op_type,
+				lhs: Expression::GetTemporary(expected).into(),
+				rhs: Expression::Value(value).into(),
+			}),
+			None => Expression::GetTemporary(expected),
+		};
+
+		let op_type = match load_type {
+			LoadType::I32 | LoadType::I32_U8 | LoadType::I32_U16 => CmpOpType::Eq_I32,
+			_ => CmpOpType::Eq_I64,
+		};
+
+		let store = Statement::StoreAt(StoreAt {
+			store_type,
+			memory,
+			offset,
+			pointer: Expression::GetTemporary(pointer).into(),
+			value: Expression::GetTemporary(replacement).into(),
+		});
+
+		let branch = Statement::If(If {
+			condition: Expression::CmpOp(CmpOp {
+				op_type,
+				lhs: Expression::GetTemporary(old).into(),
+				rhs: expected.into(),
+			})
+			.into(),
+			on_true: Block {
+				label_type: None,
+				code: vec![store],
+				offsets: vec![self.position],
+				last: None,
+			}
+			.into(),
+			on_false: None,
+		});
+
+		let set = Statement::SetTemporary(SetTemporary {
+			var: result,
+			value: Expression::GetTemporary(old).into(),
+		});
+
+		self.code.extend([load, branch, set]);
+	}
+
+	// No other thread could ever wake us up, so waiting returns at once with
+	// either "not-equal" or "timed-out"
+	fn push_atomic_wait(&mut self, load_type: LoadType, op_type: CmpOpType, memarg: MemArg) {
+		let memory = memarg.memory.try_into().unwrap();
+		let offset = memarg.offset.try_into().unwrap();
+
+		self.stack.pop();
+
+		let expected = self.stack.pop().into();
+		let load = Expression::LoadAt(LoadAt {
+			load_type,
+			memory,
+			offset,
+			pointer: self.stack.pop().into(),
+		});
+
+		let data = Expression::Select(Select {
+			ty: None,
+			condition: Expression::CmpOp(CmpOp {
+				op_type,
+				lhs: load.into(),
+				rhs: expected,
+			})
+			.into(),
+			on_true: Expression::Value(Value::I32(1)).into(),
+			on_false: Expression::Value(Value::I32(2)).into(),
+		});
+
+		self.stack.push(data);
+	}
+
+	// There are never any waiters, so nobody is woken up
+	fn push_atomic_notify(&mut self) {
+		self.stack.pop();
+		self.stack.pop();
+		self.push_constant(0_i32);
+	}
+
 	fn push_constant<T: Into<Value>>(&mut self, value: T) {
 		let value = Expression::Value(value.into());
 
@@ -151,6 +365,27 @@ impl StatList {
 		self.stack.push(data);
 	}
 
+	fn push_extract_lane(&mut self, op_type: ExtractLaneType, lane: u8) {
+		let data = Expression::ExtractLane(ExtractLane {
+			op_type,
+			lane,
+			rhs: self.stack.pop().into(),
+		});
+
+		self.stack.push(data);
+	}
+
+	fn push_replace_lane(&mut self, op_type: ReplaceLaneType, lane: u8) {
+		let data = Expression::ReplaceLane(ReplaceLane {
+			op_type,
+			lane,
+			rhs: self.stack.pop().into(),
+			lhs: self.stack.pop().into(),
+		});
+
+		self.stack.push(data);
+	}
+
 	// Eqz is the only unary comparison so it's "emulated"
 	// using a constant operand
 	fn try_add_equal_zero(&mut self, op: &Operator) -> bool {
@@ -197,12 +432,15 @@ impl StatList {
 }
 
 impl From<StatList> for Block {
-	fn from(stat: StatList) -> Self {
+	fn from(mut stat: StatList) -> Self {
+		stat.sync_offsets();
+
 		let label_type = stat.has_reference.then(|| stat.block_data.into());
 
 		Self {
 			label_type,
 			code: stat.code,
+			offsets: stat.offsets,
 			last: stat.last,
 		}
 	}
@@ -228,30 +466,41 @@ impl<'a> Factory<'a> {
 		}
 	}
 
-	#[must_use]
-	pub fn create_anonymous(&mut self, list: &[Operator]) -> FuncData {
-		let data = self.build_stat_list(list, 1);
+	/// # Errors
+	///
+	/// Returns an error if the code is malformed or contains an
+	/// unsupported instruction.
+	pub fn create_anonymous(&mut self, list: &[Operator]) -> Result<FuncData> {
+		let data = self.build_stat_list(list, &[], 1)?;
 
-		FuncData {
+		Ok(FuncData {
+			param_data: Vec::new(),
 			local_data: Vec::new(),
 			num_result: 1,
 			num_param: 0,
 			num_stack: data.stack.capacity,
 			code: data.into(),
-		}
+		})
 	}
 
 	/// # Errors
 	///
-	/// Returns an error if the function is malformed.
+	/// Returns an error if the function is malformed or contains an
+	/// unsupported instruction.
 	pub fn create_indexed(&mut self, index: usize, func: &FunctionBody) -> Result<FuncData> {
-		let code = read_checked(func.get_operators_reader()?)?;
+		let (code, offsets): (Vec<_>, Vec<_>) =
+			read_checked(func.get_operators_reader()?.into_iter_with_offsets())?
+				.into_iter()
+				.unzip();
+
 		let local_data = read_checked_locals(func.get_locals_reader()?)?;
 
 		let (num_param, num_result) = self.type_info.by_func_index(index);
-		let data = self.build_stat_list(&code, num_result);
+		let param_data = self.type_info.func_type(index).params().to_vec();
+		let data = self.build_stat_list(&code, &offsets, num_result)?;
 
 		Ok(FuncData {
+			param_data,
 			local_data,
 			num_result,
 			num_param,
@@ -260,7 +509,15 @@ impl<'a> Factory<'a> {
 		})
 	}
 
-	fn start_block(&mut self, ty: BlockType, variant: BlockVariant) {
+	fn start_block(&mut self, ty: BlockType, variant: BlockVariant) -> Result<()> {
+		if let BlockType::FuncType(index) = ty {
+			if !self.type_info.has_func_type(index.try_into().unwrap()) {
+				let reason = format!("block type refers to missing type {index}");
+
+				return Err(Error::Malformed(reason));
+			}
+		}
+
 		let (num_param, num_result) = self.type_info.by_block_type(ty);
 		let mut old = std::mem::take(&mut self.target);
 
@@ -283,20 +540,25 @@ impl<'a> Factory<'a> {
 		old.stack.push_temporaries(num_result);
 
 		self.pending.push(old);
+
+		Ok(())
 	}
 
-	fn start_else(&mut self) {
+	fn start_else(&mut self) -> Result<()> {
 		let BlockData::If { ty, .. } = self.target.block_data else {
-			unreachable!()
+			return Err(Error::Malformed("`else` outside of an `if` block".into()));
 		};
 
 		self.target.leak_all();
-		self.end_block();
-		self.start_block(ty, BlockVariant::Else);
+		self.end_block()?;
+		self.start_block(ty, BlockVariant::Else)
 	}
 
-	fn end_block(&mut self) {
-		let old = self.pending.pop().unwrap();
+	fn end_block(&mut self) -> Result<()> {
+		let Some(old) = self.pending.pop() else {
+			return Err(Error::Malformed("`end` without a matching block".into()));
+		};
+
 		let now = std::mem::replace(&mut self.target, old);
 
 		self.target.stack.capacity = now.stack.capacity;
@@ -315,11 +577,13 @@ impl<'a> Factory<'a> {
 
 				last.on_false = Some(Box::new(now.into()));
 
-				return;
+				return Ok(());
 			}
 		};
 
 		self.target.code.push(stat);
+
+		Ok(())
 	}
 
 	fn get_relative_block(&mut self, index: usize) -> &mut StatList {
@@ -332,7 +596,13 @@ impl<'a> Factory<'a> {
 		}
 	}
 
-	fn get_br_terminator(&mut self, target: usize) -> Br {
+	fn get_br_terminator(&mut self, target: usize) -> Result<Br> {
+		if target > self.pending.len() {
+			let reason = format!("branch depth {target} is past the outermost label");
+
+			return Err(Error::Malformed(reason));
+		}
+
 		let block = self.get_relative_block(target);
 		let previous = block.stack.previous;
 		let result = match block.block_data {
@@ -346,10 +616,26 @@ impl<'a> Factory<'a> {
 
 		let align = self.target.stack.get_br_alignment(previous, result);
 
-		Br { target, align }
+		Ok(Br { target, align })
+	}
+
+	fn check_index(exists: bool, kind: &str, index: usize) -> Result<()> {
+		if exists {
+			Ok(())
+		} else {
+			let reason = format!("call refers to missing {kind} {index}");
+
+			Err(Error::Malformed(reason))
+		}
 	}
 
-	fn add_call(&mut self, function: usize) {
+	fn add_call(&mut self, function: usize) -> Result<()> {
+		Self::check_index(
+			self.type_info.has_func_index(function),
+			"function",
+			function,
+		)?;
+
 		let (num_param, num_result) = self.type_info.by_func_index(function);
 		let param_list = self.target.stack.pop_len(num_param).collect();
 
@@ -364,9 +650,13 @@ impl<'a> Factory<'a> {
 		});
 
 		self.target.code.push(data);
+
+		Ok(())
 	}
 
-	fn add_call_indirect(&mut self, ty: usize, table: usize) {
+	fn add_call_indirect(&mut self, ty: usize, table: usize) -> Result<()> {
+		Self::check_index(self.type_info.has_func_type(ty), "type", ty)?;
+
 		let (num_param, num_result) = self.type_info.by_type_index(ty);
 		let index = self.target.stack.pop().into();
 		let param_list = self.target.stack.pop_len(num_param).collect();
@@ -376,6 +666,7 @@ impl<'a> Factory<'a> {
 		let result_list = self.target.stack.push_temporaries(num_result);
 
 		let data = Statement::CallIndirect(CallIndirect {
+			ty,
 			table,
 			index,
 			param_list,
@@ -383,10 +674,57 @@ impl<'a> Factory<'a> {
 		});
 
 		self.target.code.push(data);
+
+		Ok(())
+	}
+
+	// Tail calls hand their results straight back to the caller,
+	// so nothing is pushed and the rest of the block is dead
+	fn add_return_call(&mut self, function: usize) -> Result<()> {
+		Self::check_index(
+			self.type_info.has_func_index(function),
+			"function",
+			function,
+		)?;
+
+		let (num_param, _) = self.type_info.by_func_index(function);
+		let param_list = self.target.stack.pop_len(num_param).collect();
+
+		let term = Terminator::ReturnCall(Call {
+			function,
+			param_list,
+			result_list: ResultList::new(0, 0),
+		});
+
+		self.target.set_terminator(term);
+		self.nested_unreachable += 1;
+
+		Ok(())
+	}
+
+	fn add_return_call_indirect(&mut self, ty: usize, table: usize) -> Result<()> {
+		Self::check_index(self.type_info.has_func_type(ty), "type", ty)?;
+
+		let (num_param, _) = self.type_info.by_type_index(ty);
+		let index = self.target.stack.pop().into();
+		let param_list = self.target.stack.pop_len(num_param).collect();
+
+		let term = Terminator::ReturnCallIndirect(CallIndirect {
+			ty,
+			table,
+			index,
+			param_list,
+			result_list: ResultList::new(0, 0),
+		});
+
+		self.target.set_terminator(term);
+		self.nested_unreachable += 1;
+
+		Ok(())
 	}
 
 	#[cold]
-	fn drop_unreachable(&mut self, op: &Operator) {
+	fn drop_unreachable(&mut self, op: &Operator) -> Result<()> {
 		match op {
 			Operator::Block { .. } | Operator::Loop { .. } | Operator::If { .. } => {
 				self.nested_unreachable += 1;
@@ -394,24 +732,26 @@ impl<'a> Factory<'a> {
 			Operator::Else if self.nested_unreachable == 1 => {
 				self.nested_unreachable -= 1;
 
-				self.start_else();
+				self.start_else()?;
 			}
 			Operator::End if self.nested_unreachable == 1 => {
 				self.nested_unreachable -= 1;
 
-				self.end_block();
+				self.end_block()?;
 			}
 			Operator::End => {
 				self.nested_unreachable -= 1;
 			}
 			_ => {}
 		}
+
+		Ok(())
 	}
 
 	#[allow(clippy::too_many_lines)]
-	fn add_instruction(&mut self, op: &Operator) {
+	fn add_instruction(&mut self, op: &Operator) -> Result<()> {
 		if self.target.try_add_operation(op) {
-			return;
+			return Ok(());
 		}
 
 		match *op {
@@ -422,27 +762,27 @@ impl<'a> Factory<'a> {
 			}
 			Operator::Nop => {}
 			Operator::Block { blockty } => {
-				self.start_block(blockty, BlockVariant::Forward);
+				self.start_block(blockty, BlockVariant::Forward)?;
 			}
 			Operator::Loop { blockty } => {
-				self.start_block(blockty, BlockVariant::Backward);
+				self.start_block(blockty, BlockVariant::Backward)?;
 			}
 			Operator::If { blockty } => {
 				let cond = self.target.stack.pop();
 
-				self.start_block(blockty, BlockVariant::If);
+				self.start_block(blockty, BlockVariant::If)?;
 				self.pending.last_mut().unwrap().stack.push(cond);
 			}
 			Operator::Else => {
-				self.start_else();
+				self.start_else()?;
 			}
 			Operator::End => {
 				self.target.leak_all();
-				self.end_block();
+				self.end_block()?;
 			}
 			Operator::Br { relative_depth } => {
 				let target = relative_depth.try_into().unwrap();
-				let term = Terminator::Br(self.get_br_terminator(target));
+				let term = Terminator::Br(self.get_br_terminator(target)?);
 
 				self.target.set_terminator(term);
 				self.nested_unreachable += 1;
@@ -451,7 +791,7 @@ impl<'a> Factory<'a> {
 				let target = relative_depth.try_into().unwrap();
 				let data = Statement::BrIf(BrIf {
 					condition: self.target.stack.pop().into(),
-					target: self.get_br_terminator(target),
+					target: self.get_br_terminator(target)?,
 				});
 
 				self.target.leak_all();
@@ -461,11 +801,10 @@ impl<'a> Factory<'a> {
 				let condition = self.target.stack.pop().into();
 				let data = targets
 					.targets()
-					.map(Result::unwrap)
-					.map(|v| self.get_br_terminator(v.try_into().unwrap()))
-					.collect();
+					.map(|v| self.get_br_terminator(v?.try_into().unwrap()))
+					.collect::<Result<_>>()?;
 
-				let default = self.get_br_terminator(targets.default().try_into().unwrap());
+				let default = self.get_br_terminator(targets.default().try_into().unwrap())?;
 
 				let term = Terminator::BrTable(BrTable {
 					condition,
@@ -478,7 +817,7 @@ impl<'a> Factory<'a> {
 			}
 			Operator::Return => {
 				let target = self.pending.len();
-				let term = Terminator::Br(self.get_br_terminator(target));
+				let term = Terminator::Br(self.get_br_terminator(target)?);
 
 				self.target.set_terminator(term);
 				self.nested_unreachable += 1;
@@ -486,7 +825,7 @@ impl<'a> Factory<'a> {
 			Operator::Call { function_index } => {
 				let index = function_index.try_into().unwrap();
 
-				self.add_call(index);
+				self.add_call(index)?;
 			}
 			Operator::CallIndirect {
 				type_index,
@@ -496,13 +835,148 @@ impl<'a> Factory<'a> {
 				let type_index = type_index.try_into().unwrap();
 				let table_index = table_index.try_into().unwrap();
 
-				self.add_call_indirect(type_index, table_index);
+				self.add_call_indirect(type_index, table_index)?;
+			}
+			Operator::ReturnCall { function_index } => {
+				let index = function_index.try_into().unwrap();
+
+				self.add_return_call(index)?;
+			}
+			Operator::ReturnCallIndirect {
+				type_index,
+				table_index,
+			} => {
+				let type_index = type_index.try_into().unwrap();
+				let table_index = table_index.try_into().unwrap();
+
+				self.add_return_call_indirect(type_index, table_index)?;
+			}
+			Operator::RefNull { .. } => {
+				let data = Expression::Value(Value::RefNull);
+
+				self.target.stack.push(data);
+			}
+			Operator::RefFunc { function_index } => {
+				let function = function_index.try_into().unwrap();
+				let data = Expression::Value(Value::RefFunc(function));
+
+				self.target.stack.push(data);
+			}
+			Operator::RefIsNull => {
+				let data = Expression::RefIsNull(RefIsNull {
+					value: self.target.stack.pop().into(),
+				});
+
+				self.target.stack.push(data);
+			}
+			Operator::TableGet { table } => {
+				let data = Expression::TableGet(TableGet {
+					table: table.try_into().unwrap(),
+					index: self.target.stack.pop().into(),
+				});
+
+				self.target.stack.push(data);
+			}
+			Operator::TableSet { table } => {
+				let table = table.try_into().unwrap();
+				let value = self.target.stack.pop().into();
+				let data = Statement::TableSet(TableSet {
+					table,
+					index: self.target.stack.pop().into(),
+					value,
+				});
+
+				self.target.leak_table_write(table);
+				self.target.code.push(data);
+			}
+			Operator::TableInit { elem_index, table } => {
+				let table = table.try_into().unwrap();
+				let size = self.target.stack.pop().into();
+				let offset = self.target.stack.pop().into();
+				let data = Statement::TableInit(TableInit {
+					table,
+					element: elem_index.try_into().unwrap(),
+					destination: self.target.stack.pop().into(),
+					offset,
+					size,
+				});
+
+				self.target.leak_table_write(table);
+				self.target.code.push(data);
+			}
+			Operator::TableGrow { table } => {
+				let table = table.try_into().unwrap();
+				let size = self.target.stack.pop().into();
+				let value = self.target.stack.pop().into();
+				let result = self.target.stack.push_temporary();
+
+				let data = Statement::TableGrow(TableGrow {
+					table,
+					result,
+					value,
+					size,
+				});
+
+				self.target.leak_table_write(table);
+				self.target.code.push(data);
+			}
+			Operator::TableSize { table } => {
+				let data = Expression::TableSize(TableSize {
+					table: table.try_into().unwrap(),
+				});
+
+				self.target.stack.push(data);
+			}
+			Operator::TableFill { table } => {
+				let table = table.try_into().unwrap();
+				let size = self.target.stack.pop().into();
+				let value = self.target.stack.pop().into();
+				let data = Statement::TableFill(TableFill {
+					table,
+					destination: self.target.stack.pop().into(),
+					value,
+					size,
+				});
+
+				self.target.leak_table_write(table);
+				self.target.code.push(data);
+			}
+			Operator::TableCopy {
+				dst_table,
+				src_table,
+			} => {
+				let destination_table = dst_table.try_into().unwrap();
+				let size = self.target.stack.pop().into();
+				let source = self.target.stack.pop().into();
+				let data = Statement::TableCopy(TableCopy {
+					destination_table,
+					source_table: src_table.try_into().unwrap(),
+					destination: self.target.stack.pop().into(),
+					source,
+					size,
+				});
+
+				self.target.leak_table_write(destination_table);
+				self.target.code.push(data);
+			}
+			Operator::ElemDrop { elem_index } => {
+				let data = Statement::ElemDrop(ElemDrop {
+					element: elem_index.try_into().unwrap(),
+				});
+
+				self.target.code.push(data);
 			}
 			Operator::Drop => {
 				self.target.stack.pop();
 			}
-			Operator::Select => {
+			Operator::Select | Operator::TypedSelect { .. } => {
+				let ty = match *op {
+					Operator::TypedSelect { ty } => Some(ty),
+					_ => None,
+				};
+
 				let data = Expression::Select(Select {
+					ty,
 					condition: self.target.stack.pop().into(),
 					on_false: self.target.stack.pop().into(),
 					on_true: self.target.stack.pop().into(),
@@ -577,6 +1051,384 @@ impl<'a> Factory<'a> {
 			Operator::I64Store8 { memarg } => self.target.add_store(StoreType::I64_N8, memarg),
 			Operator::I64Store16 { memarg } => self.target.add_store(StoreType::I64_N16, memarg),
 			Operator::I64Store32 { memarg } => self.target.add_store(StoreType::I64_N32, memarg),
+			Operator::V128Load { memarg } => self.target.push_load(LoadType::V128, memarg),
+			Operator::V128Store { memarg } => self.target.add_store(StoreType::V128, memarg),
+			Operator::I32AtomicLoad { memarg } => self.target.push_load(LoadType::I32, memarg),
+			Operator::I64AtomicLoad { memarg } => self.target.push_load(LoadType::I64, memarg),
+			Operator::I32AtomicLoad8U { memarg } => self.target.push_load(LoadType::I32_U8, memarg),
+			Operator::I32AtomicLoad16U { memarg } => {
+				self.target.push_load(LoadType::I32_U16, memarg)
+			}
+			Operator::I64AtomicLoad8U { memarg } => self.target.push_load(LoadType::I64_U8, memarg),
+			Operator::I64AtomicLoad16U { memarg } => {
+				self.target.push_load(LoadType::I64_U16, memarg)
+			}
+			Operator::I64AtomicLoad32U { memarg } => {
+				self.target.push_load(LoadType::I64_U32, memarg)
+			}
+			Operator::I32AtomicStore { memarg } => self.target.add_store(StoreType::I32, memarg),
+			Operator::I64AtomicStore { memarg } => self.target.add_store(StoreType::I64, memarg),
+			Operator::I32AtomicStore8 { memarg } => {
+				self.target.add_store(StoreType::I32_N8, memarg)
+			}
+			Operator::I32AtomicStore16 { memarg } => {
+				self.target.add_store(StoreType::I32_N16, memarg)
+			}
+			Operator::I64AtomicStore8 { memarg } => {
+				self.target.add_store(StoreType::I64_N8, memarg)
+			}
+			Operator::I64AtomicStore16 { memarg } => {
+				self.target.add_store(StoreType::I64_N16, memarg)
+			}
+			Operator::I64AtomicStore32 { memarg } => {
+				self.target.add_store(StoreType::I64_N32, memarg)
+			}
+			Operator::I32AtomicRmwAdd { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32,
+					StoreType::I32,
+					Some(BinOpType::Add_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmwAdd { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64,
+					StoreType::I64,
+					Some(BinOpType::Add_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw8AddU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U8,
+					StoreType::I32_N8,
+					Some(BinOpType::Add_I32),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw16AddU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U16,
+					StoreType::I32_N16,
+					Some(BinOpType::Add_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw8AddU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U8,
+					StoreType::I64_N8,
+					Some(BinOpType::Add_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw16AddU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U16,
+					StoreType::I64_N16,
+					Some(BinOpType::Add_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw32AddU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U32,
+					StoreType::I64_N32,
+					Some(BinOpType::Add_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmwSub { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32,
+					StoreType::I32,
+					Some(BinOpType::Sub_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmwSub { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64,
+					StoreType::I64,
+					Some(BinOpType::Sub_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw8SubU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U8,
+					StoreType::I32_N8,
+					Some(BinOpType::Sub_I32),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw16SubU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U16,
+					StoreType::I32_N16,
+					Some(BinOpType::Sub_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw8SubU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U8,
+					StoreType::I64_N8,
+					Some(BinOpType::Sub_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw16SubU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U16,
+					StoreType::I64_N16,
+					Some(BinOpType::Sub_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw32SubU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U32,
+					StoreType::I64_N32,
+					Some(BinOpType::Sub_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmwAnd { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32,
+					StoreType::I32,
+					Some(BinOpType::And_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmwAnd { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64,
+					StoreType::I64,
+					Some(BinOpType::And_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw8AndU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U8,
+					StoreType::I32_N8,
+					Some(BinOpType::And_I32),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw16AndU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U16,
+					StoreType::I32_N16,
+					Some(BinOpType::And_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw8AndU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U8,
+					StoreType::I64_N8,
+					Some(BinOpType::And_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw16AndU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U16,
+					StoreType::I64_N16,
+					Some(BinOpType::And_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw32AndU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U32,
+					StoreType::I64_N32,
+					Some(BinOpType::And_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmwOr { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32,
+					StoreType::I32,
+					Some(BinOpType::Or_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmwOr { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64,
+					StoreType::I64,
+					Some(BinOpType::Or_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw8OrU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U8,
+					StoreType::I32_N8,
+					Some(BinOpType::Or_I32),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw16OrU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U16,
+					StoreType::I32_N16,
+					Some(BinOpType::Or_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw8OrU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U8,
+					StoreType::I64_N8,
+					Some(BinOpType::Or_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw16OrU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U16,
+					StoreType::I64_N16,
+					Some(BinOpType::Or_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw32OrU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U32,
+					StoreType::I64_N32,
+					Some(BinOpType::Or_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmwXor { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32,
+					StoreType::I32,
+					Some(BinOpType::Xor_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmwXor { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64,
+					StoreType::I64,
+					Some(BinOpType::Xor_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw8XorU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U8,
+					StoreType::I32_N8,
+					Some(BinOpType::Xor_I32),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmw16XorU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I32_U16,
+					StoreType::I32_N16,
+					Some(BinOpType::Xor_I32),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw8XorU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U8,
+					StoreType::I64_N8,
+					Some(BinOpType::Xor_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw16XorU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U16,
+					StoreType::I64_N16,
+					Some(BinOpType::Xor_I64),
+					memarg,
+				);
+			}
+			Operator::I64AtomicRmw32XorU { memarg } => {
+				self.target.add_atomic_rmw(
+					LoadType::I64_U32,
+					StoreType::I64_N32,
+					Some(BinOpType::Xor_I64),
+					memarg,
+				);
+			}
+			Operator::I32AtomicRmwXchg { memarg } => {
+				self.target
+					.add_atomic_rmw(LoadType::I32, StoreType::I32, None, memarg);
+			}
+			Operator::I64AtomicRmwXchg { memarg } => {
+				self.target
+					.add_atomic_rmw(LoadType::I64, StoreType::I64, None, memarg);
+			}
+			Operator::I32AtomicRmw8XchgU { memarg } => {
+				self.target
+					.add_atomic_rmw(LoadType::I32_U8, StoreType::I32_N8, None, memarg);
+			}
+			Operator::I32AtomicRmw16XchgU { memarg } => {
+				self.target
+					.add_atomic_rmw(LoadType::I32_U16, StoreType::I32_N16, None, memarg);
+			}
+			Operator::I64AtomicRmw8XchgU { memarg } => {
+				self.target
+					.add_atomic_rmw(LoadType::I64_U8, StoreType::I64_N8, None, memarg);
+			}
+			Operator::I64AtomicRmw16XchgU { memarg } => {
+				self.target
+					.add_atomic_rmw(LoadType::I64_U16, StoreType::I64_N16, None, memarg);
+			}
+			Operator::I64AtomicRmw32XchgU { memarg } => {
+				self.target
+					.add_atomic_rmw(LoadType::I64_U32, StoreType::I64_N32, None, memarg);
+			}
+			Operator::I32AtomicRmwCmpxchg { memarg } => {
+				self.target
+					.add_atomic_cmpxchg(LoadType::I32, StoreType::I32, memarg);
+			}
+			Operator::I64AtomicRmwCmpxchg { memarg } => {
+				self.target
+					.add_atomic_cmpxchg(LoadType::I64, StoreType::I64, memarg);
+			}
+			Operator::I32AtomicRmw8CmpxchgU { memarg } => {
+				self.target
+					.add_atomic_cmpxchg(LoadType::I32_U8, StoreType::I32_N8, memarg);
+			}
+			Operator::I32AtomicRmw16CmpxchgU { memarg } => {
+				self.target
+					.add_atomic_cmpxchg(LoadType::I32_U16, StoreType::I32_N16, memarg);
+			}
+			Operator::I64AtomicRmw8CmpxchgU { memarg } => {
+				self.target
+					.add_atomic_cmpxchg(LoadType::I64_U8, StoreType::I64_N8, memarg);
+			}
+			Operator::I64AtomicRmw16CmpxchgU { memarg } => {
+				self.target
+					.add_atomic_cmpxchg(LoadType::I64_U16, StoreType::I64_N16, memarg);
+			}
+			Operator::I64AtomicRmw32CmpxchgU { memarg } => {
+				self.target
+					.add_atomic_cmpxchg(LoadType::I64_U32, StoreType::I64_N32, memarg);
+			}
+			Operator::MemoryAtomicWait32 { memarg } => {
+				self.target
+					.push_atomic_wait(LoadType::I32, CmpOpType::Ne_I32, memarg);
+			}
+			Operator::MemoryAtomicWait64 { memarg } => {
+				self.target
+					.push_atomic_wait(LoadType::I64, CmpOpType::Ne_I64, memarg);
+			}
+			Operator::MemoryAtomicNotify { .. } => self.target.push_atomic_notify(),
+			Operator::AtomicFence => {}
 			Operator::MemorySize { mem, .. } => {
 				let memory = mem.try_into().unwrap();
 				let data = Expression::MemorySize(MemorySize { memory });
@@ -640,30 +1492,126 @@ impl<'a> Factory<'a> {
 
 				self.target.code.push(data);
 			}
+			Operator::MemoryInit { data_index, mem } => {
+				let size = self.target.stack.pop().into();
+				let offset = self.target.stack.pop().into();
+
+				let destination = MemoryArgument {
+					memory: mem.try_into().unwrap(),
+					pointer: self.target.stack.pop().into(),
+				};
+
+				self.target.leak_memory_write(destination.memory);
+
+				let data = Statement::MemoryInit(MemoryInit {
+					destination,
+					data: data_index.try_into().unwrap(),
+					offset,
+					size,
+				});
+
+				self.target.code.push(data);
+			}
+			Operator::DataDrop { data_index } => {
+				let data = Statement::DataDrop(DataDrop {
+					data: data_index.try_into().unwrap(),
+				});
+
+				self.target.code.push(data);
+			}
 			Operator::I32Const { value } => self.target.push_constant(value),
 			Operator::I64Const { value } => self.target.push_constant(value),
 			Operator::F32Const { value } => self.target.push_constant(value.bits()),
 			Operator::F64Const { value } => self.target.push_constant(value.bits()),
-			_ => panic!("Unsupported instruction: {op:?}"),
+			Operator::V128Const { value } => {
+				let value = u128::from_le_bytes(*value.bytes());
+
+				self.target.push_constant(value);
+			}
+			Operator::I8x16ExtractLaneS { lane } => {
+				self.target
+					.push_extract_lane(ExtractLaneType::I32_I8X16, lane);
+			}
+			Operator::I8x16ExtractLaneU { lane } => {
+				self.target
+					.push_extract_lane(ExtractLaneType::I32_U8X16, lane);
+			}
+			Operator::I16x8ExtractLaneS { lane } => {
+				self.target
+					.push_extract_lane(ExtractLaneType::I32_I16X8, lane);
+			}
+			Operator::I16x8ExtractLaneU { lane } => {
+				self.target
+					.push_extract_lane(ExtractLaneType::I32_U16X8, lane);
+			}
+			Operator::I32x4ExtractLane { lane } => {
+				self.target.push_extract_lane(ExtractLaneType::I32X4, lane);
+			}
+			Operator::I64x2ExtractLane { lane } => {
+				self.target.push_extract_lane(ExtractLaneType::I64X2, lane);
+			}
+			Operator::F32x4ExtractLane { lane } => {
+				self.target.push_extract_lane(ExtractLaneType::F32X4, lane);
+			}
+			Operator::F64x2ExtractLane { lane } => {
+				self.target.push_extract_lane(ExtractLaneType::F64X2, lane);
+			}
+			Operator::I8x16ReplaceLane { lane } => {
+				self.target.push_replace_lane(ReplaceLaneType::I8X16, lane);
+			}
+			Operator::I16x8ReplaceLane { lane } => {
+				self.target.push_replace_lane(ReplaceLaneType::I16X8, lane);
+			}
+			Operator::I32x4ReplaceLane { lane } => {
+				self.target.push_replace_lane(ReplaceLaneType::I32X4, lane);
+			}
+			Operator::I64x2ReplaceLane { lane } => {
+				self.target.push_replace_lane(ReplaceLaneType::I64X2, lane);
+			}
+			Operator::F32x4ReplaceLane { lane } => {
+				self.target.push_replace_lane(ReplaceLaneType::F32X4, lane);
+			}
+			Operator::F64x2ReplaceLane { lane } => {
+				self.target.push_replace_lane(ReplaceLaneType::F64X2, lane);
+			}
+			_ => return Err(Error::unsupported(op)),
 		}
+
+		Ok(())
 	}
 
-	fn build_stat_list(&mut self, list: &[Operator], num_result: usize) -> StatList {
+	fn build_stat_list(
+		&mut self,
+		list: &[Operator],
+		offsets: &[usize],
+		num_result: usize,
+	) -> Result<StatList> {
+		if list.is_empty() {
+			return Err(Error::Malformed("function body is empty".into()));
+		}
+
 		self.target.block_data = BlockData::Forward { num_result };
 		self.nested_unreachable = 0;
 
-		for op in list.iter().take(list.len() - 1) {
+		for (i, op) in list.iter().enumerate().take(list.len() - 1) {
+			self.target.sync_offsets();
+			self.target.position = offsets.get(i).copied().unwrap_or_default();
+
 			if self.nested_unreachable == 0 {
-				self.add_instruction(op);
+				self.add_instruction(op)?;
 			} else {
-				self.drop_unreachable(op);
+				self.drop_unreachable(op)?;
 			}
 		}
 
+		if !self.pending.is_empty() {
+			return Err(Error::Malformed("function body has unclosed blocks".into()));
+		}
+
 		if self.nested_unreachable == 0 {
 			self.target.leak_all();
 		}
 
-		std::mem::take(&mut self.target)
+		Ok(std::mem::take(&mut self.target))
 	}
 }
@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use wasmparser::{
-	BlockType, Data, Element, Export, ExternalKind, FunctionBody, Global, Import, LocalsReader,
-	MemoryType, Name, NameSectionReader, Parser, Payload, Result, Table, Type, TypeRef, ValType,
+	BlockType, Data, Element, Export, ExternalKind, FuncType, FunctionBody, Global, Import,
+	LocalsReader, MemoryType, Name, NameSectionReader, Parser, Payload, Result, Table, Type,
+	TypeRef, ValType,
 };
 
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -47,7 +48,7 @@ where
 
 pub(crate) fn read_checked_locals(reader: LocalsReader) -> Result<Vec<ValType>> {
 	read_checked(reader).map(|locals| {
-		let convert = |(a, b)| std::iter::repeat(b).take(usize::try_from(a).unwrap());
+		let convert = |(a, b)| std::iter::repeat_n(b, usize::try_from(a).unwrap());
 
 		locals.into_iter().flat_map(convert).collect()
 	})
@@ -65,7 +66,9 @@ pub struct Module<'a> {
 	data_section: Vec<Data<'a>>,
 	code_section: Vec<FunctionBody<'a>>,
 
-	name_section: HashMap<u32, &'a str>,
+	name_section: BTreeMap<u32, &'a str>,
+	local_name_section: BTreeMap<u32, BTreeMap<u32, &'a str>>,
+	global_name_section: BTreeMap<u32, &'a str>,
 
 	start_section: Option<u32>,
 }
@@ -86,7 +89,9 @@ impl<'a> Module<'a> {
 			element_section: Vec::new(),
 			data_section: Vec::new(),
 			code_section: Vec::new(),
-			name_section: HashMap::new(),
+			name_section: BTreeMap::new(),
+			local_name_section: BTreeMap::new(),
+			global_name_section: BTreeMap::new(),
 			start_section: None,
 		};
 
@@ -114,11 +119,31 @@ impl<'a> Module<'a> {
 				}
 				Payload::CustomSection(v) if v.name() == "name" => {
 					for name in NameSectionReader::new(v.data(), v.data_offset()) {
-						if let Name::Function(map) = name? {
-							let mut iter = map.into_iter();
-							while let Some(Ok(elem)) = iter.next() {
-								self.name_section.insert(elem.index, elem.name);
+						match name? {
+							Name::Function(map) => {
+								let mut iter = map.into_iter();
+								while let Some(Ok(elem)) = iter.next() {
+									self.name_section.insert(elem.index, elem.name);
+								}
 							}
+							Name::Local(map) => {
+								let mut iter = map.into_iter();
+								while let Some(Ok(func)) = iter.next() {
+									let entry =
+										self.local_name_section.entry(func.index).or_default();
+									let mut iter = func.names.into_iter();
+									while let Some(Ok(elem)) = iter.next() {
+										entry.insert(elem.index, elem.name);
+									}
+								}
+							}
+							Name::Global(map) => {
+								let mut iter = map.into_iter();
+								while let Some(Ok(elem)) = iter.next() {
+									self.global_name_section.insert(elem.index, elem.name);
+								}
+							}
+							_ => {}
 						}
 					}
 				}
@@ -156,13 +181,23 @@ impl<'a> Module<'a> {
 		self.import_count(External::Global) + self.global_section.len()
 	}
 
+	#[must_use]
+	pub fn element_space(&self) -> usize {
+		self.element_section.len()
+	}
+
+	#[must_use]
+	pub fn data_space(&self) -> usize {
+		self.data_section.len()
+	}
+
 	#[must_use]
 	pub fn type_section(&self) -> &[Type] {
 		&self.type_section
 	}
 
 	#[must_use]
-	pub fn import_section(&self) -> &[Import] {
+	pub fn import_section(&self) -> &[Import<'_>] {
 		&self.import_section
 	}
 
@@ -172,7 +207,7 @@ impl<'a> Module<'a> {
 	}
 
 	#[must_use]
-	pub fn table_section(&self) -> &[Table] {
+	pub fn table_section(&self) -> &[Table<'_>] {
 		&self.table_section
 	}
 
@@ -182,35 +217,45 @@ impl<'a> Module<'a> {
 	}
 
 	#[must_use]
-	pub fn global_section(&self) -> &[Global] {
+	pub fn global_section(&self) -> &[Global<'_>] {
 		&self.global_section
 	}
 
 	#[must_use]
-	pub fn export_section(&self) -> &[Export] {
+	pub fn export_section(&self) -> &[Export<'_>] {
 		&self.export_section
 	}
 
 	#[must_use]
-	pub fn element_section(&self) -> &[Element] {
+	pub fn element_section(&self) -> &[Element<'_>] {
 		&self.element_section
 	}
 
 	#[must_use]
-	pub fn data_section(&self) -> &[Data] {
+	pub fn data_section(&self) -> &[Data<'_>] {
 		&self.data_section
 	}
 
 	#[must_use]
-	pub fn code_section(&self) -> &[FunctionBody] {
+	pub fn code_section(&self) -> &[FunctionBody<'_>] {
 		&self.code_section
 	}
 
 	#[must_use]
-	pub const fn name_section(&self) -> &HashMap<u32, &'a str> {
+	pub const fn name_section(&self) -> &BTreeMap<u32, &'a str> {
 		&self.name_section
 	}
 
+	#[must_use]
+	pub const fn local_name_section(&self) -> &BTreeMap<u32, BTreeMap<u32, &'a str>> {
+		&self.local_name_section
+	}
+
+	#[must_use]
+	pub const fn global_name_section(&self) -> &BTreeMap<u32, &'a str> {
+		&self.global_name_section
+	}
+
 	#[must_use]
 	pub const fn start_section(&self) -> Option<u32> {
 		self.start_section
@@ -254,11 +299,34 @@ impl<'a> TypeInfo<'a> {
 		self.func_list.extend(iter);
 	}
 
-	pub(crate) fn by_type_index(&self, index: usize) -> (usize, usize) {
+	fn func_type_at(&self, index: usize) -> &FuncType {
 		let Type::Func(ty) = &self.type_list[index] else {
 			unreachable!("type at func index must be a func type");
 		};
 
+		ty
+	}
+
+	/// Signature of the function at `index`, counting imported
+	/// functions first.
+	#[must_use]
+	pub fn func_type(&self, index: usize) -> &FuncType {
+		self.func_type_at(self.func_list[index])
+	}
+
+	pub(crate) fn has_func_type(&self, index: usize) -> bool {
+		matches!(self.type_list.get(index), Some(Type::Func(_)))
+	}
+
+	pub(crate) fn has_func_index(&self, index: usize) -> bool {
+		self.func_list
+			.get(index)
+			.is_some_and(|&index| self.has_func_type(index))
+	}
+
+	pub(crate) fn by_type_index(&self, index: usize) -> (usize, usize) {
+		let ty = self.func_type_at(index);
+
 		(ty.params().len(), ty.results().len())
 	}
 
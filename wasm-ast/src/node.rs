@@ -17,6 +17,7 @@ pub enum LoadType {
 	I64_U16,
 	I64_I32,
 	I64_U32,
+	V128,
 }
 
 impl TryFrom<&Operator<'_>> for LoadType {
@@ -38,6 +39,7 @@ impl TryFrom<&Operator<'_>> for LoadType {
 			Operator::I64Load16U { .. } => Self::I64_U16,
 			Operator::I64Load32S { .. } => Self::I64_I32,
 			Operator::I64Load32U { .. } => Self::I64_U32,
+			Operator::V128Load { .. } => Self::V128,
 			_ => return Err(()),
 		};
 
@@ -57,6 +59,7 @@ pub enum StoreType {
 	I64_N8,
 	I64_N16,
 	I64_N32,
+	V128,
 }
 
 impl TryFrom<&Operator<'_>> for StoreType {
@@ -73,6 +76,7 @@ impl TryFrom<&Operator<'_>> for StoreType {
 			Operator::I64Store8 { .. } => Self::I64_N8,
 			Operator::I64Store16 { .. } => Self::I64_N16,
 			Operator::I64Store32 { .. } => Self::I64_N32,
+			Operator::V128Store { .. } => Self::V128,
 			_ => return Err(()),
 		};
 
@@ -143,6 +147,13 @@ pub enum UnOpType {
 	Reinterpret_I64_F64,
 	Reinterpret_F32_I32,
 	Reinterpret_F64_I64,
+	Splat_I8X16,
+	Splat_I16X8,
+	Splat_I32X4,
+	Splat_I64X2,
+	Splat_F32X4,
+	Splat_F64X2,
+	Not_V128,
 }
 
 impl TryFrom<&Operator<'_>> for UnOpType {
@@ -208,6 +219,13 @@ impl TryFrom<&Operator<'_>> for UnOpType {
 			Operator::I64ReinterpretF64 => Self::Reinterpret_I64_F64,
 			Operator::F32ReinterpretI32 => Self::Reinterpret_F32_I32,
 			Operator::F64ReinterpretI64 => Self::Reinterpret_F64_I64,
+			Operator::I8x16Splat => Self::Splat_I8X16,
+			Operator::I16x8Splat => Self::Splat_I16X8,
+			Operator::I32x4Splat => Self::Splat_I32X4,
+			Operator::I64x2Splat => Self::Splat_I64X2,
+			Operator::F32x4Splat => Self::Splat_F32X4,
+			Operator::F64x2Splat => Self::Splat_F64X2,
+			Operator::V128Not => Self::Not_V128,
 			_ => return Err(()),
 		};
 
@@ -262,6 +280,21 @@ pub enum BinOpType {
 	Min_F64,
 	Max_F64,
 	Copysign_F64,
+	And_V128,
+	AndNot_V128,
+	Or_V128,
+	Xor_V128,
+	Add_I8X16,
+	Sub_I8X16,
+	Add_I16X8,
+	Sub_I16X8,
+	Mul_I16X8,
+	Add_I32X4,
+	Sub_I32X4,
+	Mul_I32X4,
+	Add_I64X2,
+	Sub_I64X2,
+	Mul_I64X2,
 }
 
 impl TryFrom<&Operator<'_>> for BinOpType {
@@ -313,6 +346,21 @@ impl TryFrom<&Operator<'_>> for BinOpType {
 			Operator::F64Min => Self::Min_F64,
 			Operator::F64Max => Self::Max_F64,
 			Operator::F64Copysign => Self::Copysign_F64,
+			Operator::V128And => Self::And_V128,
+			Operator::V128AndNot => Self::AndNot_V128,
+			Operator::V128Or => Self::Or_V128,
+			Operator::V128Xor => Self::Xor_V128,
+			Operator::I8x16Add => Self::Add_I8X16,
+			Operator::I8x16Sub => Self::Sub_I8X16,
+			Operator::I16x8Add => Self::Add_I16X8,
+			Operator::I16x8Sub => Self::Sub_I16X8,
+			Operator::I16x8Mul => Self::Mul_I16X8,
+			Operator::I32x4Add => Self::Add_I32X4,
+			Operator::I32x4Sub => Self::Sub_I32X4,
+			Operator::I32x4Mul => Self::Mul_I32X4,
+			Operator::I64x2Add => Self::Add_I64X2,
+			Operator::I64x2Sub => Self::Sub_I64X2,
+			Operator::I64x2Mul => Self::Mul_I64X2,
 			_ => {
 				return Err(());
 			}
@@ -405,13 +453,44 @@ impl TryFrom<&Operator<'_>> for CmpOpType {
 	}
 }
 
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub enum ExtractLaneType {
+	I32_I8X16,
+	I32_U8X16,
+	I32_I16X8,
+	I32_U16X8,
+	I32X4,
+	I64X2,
+	F32X4,
+	F64X2,
+}
+
+#[derive(Clone, Copy)]
+pub enum ReplaceLaneType {
+	I8X16,
+	I16X8,
+	I32X4,
+	I64X2,
+	F32X4,
+	F64X2,
+}
+
 pub struct Select {
+	pub(crate) ty: Option<ValType>,
 	pub(crate) condition: Box<Expression>,
 	pub(crate) on_true: Box<Expression>,
 	pub(crate) on_false: Box<Expression>,
 }
 
 impl Select {
+	/// The operand type given by the typed form of `select`, which is the
+	/// only form that can choose between references.
+	#[must_use]
+	pub const fn ty(&self) -> Option<ValType> {
+		self.ty
+	}
+
 	#[must_use]
 	pub const fn condition(&self) -> &Expression {
 		&self.condition
@@ -511,6 +590,9 @@ pub enum Value {
 	I64(i64),
 	F32(f32),
 	F64(f64),
+	V128(u128),
+	RefNull,
+	RefFunc(usize),
 }
 
 impl From<i32> for Value {
@@ -537,6 +619,52 @@ impl From<u64> for Value {
 	}
 }
 
+impl From<u128> for Value {
+	fn from(value: u128) -> Self {
+		Self::V128(value)
+	}
+}
+
+pub struct RefIsNull {
+	pub(crate) value: Box<Expression>,
+}
+
+impl RefIsNull {
+	#[must_use]
+	pub const fn value(&self) -> &Expression {
+		&self.value
+	}
+}
+
+pub struct TableGet {
+	pub(crate) table: usize,
+	pub(crate) index: Box<Expression>,
+}
+
+impl TableGet {
+	#[must_use]
+	pub const fn table(&self) -> usize {
+		self.table
+	}
+
+	#[must_use]
+	pub const fn index(&self) -> &Expression {
+		&self.index
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct TableSize {
+	pub(crate) table: usize,
+}
+
+impl TableSize {
+	#[must_use]
+	pub const fn table(self) -> usize {
+		self.table
+	}
+}
+
 pub struct UnOp {
 	pub(crate) op_type: UnOpType,
 	pub(crate) rhs: Box<Expression>,
@@ -577,6 +705,58 @@ impl BinOp {
 	}
 }
 
+pub struct ExtractLane {
+	pub(crate) op_type: ExtractLaneType,
+	pub(crate) lane: u8,
+	pub(crate) rhs: Box<Expression>,
+}
+
+impl ExtractLane {
+	#[must_use]
+	pub const fn op_type(&self) -> ExtractLaneType {
+		self.op_type
+	}
+
+	#[must_use]
+	pub const fn lane(&self) -> u8 {
+		self.lane
+	}
+
+	#[must_use]
+	pub const fn rhs(&self) -> &Expression {
+		&self.rhs
+	}
+}
+
+pub struct ReplaceLane {
+	pub(crate) op_type: ReplaceLaneType,
+	pub(crate) lane: u8,
+	pub(crate) lhs: Box<Expression>,
+	pub(crate) rhs: Box<Expression>,
+}
+
+impl ReplaceLane {
+	#[must_use]
+	pub const fn op_type(&self) -> ReplaceLaneType {
+		self.op_type
+	}
+
+	#[must_use]
+	pub const fn lane(&self) -> u8 {
+		self.lane
+	}
+
+	#[must_use]
+	pub const fn lhs(&self) -> &Expression {
+		&self.lhs
+	}
+
+	#[must_use]
+	pub const fn rhs(&self) -> &Expression {
+		&self.rhs
+	}
+}
+
 pub struct CmpOp {
 	pub(crate) op_type: CmpOpType,
 	pub(crate) lhs: Box<Expression>,
@@ -608,9 +788,14 @@ pub enum Expression {
 	LoadAt(LoadAt),
 	MemorySize(MemorySize),
 	Value(Value),
+	RefIsNull(RefIsNull),
+	TableGet(TableGet),
+	TableSize(TableSize),
 	UnOp(UnOp),
 	BinOp(BinOp),
 	CmpOp(CmpOp),
+	ExtractLane(ExtractLane),
+	ReplaceLane(ReplaceLane),
 }
 
 #[derive(Clone, Copy)]
@@ -710,12 +895,15 @@ pub enum Terminator {
 	Unreachable,
 	Br(Br),
 	BrTable(BrTable),
+	ReturnCall(Call),
+	ReturnCallIndirect(CallIndirect),
 }
 
 #[derive(Default)]
 pub struct Block {
 	pub(crate) label_type: Option<LabelType>,
 	pub(crate) code: Vec<Statement>,
+	pub(crate) offsets: Vec<usize>,
 	pub(crate) last: Option<Box<Terminator>>,
 }
 
@@ -730,6 +918,14 @@ impl Block {
 		&self.code
 	}
 
+	/// Byte offsets within the module of the instruction each statement
+	/// in `code` was translated from. Constant expressions have no
+	/// offsets and report 0.
+	#[must_use]
+	pub fn offsets(&self) -> &[usize] {
+		&self.offsets
+	}
+
 	#[must_use]
 	pub fn last(&self) -> Option<&Terminator> {
 		self.last.as_deref()
@@ -800,6 +996,7 @@ impl Call {
 }
 
 pub struct CallIndirect {
+	pub(crate) ty: usize,
 	pub(crate) table: usize,
 	pub(crate) index: Box<Expression>,
 	pub(crate) param_list: Vec<Expression>,
@@ -807,6 +1004,11 @@ pub struct CallIndirect {
 }
 
 impl CallIndirect {
+	#[must_use]
+	pub const fn ty(&self) -> usize {
+		self.ty
+	}
+
 	#[must_use]
 	pub const fn table(&self) -> usize {
 		self.table
@@ -1000,6 +1202,210 @@ impl MemoryFill {
 	}
 }
 
+pub struct MemoryInit {
+	pub(crate) destination: MemoryArgument,
+	pub(crate) data: usize,
+	pub(crate) offset: Box<Expression>,
+	pub(crate) size: Box<Expression>,
+}
+
+impl MemoryInit {
+	#[must_use]
+	pub const fn destination(&self) -> &MemoryArgument {
+		&self.destination
+	}
+
+	#[must_use]
+	pub const fn data(&self) -> usize {
+		self.data
+	}
+
+	#[must_use]
+	pub const fn offset(&self) -> &Expression {
+		&self.offset
+	}
+
+	#[must_use]
+	pub const fn size(&self) -> &Expression {
+		&self.size
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct DataDrop {
+	pub(crate) data: usize,
+}
+
+impl DataDrop {
+	#[must_use]
+	pub const fn data(self) -> usize {
+		self.data
+	}
+}
+
+pub struct TableSet {
+	pub(crate) table: usize,
+	pub(crate) index: Box<Expression>,
+	pub(crate) value: Box<Expression>,
+}
+
+impl TableSet {
+	#[must_use]
+	pub const fn table(&self) -> usize {
+		self.table
+	}
+
+	#[must_use]
+	pub const fn index(&self) -> &Expression {
+		&self.index
+	}
+
+	#[must_use]
+	pub const fn value(&self) -> &Expression {
+		&self.value
+	}
+}
+
+pub struct TableInit {
+	pub(crate) table: usize,
+	pub(crate) element: usize,
+	pub(crate) destination: Box<Expression>,
+	pub(crate) offset: Box<Expression>,
+	pub(crate) size: Box<Expression>,
+}
+
+impl TableInit {
+	#[must_use]
+	pub const fn table(&self) -> usize {
+		self.table
+	}
+
+	#[must_use]
+	pub const fn element(&self) -> usize {
+		self.element
+	}
+
+	#[must_use]
+	pub const fn destination(&self) -> &Expression {
+		&self.destination
+	}
+
+	#[must_use]
+	pub const fn offset(&self) -> &Expression {
+		&self.offset
+	}
+
+	#[must_use]
+	pub const fn size(&self) -> &Expression {
+		&self.size
+	}
+}
+
+pub struct TableGrow {
+	pub(crate) table: usize,
+	pub(crate) result: Temporary,
+	pub(crate) value: Box<Expression>,
+	pub(crate) size: Box<Expression>,
+}
+
+impl TableGrow {
+	#[must_use]
+	pub const fn table(&self) -> usize {
+		self.table
+	}
+
+	#[must_use]
+	pub const fn result(&self) -> Temporary {
+		self.result
+	}
+
+	#[must_use]
+	pub const fn value(&self) -> &Expression {
+		&self.value
+	}
+
+	#[must_use]
+	pub const fn size(&self) -> &Expression {
+		&self.size
+	}
+}
+
+pub struct TableFill {
+	pub(crate) table: usize,
+	pub(crate) destination: Box<Expression>,
+	pub(crate) value: Box<Expression>,
+	pub(crate) size: Box<Expression>,
+}
+
+impl TableFill {
+	#[must_use]
+	pub const fn table(&self) -> usize {
+		self.table
+	}
+
+	#[must_use]
+	pub const fn destination(&self) -> &Expression {
+		&self.destination
+	}
+
+	#[must_use]
+	pub const fn value(&self) -> &Expression {
+		&self.value
+	}
+
+	#[must_use]
+	pub const fn size(&self) -> &Expression {
+		&self.size
+	}
+}
+
+pub struct TableCopy {
+	pub(crate) destination_table: usize,
+	pub(crate) source_table: usize,
+	pub(crate) destination: Box<Expression>,
+	pub(crate) source: Box<Expression>,
+	pub(crate) size: Box<Expression>,
+}
+
+impl TableCopy {
+	#[must_use]
+	pub const fn destination_table(&self) -> usize {
+		self.destination_table
+	}
+
+	#[must_use]
+	pub const fn source_table(&self) -> usize {
+		self.source_table
+	}
+
+	#[must_use]
+	pub const fn destination(&self) -> &Expression {
+		&self.destination
+	}
+
+	#[must_use]
+	pub const fn source(&self) -> &Expression {
+		&self.source
+	}
+
+	#[must_use]
+	pub const fn size(&self) -> &Expression {
+		&self.size
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct ElemDrop {
+	pub(crate) element: usize,
+}
+
+impl ElemDrop {
+	#[must_use]
+	pub const fn element(self) -> usize {
+		self.element
+	}
+}
+
 pub enum Statement {
 	Block(Block),
 	BrIf(BrIf),
@@ -1013,9 +1419,18 @@ pub enum Statement {
 	MemoryGrow(MemoryGrow),
 	MemoryCopy(MemoryCopy),
 	MemoryFill(MemoryFill),
+	MemoryInit(MemoryInit),
+	DataDrop(DataDrop),
+	TableSet(TableSet),
+	TableInit(TableInit),
+	TableGrow(TableGrow),
+	TableFill(TableFill),
+	TableCopy(TableCopy),
+	ElemDrop(ElemDrop),
 }
 
 pub struct FuncData {
+	pub(crate) param_data: Vec<ValType>,
 	pub(crate) local_data: Vec<ValType>,
 	pub(crate) num_result: usize,
 	pub(crate) num_param: usize,
@@ -1024,6 +1439,12 @@ pub struct FuncData {
 }
 
 impl FuncData {
+	/// Types of the parameters, which are empty for anonymous code.
+	#[must_use]
+	pub fn param_data(&self) -> &[ValType] {
+		&self.param_data
+	}
+
 	#[must_use]
 	pub fn local_data(&self) -> &[ValType] {
 		&self.local_data
@@ -1,28 +1,38 @@
 use crate::{
 	node::{
-		Align, Expression, GetGlobal, LoadAt, Local, ResultList, SetTemporary, Statement, Temporary,
+		Align, Expression, GetGlobal, LoadAt, Local, ResultList, SetTemporary, Statement, TableGet,
+		TableSize, Temporary,
 	},
 	visit::{Driver, Visitor},
 };
 
-pub struct ReadGet<A, B, C> {
+pub struct ReadGet<A, B, C, D> {
 	has_local: A,
 	has_global: B,
 	has_memory: C,
+	has_table: D,
 	result: bool,
 }
 
-impl<A, B, C> ReadGet<A, B, C>
+impl<A, B, C, D> ReadGet<A, B, C, D>
 where
 	A: Fn(Local) -> bool,
 	B: Fn(GetGlobal) -> bool,
 	C: Fn(&LoadAt) -> bool,
+	D: Fn(usize) -> bool,
 {
-	pub fn run<D: Driver<Self>>(node: &D, has_local: A, has_global: B, has_memory: C) -> bool {
+	pub fn run<E: Driver<Self>>(
+		node: &E,
+		has_local: A,
+		has_global: B,
+		has_memory: C,
+		has_table: D,
+	) -> bool {
 		let mut visitor = Self {
 			has_local,
 			has_global,
 			has_memory,
+			has_table,
 			result: false,
 		};
 
@@ -32,11 +42,12 @@ where
 	}
 }
 
-impl<A, B, C> Visitor for ReadGet<A, B, C>
+impl<A, B, C, D> Visitor for ReadGet<A, B, C, D>
 where
 	A: Fn(Local) -> bool,
 	B: Fn(GetGlobal) -> bool,
 	C: Fn(&LoadAt) -> bool,
+	D: Fn(usize) -> bool,
 {
 	fn visit_get_global(&mut self, get_global: GetGlobal) {
 		self.result |= (self.has_global)(get_global);
@@ -49,6 +60,14 @@ where
 	fn visit_get_local(&mut self, local: Local) {
 		self.result |= (self.has_local)(local);
 	}
+
+	fn visit_table_get(&mut self, table_get: &TableGet) {
+		self.result |= (self.has_table)(table_get.table());
+	}
+
+	fn visit_table_size(&mut self, table_size: TableSize) {
+		self.result |= (self.has_table)(table_size.table());
+	}
 }
 
 #[derive(Default)]
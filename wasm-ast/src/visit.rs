@@ -1,7 +1,9 @@
 use crate::node::{
-	BinOp, Block, Br, BrIf, BrTable, Call, CallIndirect, CmpOp, Expression, FuncData, GetGlobal,
-	If, LoadAt, Local, MemoryCopy, MemoryFill, MemoryGrow, MemorySize, Select, SetGlobal, SetLocal,
-	SetTemporary, Statement, StoreAt, Temporary, Terminator, UnOp, Value,
+	BinOp, Block, Br, BrIf, BrTable, Call, CallIndirect, CmpOp, DataDrop, ElemDrop, Expression,
+	ExtractLane, FuncData, GetGlobal, If, LoadAt, Local, MemoryCopy, MemoryFill, MemoryGrow,
+	MemoryInit, MemorySize, RefIsNull, ReplaceLane, Select, SetGlobal, SetLocal, SetTemporary,
+	Statement, StoreAt, TableCopy, TableFill, TableGet, TableGrow, TableInit, TableSet, TableSize,
+	Temporary, Terminator, UnOp, Value,
 };
 
 pub trait Visitor {
@@ -19,12 +21,22 @@ pub trait Visitor {
 
 	fn visit_value(&mut self, _: Value) {}
 
+	fn visit_ref_is_null(&mut self, _: &RefIsNull) {}
+
+	fn visit_table_get(&mut self, _: &TableGet) {}
+
+	fn visit_table_size(&mut self, _: TableSize) {}
+
 	fn visit_un_op(&mut self, _: &UnOp) {}
 
 	fn visit_bin_op(&mut self, _: &BinOp) {}
 
 	fn visit_cmp_op(&mut self, _: &CmpOp) {}
 
+	fn visit_extract_lane(&mut self, _: &ExtractLane) {}
+
+	fn visit_replace_lane(&mut self, _: &ReplaceLane) {}
+
 	fn visit_expression(&mut self, _: &Expression) {}
 
 	fn visit_unreachable(&mut self) {}
@@ -59,6 +71,22 @@ pub trait Visitor {
 
 	fn visit_memory_fill(&mut self, _: &MemoryFill) {}
 
+	fn visit_memory_init(&mut self, _: &MemoryInit) {}
+
+	fn visit_data_drop(&mut self, _: DataDrop) {}
+
+	fn visit_table_set(&mut self, _: &TableSet) {}
+
+	fn visit_table_init(&mut self, _: &TableInit) {}
+
+	fn visit_table_grow(&mut self, _: &TableGrow) {}
+
+	fn visit_table_fill(&mut self, _: &TableFill) {}
+
+	fn visit_table_copy(&mut self, _: &TableCopy) {}
+
+	fn visit_elem_drop(&mut self, _: ElemDrop) {}
+
 	fn visit_statement(&mut self, _: &Statement) {}
 }
 
@@ -128,12 +156,50 @@ impl<T: Visitor> Driver<T> for MemoryFill {
 	}
 }
 
+impl<T: Visitor> Driver<T> for MemoryInit {
+	fn accept(&self, visitor: &mut T) {
+		self.destination().pointer().accept(visitor);
+		self.offset().accept(visitor);
+		self.size().accept(visitor);
+
+		visitor.visit_memory_init(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for DataDrop {
+	fn accept(&self, visitor: &mut T) {
+		visitor.visit_data_drop(*self);
+	}
+}
+
 impl<T: Visitor> Driver<T> for Value {
 	fn accept(&self, visitor: &mut T) {
 		visitor.visit_value(*self);
 	}
 }
 
+impl<T: Visitor> Driver<T> for RefIsNull {
+	fn accept(&self, visitor: &mut T) {
+		self.value().accept(visitor);
+
+		visitor.visit_ref_is_null(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for TableGet {
+	fn accept(&self, visitor: &mut T) {
+		self.index().accept(visitor);
+
+		visitor.visit_table_get(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for TableSize {
+	fn accept(&self, visitor: &mut T) {
+		visitor.visit_table_size(*self);
+	}
+}
+
 impl<T: Visitor> Driver<T> for UnOp {
 	fn accept(&self, visitor: &mut T) {
 		self.rhs().accept(visitor);
@@ -160,6 +226,23 @@ impl<T: Visitor> Driver<T> for CmpOp {
 	}
 }
 
+impl<T: Visitor> Driver<T> for ExtractLane {
+	fn accept(&self, visitor: &mut T) {
+		self.rhs().accept(visitor);
+
+		visitor.visit_extract_lane(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for ReplaceLane {
+	fn accept(&self, visitor: &mut T) {
+		self.lhs().accept(visitor);
+		self.rhs().accept(visitor);
+
+		visitor.visit_replace_lane(self);
+	}
+}
+
 impl<T: Visitor> Driver<T> for Expression {
 	fn accept(&self, visitor: &mut T) {
 		match self {
@@ -170,9 +253,14 @@ impl<T: Visitor> Driver<T> for Expression {
 			Self::LoadAt(v) => v.accept(visitor),
 			Self::MemorySize(v) => v.accept(visitor),
 			Self::Value(v) => v.accept(visitor),
+			Self::RefIsNull(v) => v.accept(visitor),
+			Self::TableGet(v) => v.accept(visitor),
+			Self::TableSize(v) => v.accept(visitor),
 			Self::UnOp(v) => v.accept(visitor),
 			Self::BinOp(v) => v.accept(visitor),
 			Self::CmpOp(v) => v.accept(visitor),
+			Self::ExtractLane(v) => v.accept(visitor),
+			Self::ReplaceLane(v) => v.accept(visitor),
 		}
 
 		visitor.visit_expression(self);
@@ -199,6 +287,8 @@ impl<T: Visitor> Driver<T> for Terminator {
 			Self::Unreachable => visitor.visit_unreachable(),
 			Self::Br(v) => v.accept(visitor),
 			Self::BrTable(v) => v.accept(visitor),
+			Self::ReturnCall(v) => v.accept(visitor),
+			Self::ReturnCallIndirect(v) => v.accept(visitor),
 		}
 
 		visitor.visit_terminator(self);
@@ -303,6 +393,60 @@ impl<T: Visitor> Driver<T> for MemoryGrow {
 	}
 }
 
+impl<T: Visitor> Driver<T> for TableSet {
+	fn accept(&self, visitor: &mut T) {
+		self.index().accept(visitor);
+		self.value().accept(visitor);
+
+		visitor.visit_table_set(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for TableInit {
+	fn accept(&self, visitor: &mut T) {
+		self.destination().accept(visitor);
+		self.offset().accept(visitor);
+		self.size().accept(visitor);
+
+		visitor.visit_table_init(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for TableGrow {
+	fn accept(&self, visitor: &mut T) {
+		self.value().accept(visitor);
+		self.size().accept(visitor);
+
+		visitor.visit_table_grow(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for TableFill {
+	fn accept(&self, visitor: &mut T) {
+		self.destination().accept(visitor);
+		self.value().accept(visitor);
+		self.size().accept(visitor);
+
+		visitor.visit_table_fill(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for TableCopy {
+	fn accept(&self, visitor: &mut T) {
+		self.destination().accept(visitor);
+		self.source().accept(visitor);
+		self.size().accept(visitor);
+
+		visitor.visit_table_copy(self);
+	}
+}
+
+impl<T: Visitor> Driver<T> for ElemDrop {
+	fn accept(&self, visitor: &mut T) {
+		visitor.visit_elem_drop(*self);
+	}
+}
+
 impl<T: Visitor> Driver<T> for Statement {
 	fn accept(&self, visitor: &mut T) {
 		match self {
@@ -318,6 +462,14 @@ impl<T: Visitor> Driver<T> for Statement {
 			Self::MemoryGrow(v) => v.accept(visitor),
 			Self::MemoryCopy(v) => v.accept(visitor),
 			Self::MemoryFill(v) => v.accept(visitor),
+			Self::MemoryInit(v) => v.accept(visitor),
+			Self::DataDrop(v) => v.accept(visitor),
+			Self::TableSet(v) => v.accept(visitor),
+			Self::TableInit(v) => v.accept(visitor),
+			Self::TableGrow(v) => v.accept(visitor),
+			Self::TableFill(v) => v.accept(visitor),
+			Self::TableCopy(v) => v.accept(visitor),
+			Self::ElemDrop(v) => v.accept(visitor),
 		}
 
 		visitor.visit_statement(self);